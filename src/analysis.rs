@@ -1,8 +1,18 @@
 use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
 use std::fs::File;
-use std::io::{BufReader, BufRead};
+use std::io::BufRead;
+use amzn_career_pathway_activity_rust::events::{
+    AccessEvent, EventGenerator, ImpossibleTravelerMetadata, MAX_READER_RETRY_DELAY_SECONDS,
+};
+use amzn_career_pathway_activity_rust::facility::LocationRegistry;
+use amzn_career_pathway_activity_rust::simulation::{
+    open_input_file, EventSink, SimulationResult, TimeManager, DEFAULT_VARIANCE_WINDOW_SECONDS,
+};
+use amzn_career_pathway_activity_rust::types::{BuildingId, EventType, RoomId, SecurityLevel, SimulationConfig, UserId};
 use serde::{Serialize, Deserialize};
-use chrono::Utc;
+use chrono::{DateTime, Duration, NaiveDate, Utc};
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct UserAnomaly {
@@ -12,67 +22,500 @@ pub struct UserAnomaly {
     pub issue: String,
     pub severity: String, // "low", "medium", "high"
     pub timestamp: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub evidence: Option<AnomalyEvidence>,
 }
 
-pub fn detect_cloned_badges(path: &str) -> Vec<UserAnomaly> {
-    let file = File::open(path).expect("Cannot open user profile JSON");
-    let reader = BufReader::new(file);
-    let mut anomalies = Vec::new();
+/// Events and measurements backing an anomaly
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AnomalyEvidence {
+    /// Consecutive badge events too far apart to travel between in the time available
+    ImpossibleTravel { findings: Vec<ImpossibleTravelFinding> },
+    /// Denied attempts on rooms the badge does not open, scored against building peers
+    UnauthorizedAccess(CuriousUserScore),
+    /// Denied attempts on rooms the badge does not open within a sliding window
+    RecentDenials { window_hours: i64, weighted_attempts: f64, attempts: Vec<AccessEvent> },
+}
 
-    for line in reader.lines() {
-        let line = line.expect("Failed to read line");
-        let user: Value = serde_json::from_str(&line).expect("Cannot parse JSON line");
-        let user_id = user["user_id"].as_str().unwrap_or("unknown").to_string();
+/// Two consecutive events of one badge at locations that cannot be travelled between in time
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ImpossibleTravelFinding {
+    pub first_event: AccessEvent,
+    pub second_event: AccessEvent,
+    /// Distance, time gap, minimum travel time and impossibility factor of the trip
+    pub travel: ImpossibleTravelerMetadata,
+}
 
-        if user["has_cloned_badge"].as_bool().unwrap_or(false) {
-            anomalies.push(UserAnomaly {
-                user_id,
-                anomaly_type: "cloned_badge".to_string(),
-                details: "User has a cloned badge assigned.".to_string(),
-                issue: "Multiple active badge IDs detected".to_string(),
-                severity: "high".to_string(),
-                timestamp: Utc::now().to_rfc3339(),
-            });
+/// Cloned-badge detector that watches the event stream for impossible travel
+///
+/// Each user's last event is kept, and every new event at a different location is
+/// checked against the simulator's travel-time model (`EventGenerator`). Only the
+/// observable fields of the events are used; the simulator's metadata, failure reason
+/// and event type labels are dropped before an event is stored so findings cannot echo
+/// the answer key.
+#[derive(Debug)]
+pub struct ClonedBadgeDetector {
+    travel_model: EventGenerator,
+    location_registry: LocationRegistry,
+    last_events: HashMap<UserId, AccessEvent>,
+    findings: Vec<ImpossibleTravelFinding>,
+}
+
+impl ClonedBadgeDetector {
+    pub fn new(config: &SimulationConfig, location_registry: LocationRegistry) -> Self {
+        Self {
+            travel_model: EventGenerator::new(config.clone(), location_registry.clone(), TimeManager::new()),
+            location_registry,
+            last_events: HashMap::new(),
+            findings: Vec::new(),
         }
     }
 
-    anomalies
+    /// Check an event against the user's previous one, returning the finding it raised
+    pub fn observe(&mut self, event: &AccessEvent) -> Option<&ImpossibleTravelFinding> {
+        let event = observable_event(event);
+        let previous = self.last_events.insert(event.user_id, event.clone())?;
+
+        let finding = find_impossible_travel(&self.travel_model, &self.location_registry, previous, event)?;
+        self.findings.push(finding);
+        self.findings.last()
+    }
+
+    /// All findings raised so far, in detection order
+    pub fn findings(&self) -> &[ImpossibleTravelFinding] {
+        &self.findings
+    }
 }
 
-pub fn detect_curious_users(path: &str) -> Vec<UserAnomaly> {
-    let file = File::open(path).expect("Cannot open user profile JSON");
-    let reader = BufReader::new(file);
-    let mut anomalies = Vec::new();
+/// Copy of an event with only what a badge system records
+///
+/// The simulator's metadata, failure reason and event type label the scenario that
+/// produced the event (such as `CuriousUser` or `ImpossibleTraveler`), so they are dropped
+/// and the event type is set from the success flag alone.
+pub fn observable_event(event: &AccessEvent) -> AccessEvent {
+    AccessEvent {
+        event_type: if event.success { EventType::Success } else { EventType::Failure },
+        failure_reason: None,
+        metadata: None,
+        ..event.clone()
+    }
+}
 
-    for line in reader.lines() {
-        let line = line.expect("Failed to read line");
-        let user: Value = serde_json::from_str(&line).expect("Cannot parse JSON line");
-        let user_id = user["user_id"].as_str().unwrap_or("unknown").to_string();
+/// Check two consecutive events of a user against the travel-time model
+///
+/// Returns a finding when the second event is at a different location that cannot be
+/// reached from the first in the time between them.
+pub fn find_impossible_travel(
+    travel_model: &EventGenerator,
+    location_registry: &LocationRegistry,
+    previous: AccessEvent,
+    event: AccessEvent,
+) -> Option<ImpossibleTravelFinding> {
+    if !travel_model.validate_impossible_traveler_scenario(&previous, &event) {
+        return None;
+    }
 
-        if user["is_curious"].as_bool().unwrap_or(false) {
-            anomalies.push(UserAnomaly {
-                user_id,
-                anomaly_type: "curious_access".to_string(),
-                details: "User accessed unauthorized rooms.".to_string(),
-                issue: "Unauthorized access pattern detected".to_string(),
-                severity: "high".to_string(),
-                timestamp: Utc::now().to_rfc3339(),
-            });
+    let coordinates =
+        |location_id| location_registry.get_location(location_id).map(|location| location.coordinates);
+    let distance_km = travel_model
+        .calculate_geographical_distance(coordinates(previous.location_id)?, coordinates(event.location_id)?);
+    let travel = ImpossibleTravelerMetadata::new(
+        event.user_id,
+        previous.location_id,
+        event.location_id,
+        distance_km,
+        (event.timestamp - previous.timestamp).abs(),
+        travel_model.get_minimum_travel_time_between_locations(previous.location_id, event.location_id),
+    );
+
+    Some(ImpossibleTravelFinding { first_event: previous, second_event: event, travel })
+}
+
+/// Failed attempt followed by a success at the same room within this window is a reader retry
+///
+/// Retries come a few seconds after the failure, but time variance shifts each event forward
+/// independently, so the gap can grow by the whole variance window.
+pub const READER_RETRY_WINDOW_SECONDS: i64 =
+    DEFAULT_VARIANCE_WINDOW_SECONDS as i64 + MAX_READER_RETRY_DELAY_SECONDS;
+
+/// Peer-relative score from which a user is reported as curious
+const CURIOUS_SCORE_THRESHOLD: f64 = 2.0;
+
+/// Weight of a denied attempt on a room of the given security level
+pub fn security_weight(security_level: SecurityLevel) -> f64 {
+    match security_level {
+        SecurityLevel::Public => 0.5,
+        SecurityLevel::Standard => 1.0,
+        SecurityLevel::Restricted => 2.0,
+        SecurityLevel::HighSecurity => 3.0,
+        SecurityLevel::MaxSecurity => 4.0,
+    }
+}
+
+/// Observed access behavior of one user
+#[derive(Debug, Default)]
+struct UserAccessHistory {
+    events_by_building: HashMap<BuildingId, usize>,
+    pending_failure: Option<AccessEvent>,
+    unauthorized_attempts: Vec<AccessEvent>,
+    reader_retries: usize,
+}
+
+impl UserAccessHistory {
+    /// Building the user badges into most often
+    fn home_building(&self) -> Option<BuildingId> {
+        self.events_by_building
+            .iter()
+            .max_by(|a, b| a.1.cmp(b.1).then_with(|| b.0 .0.cmp(&a.0 .0)))
+            .map(|(building_id, _)| *building_id)
+    }
+
+    /// Unauthorized attempts in order, each with the running weighted total
+    ///
+    /// The n-th attempt at a room adds the room's security weight divided by n, so a room
+    /// counts its full weight once and repeats add less and less without ever taking weight
+    /// away. The running total only depends on the attempts made so far.
+    fn weighted_attempts<'a>(
+        &'a self,
+        location_registry: &'a LocationRegistry,
+    ) -> impl Iterator<Item = (&'a AccessEvent, f64)> + 'a {
+        let mut attempts_per_room: HashMap<RoomId, usize> = HashMap::new();
+        let mut weighted_attempts = 0.0;
+        self.unauthorized_attempts.iter().map(move |attempt| {
+            let security_level = location_registry
+                .get_room(attempt.room_id)
+                .map(|room| room.security_level)
+                .unwrap_or(SecurityLevel::Standard);
+            let attempts = attempts_per_room.entry(attempt.room_id).or_default();
+            *attempts += 1;
+            weighted_attempts += security_weight(security_level) / *attempts as f64;
+            (attempt, weighted_attempts)
+        })
+    }
+}
+
+/// A user's denied attempts compared with the other users of their home building
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CuriousUserScore {
+    pub user_id: UserId,
+    pub home_building: BuildingId,
+    /// Denied attempts that were not followed by a quick successful retry
+    pub unauthorized_attempts: Vec<AccessEvent>,
+    /// Denied attempts that a successful retry at the same reader showed to be read errors
+    pub reader_retries: usize,
+    /// Unauthorized attempts weighted by room security level and discounted for repeats
+    pub weighted_attempts: f64,
+    /// Mean weighted attempts of the other users of the home building
+    pub peer_baseline: f64,
+    /// Standard deviations above the peer baseline (with a floor of one attempt)
+    pub score: f64,
+    /// Time of the attempt that took the user over the reporting threshold
+    pub detected_at: Option<DateTime<Utc>>,
+}
+
+/// Curious-user detector that scores denied access attempts against building peers
+///
+/// A denied attempt is held back until the user's next event: a success at the same room
+/// within the retry window marks it as a badge reader retry, anything else as an
+/// unauthorized attempt. Each attempt is weighted by the room's security level from the
+/// facility layout, and the n-th attempt at one room adds only 1/n of it: curious users
+/// probe many different doors, while users who keep hitting the same time-restricted door
+/// grow slowly and are left to the peer baseline. Only success flags, rooms and
+/// timestamps are read from the events; their labels are dropped on arrival.
+#[derive(Debug)]
+pub struct CuriousUserDetector {
+    location_registry: LocationRegistry,
+    users: HashMap<UserId, UserAccessHistory>,
+}
+
+impl CuriousUserDetector {
+    pub fn new(location_registry: LocationRegistry) -> Self {
+        Self { location_registry, users: HashMap::new() }
+    }
+
+    pub fn observe(&mut self, event: &AccessEvent) {
+        let event = observable_event(event);
+        let history = self.users.entry(event.user_id).or_default();
+        *history.events_by_building.entry(event.building_id).or_default() += 1;
+
+        if let Some(failure) = history.pending_failure.take() {
+            let is_retry = event.success
+                && event.room_id == failure.room_id
+                && event.timestamp - failure.timestamp <= Duration::seconds(READER_RETRY_WINDOW_SECONDS);
+            if is_retry {
+                history.reader_retries += 1;
+            } else {
+                history.unauthorized_attempts.push(failure);
+            }
+        }
+
+        if !event.success {
+            history.pending_failure = Some(event);
+        }
+    }
+
+    /// Score every user with unauthorized attempts, highest score first
+    ///
+    /// Denied attempts still waiting for a retry count as unauthorized.
+    pub fn ranking(&mut self) -> Vec<CuriousUserScore> {
+        for history in self.users.values_mut() {
+            if let Some(failure) = history.pending_failure.take() {
+                history.unauthorized_attempts.push(failure);
+            }
+        }
+
+        let weighted_attempts: HashMap<UserId, f64> = self
+            .users
+            .iter()
+            .map(|(user_id, history)| {
+                let weighted = history
+                    .weighted_attempts(&self.location_registry)
+                    .last()
+                    .map_or(0.0, |(_, weighted)| weighted);
+                (*user_id, weighted)
+            })
+            .collect();
+
+        // Sums of weighted attempts and their squares over each building's users
+        let mut peer_totals: HashMap<BuildingId, (f64, f64, usize)> = HashMap::new();
+        for (user_id, history) in &self.users {
+            if let Some(building_id) = history.home_building() {
+                let weighted = weighted_attempts[user_id];
+                let totals = peer_totals.entry(building_id).or_default();
+                totals.0 += weighted;
+                totals.1 += weighted.powi(2);
+                totals.2 += 1;
+            }
+        }
+
+        let mut ranking: Vec<CuriousUserScore> = self
+            .users
+            .iter()
+            .filter(|(_, history)| !history.unauthorized_attempts.is_empty())
+            .filter_map(|(user_id, history)| {
+                let home_building = history.home_building()?;
+                let weighted = weighted_attempts[user_id];
+                let (sum, sum_of_squares, count) = peer_totals[&home_building];
+
+                // Leave the user out of their own baseline
+                let peers = count.saturating_sub(1) as f64;
+                let (peer_baseline, peer_deviation) = if peers > 0.0 {
+                    let mean = (sum - weighted) / peers;
+                    let variance = (sum_of_squares - weighted.powi(2)) / peers - mean.powi(2);
+                    (mean, variance.max(0.0).sqrt())
+                } else {
+                    (0.0, 0.0)
+                };
+                let deviation = peer_deviation.max(1.0);
+                let score = (weighted - peer_baseline) / deviation;
+
+                let threshold = peer_baseline + CURIOUS_SCORE_THRESHOLD * deviation;
+                let detected_at = history
+                    .weighted_attempts(&self.location_registry)
+                    .find(|(_, running_total)| *running_total >= threshold)
+                    .map(|(attempt, _)| attempt.timestamp);
+
+                Some(CuriousUserScore {
+                    user_id: *user_id,
+                    home_building,
+                    unauthorized_attempts: history.unauthorized_attempts.clone(),
+                    reader_retries: history.reader_retries,
+                    weighted_attempts: weighted,
+                    peer_baseline,
+                    score,
+                    detected_at,
+                })
+            })
+            .collect();
+
+        ranking.sort_by(|a, b| b.score.total_cmp(&a.score).then_with(|| a.user_id.0.cmp(&b.user_id.0)));
+        ranking
+    }
+}
+
+/// Consumer of the event stream, fed one event at a time in timestamp order
+pub trait EventObserver: fmt::Debug {
+    fn observe(&mut self, event: &AccessEvent);
+}
+
+/// All event-driven detectors
+#[derive(Debug)]
+pub struct EventDetectors {
+    pub cloned_badges: ClonedBadgeDetector,
+    pub curious_users: CuriousUserDetector,
+}
+
+impl EventDetectors {
+    pub fn new(config: &SimulationConfig, location_registry: LocationRegistry) -> Self {
+        Self {
+            cloned_badges: ClonedBadgeDetector::new(config, location_registry.clone()),
+            curious_users: CuriousUserDetector::new(location_registry),
+        }
+    }
+}
+
+impl EventObserver for EventDetectors {
+    fn observe(&mut self, event: &AccessEvent) {
+        self.cloned_badges.observe(event);
+        self.curious_users.observe(event);
+    }
+}
+
+/// Event sink that feeds every event to the observers before passing it on
+#[derive(Debug)]
+pub struct ObservingEventSink<'a> {
+    inner: Box<dyn EventSink>,
+    observers: Vec<&'a mut dyn EventObserver>,
+}
+
+impl<'a> ObservingEventSink<'a> {
+    pub fn new(inner: Box<dyn EventSink>, observers: Vec<&'a mut dyn EventObserver>) -> Self {
+        Self { inner, observers }
+    }
+}
+
+impl EventSink for ObservingEventSink<'_> {
+    fn begin_day(&mut self, date: NaiveDate) -> SimulationResult<()> {
+        self.inner.begin_day(date)
+    }
+
+    fn write_event(&mut self, event: &AccessEvent) -> SimulationResult<()> {
+        for observer in self.observers.iter_mut() {
+            observer.observe(event);
         }
+        self.inner.write_event(event)
+    }
+
+    fn end_day(&mut self, date: NaiveDate) -> SimulationResult<()> {
+        self.inner.end_day(date)
+    }
+
+    fn finish(&mut self) -> SimulationResult<()> {
+        self.inner.finish()
+    }
+}
+
+/// Turn impossible travel findings into one cloned-badge anomaly per user
+///
+/// The anomaly is timestamped with the event that first gave the user away, and its
+/// severity follows the most impossible trip.
+pub fn detect_cloned_badges(findings: &[ImpossibleTravelFinding]) -> Vec<UserAnomaly> {
+    let mut user_indices: HashMap<UserId, usize> = HashMap::new();
+    let mut findings_by_user: Vec<(UserId, ImpossibleTravelFinding, Vec<ImpossibleTravelFinding>)> = Vec::new();
+    for finding in findings {
+        let user_id = finding.second_event.user_id;
+        match user_indices.get(&user_id) {
+            Some(&index) => findings_by_user[index].2.push(finding.clone()),
+            None => {
+                user_indices.insert(user_id, findings_by_user.len());
+                findings_by_user.push((user_id, finding.clone(), Vec::new()));
+            }
+        }
+    }
+
+    findings_by_user
+        .into_iter()
+        .map(|(user_id, first, rest)| cloned_badge_anomaly(user_id.to_string(), first, rest))
+        .collect()
+}
+
+/// Cloned-badge anomaly for one user's impossible travel findings
+///
+/// `first` is the finding that gave the user away and `rest` the later ones, in detection order.
+pub fn cloned_badge_anomaly(
+    user_id: String,
+    first: ImpossibleTravelFinding,
+    rest: Vec<ImpossibleTravelFinding>,
+) -> UserAnomaly {
+    let timestamp = first.second_event.timestamp.to_rfc3339();
+    let mut findings = Vec::with_capacity(rest.len() + 1);
+    findings.push(first);
+    findings.extend(rest);
+
+    let worst = findings
+        .iter()
+        .skip(1)
+        .fold(&findings[0], |worst, finding| {
+            if finding.travel.impossibility_factor > worst.travel.impossibility_factor {
+                finding
+            } else {
+                worst
+            }
+        });
+    let severity = match worst.travel.impossibility_factor {
+        factor if factor >= 4.0 => "high",
+        factor if factor >= 2.0 => "medium",
+        _ => "low",
+    };
+
+    UserAnomaly {
+        user_id,
+        anomaly_type: "cloned_badge".to_string(),
+        details: format!(
+            "{} impossible trip(s); worst: {:.0} km in {} min where at least {} min are needed ({:.1}x too fast)",
+            findings.len(),
+            worst.travel.geographical_distance_km,
+            worst.travel.actual_time_gap.num_minutes(),
+            worst.travel.minimum_required_time.num_minutes(),
+            worst.travel.impossibility_factor
+        ),
+        issue: "Badge used at distant locations faster than physically possible".to_string(),
+        severity: severity.to_string(),
+        timestamp,
+        evidence: Some(AnomalyEvidence::ImpossibleTravel { findings }),
     }
+}
+
+/// Report the users whose denied attempts stand out from their building peers
+///
+/// Anomalies keep the ranking order and are timestamped with the attempt that took the
+/// user over the threshold.
+pub fn detect_curious_users(ranking: &[CuriousUserScore]) -> Vec<UserAnomaly> {
+    ranking
+        .iter()
+        .filter(|user| user.score >= CURIOUS_SCORE_THRESHOLD)
+        .map(|user| {
+            let severity = match user.score {
+                score if score >= 6.0 => "high",
+                score if score >= 4.0 => "medium",
+                _ => "low",
+            };
 
-    anomalies
+            UserAnomaly {
+                user_id: user.user_id.to_string(),
+                anomaly_type: "curious_access".to_string(),
+                details: format!(
+                    "{} unauthorized attempt(s) at {} room(s) weighing {:.1} against a building baseline of {:.1} (score {:.1}); {} reader retries ignored",
+                    user.unauthorized_attempts.len(),
+                    user.unauthorized_attempts.iter().map(|attempt| attempt.room_id).collect::<HashSet<_>>().len(),
+                    user.weighted_attempts,
+                    user.peer_baseline,
+                    user.score,
+                    user.reader_retries
+                ),
+                issue: "Repeated attempts to access unauthorized rooms".to_string(),
+                severity: severity.to_string(),
+                timestamp: user
+                    .detected_at
+                    .or_else(|| user.unauthorized_attempts.last().map(|attempt| attempt.timestamp))
+                    .map(|timestamp| timestamp.to_rfc3339())
+                    .unwrap_or_default(),
+                evidence: Some(AnomalyEvidence::UnauthorizedAccess(user.clone())),
+            }
+        })
+        .collect()
 }
 
-pub fn detect_night_shift_users(path: &str) -> Vec<UserAnomaly> {
+pub fn detect_night_shift_users(path: &str) -> Result<Vec<UserAnomaly>, String> {
     use chrono::NaiveTime;
-    let file = File::open(path).expect("Cannot open user profile JSON");
-    let reader = BufReader::new(file);
+    let reader = open_input_file(path).map_err(|e| format!("Cannot open user profile JSON '{}': {}", path, e))?;
     let mut anomalies = Vec::new();
 
-    for line in reader.lines() {
-        let line = line.expect("Failed to read line");
-        let user: Value = serde_json::from_str(&line).expect("Cannot parse JSON line");
+    for (index, line) in reader.lines().enumerate() {
+        let line = line.map_err(|e| format!("Failed to read '{}': {}", path, e))?;
+        let user: Value = serde_json::from_str(&line)
+            .map_err(|e| format!("Cannot parse line {} of '{}': {}", index + 1, path, e))?;
         let user_id = user["user_id"].as_str().unwrap_or("unknown").to_string();
 
         // Simulated access time field check
@@ -88,16 +531,17 @@ pub fn detect_night_shift_users(path: &str) -> Vec<UserAnomaly> {
                         issue: "Access outside normal business hours".to_string(),
                         severity: "medium".to_string(),
                         timestamp: Utc::now().to_rfc3339(),
+                        evidence: None,
                     });
                 }
             }
         }
     }
 
-    anomalies
+    Ok(anomalies)
 }
 
-pub fn generate_report(output_path: &str, anomalies: &Vec<UserAnomaly>) {
+pub fn generate_report(output_path: &str, anomalies: &Vec<UserAnomaly>) -> Result<(), String> {
     use serde_json::json;
 
     let summary = json!({
@@ -113,54 +557,281 @@ pub fn generate_report(output_path: &str, anomalies: &Vec<UserAnomaly>) {
         "summary": summary
     });
 
-    let file = File::create(output_path).expect("Cannot create report file");
-    serde_json::to_writer_pretty(&file, &full_report).expect("Cannot write JSON report");
+    let file = File::create(output_path).map_err(|e| format!("Cannot create report file '{}': {}", output_path, e))?;
+    serde_json::to_writer_pretty(&file, &full_report)
+        .map_err(|e| format!("Cannot write JSON report '{}': {}", output_path, e))?;
 
-    println!(" Report generated: {}", output_path);
-    println!(
+    eprintln!(" Report generated: {}", output_path);
+    eprintln!(
         "Summary: {} total anomalies ({} cloned badges, {} curious, {} night shift)",
         summary["total_anomalies"],
         summary["cloned_badge"],
         summary["curious_access"],
         summary["night_shift_access"]
     );
+    Ok(())
 }
-pub fn simulate_s3_upload(file_path: &str, bucket_name: &str) {
+pub fn simulate_s3_upload(file_path: &str, bucket_name: &str) -> Result<(), String> {
     use chrono::Utc;
-    use std::fs::{self, OpenOptions};
-    use std::io::Write;
+    use serde_json::json;
+    use std::fs;
 
-    println!(" Simulating upload of '{}' to AWS S3 bucket '{}'", file_path, bucket_name);
+    eprintln!(" Simulating upload of '{}' to AWS S3 bucket '{}'", file_path, bucket_name);
 
-    // Read the JSON content
-    let mut json_text = fs::read_to_string(file_path).expect("Failed to read JSON report");
+    // Read the JSON report
+    let json_text =
+        fs::read_to_string(file_path).map_err(|e| format!("Failed to read JSON report '{}': {}", file_path, e))?;
+    let mut report: Value = serde_json::from_str(&json_text)
+        .map_err(|e| format!("Cannot parse JSON report '{}': {}", file_path, e))?;
 
-    // Append summary metadata
-    let summary = format!(
-        r#",
-{{
-    "summary": {{
+    // Record the upload in the report, keeping it valid JSON for evaluation
+    report["s3_upload"] = json!({
         "uploaded_to_s3": true,
-        "s3_bucket": "{}",
-        "timestamp": "{}"
-    }}
-}}"#,
-        bucket_name,
-        Utc::now().to_rfc3339()
-    );
+        "s3_bucket": bucket_name,
+        "timestamp": Utc::now().to_rfc3339()
+    });
 
-    if let Some(pos) = json_text.rfind(']') {
-        json_text.insert_str(pos + 1, &summary);
+    // Write it back
+    let file = File::create(file_path).map_err(|e| format!("Failed to open '{}' for writing: {}", file_path, e))?;
+    serde_json::to_writer_pretty(&file, &report).map_err(|e| format!("Failed to update '{}': {}", file_path, e))?;
+
+    eprintln!(" Simulated S3 upload complete and summary added!");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use amzn_career_pathway_activity_rust::events::EventMetadata;
+    use amzn_career_pathway_activity_rust::facility::{FacilityDefinition, Location, Room};
+    use amzn_career_pathway_activity_rust::types::{EventType, FailureReason, LocationId};
+
+    fn registry() -> LocationRegistry {
+        let mut registry = LocationRegistry::new();
+        registry.add_location(Location::new("Seattle".to_string(), (47.6062, -122.3321)));
+        registry.add_location(Location::new("Tokyo".to_string(), (35.6762, 139.6503)));
+        registry
     }
 
-    // Write it back
-    let mut file = OpenOptions::new()
-        .write(true)
-        .truncate(true)
-        .open(file_path)
-        .expect("Failed to open file for appending");
-    file.write_all(json_text.as_bytes())
-        .expect("Failed to update JSON file");
-
-    println!(" Simulated S3 upload complete and summary added!");
-}
\ No newline at end of file
+    fn event(user_id: UserId, location: &Location, timestamp: DateTime<Utc>) -> AccessEvent {
+        let mut event = AccessEvent::new(
+            timestamp,
+            user_id,
+            RoomId::new(),
+            BuildingId::new(),
+            location.id,
+            true,
+            EventType::Success,
+        );
+        event.metadata = Some(EventMetadata::impossible_traveler(Duration::hours(1), 1.0));
+        event
+    }
+
+    #[test]
+    fn test_detector_flags_impossible_travel_only() {
+        let registry = registry();
+        let (seattle, tokyo) = (&registry.get_all_locations()[0], &registry.get_all_locations()[1]);
+        let mut detector = ClonedBadgeDetector::new(&SimulationConfig::default(), registry.clone());
+        let (user, other_user) = (UserId::new(), UserId::new());
+        let start = Utc::now();
+
+        assert!(detector.observe(&event(user, seattle, start)).is_none());
+        assert!(detector.observe(&event(other_user, tokyo, start + Duration::minutes(10))).is_none());
+        let finding = detector.observe(&event(user, tokyo, start + Duration::minutes(30))).unwrap();
+        assert_eq!(finding.travel.remote_location, tokyo.id);
+        assert!(finding.travel.geographical_distance_km > 7000.0);
+        assert_eq!(finding.travel.actual_time_gap, Duration::minutes(30));
+        assert_eq!(finding.travel.impossibility_factor, 16.0);
+        assert!(finding.first_event.metadata.is_none() && finding.second_event.metadata.is_none());
+
+        // A day later the trip back is plausible
+        assert!(detector.observe(&event(user, seattle, start + Duration::hours(24))).is_none());
+        assert_eq!(detector.findings().len(), 1);
+    }
+
+    const OFFICE: &str = r#"
+locations:
+  - name: Austin
+    latitude: 30.2672
+    longitude: -97.7431
+    buildings:
+      - name: Tower
+        rooms:
+          - { name: Lobby, room_type: Lobby, security_level: Public }
+          - { name: Desks, room_type: Workspace, security_level: Standard }
+          - { name: Vault, room_type: Storage, security_level: Restricted }
+          - { name: Lab, room_type: Laboratory, security_level: HighSecurity }
+          - { name: Data Center, room_type: ServerRoom, security_level: MaxSecurity }
+"#;
+
+    fn badge(user_id: UserId, room: &Room, timestamp: DateTime<Utc>, success: bool) -> AccessEvent {
+        AccessEvent::new(
+            timestamp,
+            user_id,
+            room.id,
+            room.building_id,
+            LocationId::new(),
+            success,
+            if success { EventType::Success } else { EventType::Failure },
+        )
+    }
+
+    #[test]
+    fn test_curious_users_rank_above_building_peers() {
+        let registry = FacilityDefinition::from_yaml_str(OFFICE).unwrap().into_registry().unwrap();
+        let room = |name: &str| registry.get_all_rooms().into_iter().find(|room| room.name == name).unwrap().clone();
+        let (lobby, lab, vault, data_center) = (room("Lobby"), room("Lab"), room("Vault"), room("Data Center"));
+        let mut detector = CuriousUserDetector::new(registry.clone());
+        let start = Utc::now();
+
+        let peers: Vec<UserId> = (0..5).map(|_| UserId::new()).collect();
+        for (i, peer) in peers.iter().enumerate() {
+            detector.observe(&badge(*peer, &lobby, start + Duration::minutes(i as i64), true));
+        }
+
+        // A reader error on the lab is retried successfully and does not count
+        detector.observe(&badge(peers[0], &lab, start + Duration::minutes(10), false));
+        detector.observe(&badge(peers[0], &lab, start + Duration::minutes(10) + Duration::seconds(20), true));
+
+        // A guard keeps being refused at the same time-restricted door
+        let guard = UserId::new();
+        for hour in 0..6 {
+            detector.observe(&badge(guard, &lobby, start + Duration::hours(hour), true));
+            detector.observe(&badge(guard, &vault, start + Duration::hours(hour) + Duration::minutes(5), false));
+        }
+
+        // A curious user tries three different restricted rooms
+        let curious = UserId::new();
+        detector.observe(&badge(curious, &lobby, start, true));
+        for (i, target) in [&lab, &vault, &data_center].into_iter().enumerate() {
+            detector.observe(&badge(curious, target, start + Duration::hours(i as i64 + 1), false));
+        }
+
+        let ranking = detector.ranking();
+        assert_eq!(ranking.len(), 2);
+        assert_eq!(ranking[0].user_id, curious);
+        assert_eq!(ranking[0].unauthorized_attempts.len(), 3);
+        assert_eq!(ranking[0].weighted_attempts, 9.0);
+        assert_eq!(ranking[0].home_building, lobby.building_id);
+        assert_eq!(ranking[1].user_id, guard);
+        assert_eq!(ranking[1].unauthorized_attempts.len(), 6);
+        // Repeats add less each time but never take weight away: 2 * (1 + 1/2 + ... + 1/6)
+        assert!((ranking[1].weighted_attempts - 4.9).abs() < 0.01);
+
+        let anomalies = detect_curious_users(&ranking);
+        assert_eq!(anomalies.len(), 1);
+        assert_eq!(anomalies[0].user_id, curious.to_string());
+        assert_eq!(anomalies[0].severity, "medium");
+        // The lab and the vault take the user over the threshold
+        assert_eq!(anomalies[0].timestamp, (start + Duration::hours(2)).to_rfc3339());
+    }
+
+    #[test]
+    fn test_reader_retries_are_not_unauthorized_attempts() {
+        let registry = FacilityDefinition::from_yaml_str(OFFICE).unwrap().into_registry().unwrap();
+        let lab = registry.get_all_rooms().into_iter().find(|room| room.name == "Lab").unwrap().clone();
+        let mut detector = CuriousUserDetector::new(registry);
+        let (user, start) = (UserId::new(), Utc::now());
+
+        detector.observe(&badge(user, &lab, start, false));
+        detector.observe(&badge(user, &lab, start + Duration::seconds(15), true));
+        detector.observe(&badge(user, &lab, start + Duration::hours(1), false));
+        detector.observe(&badge(user, &lab, start + Duration::hours(1) + Duration::minutes(10), true));
+
+        let ranking = detector.ranking();
+        assert_eq!(ranking.len(), 1);
+        assert_eq!(ranking[0].reader_retries, 1);
+        assert_eq!(ranking[0].unauthorized_attempts.len(), 1);
+        assert_eq!(ranking[0].unauthorized_attempts[0].timestamp, start + Duration::hours(1));
+    }
+
+    #[test]
+    fn test_generated_reader_retries_fall_within_the_retry_window() {
+        use amzn_career_pathway_activity_rust::permissions::{PermissionLevel, PermissionSet};
+        use amzn_career_pathway_activity_rust::types::ActivityType;
+        use amzn_career_pathway_activity_rust::user::{ScheduledActivity, User};
+        use chrono::{Datelike, TimeZone};
+
+        let registry = FacilityDefinition::from_yaml_str(OFFICE).unwrap().into_registry().unwrap();
+        let desks = registry.get_all_rooms().into_iter().find(|room| room.name == "Desks").unwrap().clone();
+        let location_id = registry.get_all_locations()[0].id;
+        let user = User::new(
+            location_id,
+            desks.building_id,
+            desks.id,
+            PermissionSet::with_permissions(vec![PermissionLevel::Building(desks.building_id)]),
+        );
+        let config = SimulationConfig { seed: Some(11), ..Default::default() };
+        let mut generator = EventGenerator::new(config, registry.clone(), TimeManager::default());
+        let mut detector = CuriousUserDetector::new(registry);
+        // Mid-morning in Austin
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 16, 0, 0).unwrap();
+
+        let (mut reader_failures, mut late_retries) = (0, 0);
+        for day in 0..10_000 {
+            let time = start + Duration::days(day);
+            if time.weekday().number_from_monday() > 5 {
+                continue;
+            }
+            let activity = ScheduledActivity::new(ActivityType::Meeting, desks.id, time, Duration::hours(1));
+            let events = generator.generate_events_from_activity(&user, &activity, time).unwrap();
+            for (i, event) in events.iter().enumerate() {
+                if event.is_badge_reader_failure() {
+                    reader_failures += 1;
+                    if events[i + 1].timestamp - event.timestamp > Duration::seconds(60) {
+                        late_retries += 1;
+                    }
+                }
+                detector.observe(event);
+            }
+        }
+
+        // Time variance pushes some retries well past the few seconds the reader takes
+        assert!(late_retries > 0, "{} reader failures", reader_failures);
+        assert_eq!(detector.users[&user.id].reader_retries, reader_failures);
+    }
+
+    #[test]
+    fn test_cloned_badge_evidence_carries_no_labels() {
+        let registry = registry();
+        let (seattle, tokyo) = (&registry.get_all_locations()[0], &registry.get_all_locations()[1]);
+        let mut detector = ClonedBadgeDetector::new(&SimulationConfig::default(), registry.clone());
+        let user = UserId::new();
+        let start = Utc::now();
+
+        detector.observe(&event(user, seattle, start));
+        let mut labeled = event(user, tokyo, start + Duration::minutes(30));
+        labeled.event_type = EventType::Suspicious;
+        labeled.failure_reason = Some(FailureReason::ImpossibleTraveler);
+        let finding = detector.observe(&labeled).unwrap();
+        assert_eq!(finding.second_event.event_type, EventType::Success);
+
+        let report = serde_json::to_string(&detect_cloned_badges(detector.findings())).unwrap();
+        for label in ["ImpossibleTraveler", "Suspicious", "is_impossible_traveler", "failure_reason\":\"", "metadata\":{"] {
+            assert!(!report.contains(label), "{} leaked into {}", label, report);
+        }
+    }
+
+    #[test]
+    fn test_cloned_badge_anomalies_are_grouped_per_user() {
+        let registry = registry();
+        let (seattle, tokyo) = (&registry.get_all_locations()[0], &registry.get_all_locations()[1]);
+        let mut detector = ClonedBadgeDetector::new(&SimulationConfig::default(), registry.clone());
+        let user = UserId::new();
+        let start = Utc::now();
+
+        detector.observe(&event(user, seattle, start));
+        detector.observe(&event(user, tokyo, start + Duration::hours(2)));
+        detector.observe(&event(user, seattle, start + Duration::hours(3)));
+
+        let anomalies = detect_cloned_badges(detector.findings());
+        assert_eq!(anomalies.len(), 1);
+        assert_eq!(anomalies[0].user_id, user.to_string());
+        assert_eq!(anomalies[0].severity, "high");
+        assert_eq!(anomalies[0].timestamp, (start + Duration::hours(2)).to_rfc3339());
+        match &anomalies[0].evidence {
+            Some(AnomalyEvidence::ImpossibleTravel { findings }) => assert_eq!(findings.len(), 2),
+            other => panic!("unexpected evidence: {:?}", other),
+        }
+    }
+}
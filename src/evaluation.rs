@@ -0,0 +1,565 @@
+// Detector evaluation against the user profiles answer key
+//
+// Scores the anomalies a detector reported (the binary's anomaly_report.json, or one
+// anomaly per line from any other detector) against the ground truth in the user profiles
+// JSONL file. Every anomaly class gets a user-level confusion matrix with precision,
+// recall and F1. When the events were written with their metadata labels, time-to-detect
+// is measured from each user's first labeled anomalous event to the first time the
+// detector flagged them.
+
+use std::collections::HashMap;
+use std::io::{BufRead, Read};
+
+use amzn_career_pathway_activity_rust::events::{parse_csv_row, EventMetadata, METADATA_COLUMN_PREFIX};
+use amzn_career_pathway_activity_rust::simulation::open_input_file;
+use amzn_career_pathway_activity_rust::user::UserProfile;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Anomaly classes with a ground truth flag in the answer key
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnomalyClass {
+    ClonedBadge,
+    CuriousAccess,
+    NightShiftAccess,
+}
+
+impl AnomalyClass {
+    pub const ALL: [AnomalyClass; 3] =
+        [AnomalyClass::ClonedBadge, AnomalyClass::CuriousAccess, AnomalyClass::NightShiftAccess];
+
+    /// The `anomaly_type` detectors report for this class
+    pub fn anomaly_type(self) -> &'static str {
+        match self {
+            AnomalyClass::ClonedBadge => "cloned_badge",
+            AnomalyClass::CuriousAccess => "curious_access",
+            AnomalyClass::NightShiftAccess => "night_shift_access",
+        }
+    }
+
+    /// Whether the answer key puts the user in this class
+    fn is_positive(self, profile: &UserProfile) -> bool {
+        match self {
+            AnomalyClass::ClonedBadge => profile.has_cloned_badge,
+            AnomalyClass::CuriousAccess => profile.is_curious,
+            AnomalyClass::NightShiftAccess => profile.is_night_shift,
+        }
+    }
+
+    /// Whether an event's metadata labels it as anomalous for this class
+    fn labels(self, metadata: &EventMetadata) -> bool {
+        match self {
+            AnomalyClass::ClonedBadge => metadata.is_impossible_traveler,
+            AnomalyClass::CuriousAccess => metadata.is_curious_attempt,
+            AnomalyClass::NightShiftAccess => metadata.is_night_shift_event,
+        }
+    }
+}
+
+/// One reported anomaly; other fields of the report are ignored
+#[derive(Debug, Clone, Deserialize)]
+pub struct Detection {
+    pub user_id: String,
+    pub anomaly_type: String,
+    #[serde(default)]
+    pub timestamp: Option<String>,
+}
+
+/// User-level confusion matrix of one anomaly class
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct ConfusionMatrix {
+    pub true_positives: usize,
+    pub false_positives: usize,
+    pub false_negatives: usize,
+    pub true_negatives: usize,
+}
+
+impl ConfusionMatrix {
+    /// Share of flagged users that are in the class, or None when nobody was flagged
+    pub fn precision(&self) -> Option<f64> {
+        ratio(self.true_positives, self.true_positives + self.false_positives)
+    }
+
+    /// Share of the class that was flagged, or None when the class is empty
+    pub fn recall(&self) -> Option<f64> {
+        ratio(self.true_positives, self.true_positives + self.false_negatives)
+    }
+
+    /// Harmonic mean of precision and recall
+    pub fn f1(&self) -> Option<f64> {
+        ratio(2 * self.true_positives, 2 * self.true_positives + self.false_positives + self.false_negatives)
+    }
+}
+
+fn ratio(numerator: usize, denominator: usize) -> Option<f64> {
+    (denominator > 0).then(|| numerator as f64 / denominator as f64)
+}
+
+/// Delay between a user's first labeled anomalous event and the detector flagging them
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TimeToDetect {
+    /// True positives with both a labeled event and a detection timestamp
+    pub measured: usize,
+    pub mean_seconds: f64,
+    pub median_seconds: f64,
+    pub max_seconds: f64,
+}
+
+impl TimeToDetect {
+    fn from_delays(mut delays: Vec<f64>) -> Option<Self> {
+        if delays.is_empty() {
+            return None;
+        }
+        delays.sort_by(f64::total_cmp);
+        let middle = delays.len() / 2;
+        let median_seconds = if delays.len().is_multiple_of(2) {
+            (delays[middle - 1] + delays[middle]) / 2.0
+        } else {
+            delays[middle]
+        };
+
+        Some(Self {
+            measured: delays.len(),
+            mean_seconds: delays.iter().sum::<f64>() / delays.len() as f64,
+            median_seconds,
+            max_seconds: delays[delays.len() - 1],
+        })
+    }
+}
+
+/// Scores of one anomaly class
+#[derive(Debug, Clone, Serialize)]
+pub struct ClassEvaluation {
+    pub anomaly_type: String,
+    pub confusion_matrix: ConfusionMatrix,
+    pub precision: Option<f64>,
+    pub recall: Option<f64>,
+    pub f1: Option<f64>,
+    pub time_to_detect: Option<TimeToDetect>,
+    /// Flagged users that are not in the class, sorted
+    pub false_positives: Vec<String>,
+    /// Users in the class that were not flagged, sorted
+    pub false_negatives: Vec<String>,
+}
+
+/// Scores of a detector run against the answer key
+#[derive(Debug, Clone, Serialize)]
+pub struct Evaluation {
+    pub users: usize,
+    pub classes: Vec<ClassEvaluation>,
+}
+
+/// Read the anomalies from a report object with an `anomalies` array or from JSON lines
+pub fn load_detections(path: &str) -> Result<Vec<Detection>, String> {
+    let mut content = String::new();
+    open_input_file(path)
+        .and_then(|mut reader| reader.read_to_string(&mut content))
+        .map_err(|e| format!("Failed to read anomaly report '{}': {}", path, e))?;
+
+    if let Ok(report) = serde_json::from_str::<Value>(&content) {
+        if let Some(anomalies) = report.get("anomalies") {
+            return serde_json::from_value(anomalies.clone())
+                .map_err(|e| format!("Invalid anomalies in '{}': {}", path, e));
+        }
+    }
+
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(index, line)| {
+            serde_json::from_str(line).map_err(|e| format!("Invalid anomaly on line {} of '{}': {}", index + 1, path, e))
+        })
+        .collect()
+}
+
+/// Read the user profiles answer key (JSON lines, optionally compressed)
+pub fn load_answer_key(path: &str) -> Result<Vec<UserProfile>, String> {
+    let reader = open_input_file(path).map_err(|e| format!("Failed to open answer key '{}': {}", path, e))?;
+    let mut profiles = Vec::new();
+    for (index, line) in reader.lines().enumerate() {
+        let line = line.map_err(|e| format!("Failed to read answer key '{}': {}", path, e))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let profile = serde_json::from_str(&line)
+            .map_err(|e| format!("Invalid user profile on line {} of '{}': {}", index + 1, path, e))?;
+        profiles.push(profile);
+    }
+    Ok(profiles)
+}
+
+/// Find each user's first labeled event of every class in JSON lines or CSV events
+///
+/// The format is detected from the first line: JSON lines carry the labels in their
+/// `metadata` object, CSV files in the `metadata_`-prefixed columns of their header row.
+/// Events keyed by badge number are mapped back to users through the answer key, and
+/// events without metadata are skipped.
+pub fn load_first_labeled_events(
+    path: &str,
+    profiles: &[UserProfile],
+) -> Result<HashMap<(String, &'static str), DateTime<Utc>>, String> {
+    let user_ids = UserIndex::new(profiles);
+    let reader = open_input_file(path).map_err(|e| format!("Failed to open events '{}': {}", path, e))?;
+    let mut csv_columns: Option<HashMap<String, usize>> = None;
+    let mut json_lines = false;
+    let mut first_events = HashMap::new();
+
+    for (index, line) in reader.lines().enumerate() {
+        let line = line.map_err(|e| format!("Failed to read events '{}': {}", path, e))?;
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let labeled_event = match &csv_columns {
+            Some(columns) => {
+                let values = parse_csv_row(line)
+                    .map_err(|e| format!("Invalid event on line {} of '{}': {}", index + 1, path, e))?;
+                labeled_csv_event(columns, &values)
+            }
+            None if !json_lines && !line.starts_with('{') => {
+                let header = parse_csv_row(line).map_err(|e| format!("Invalid CSV header in '{}': {}", path, e))?;
+                csv_columns = Some(header.into_iter().enumerate().map(|(i, name)| (name, i)).collect());
+                continue;
+            }
+            None => {
+                json_lines = true;
+                let event: Value = serde_json::from_str(line)
+                    .map_err(|e| format!("Invalid event on line {} of '{}': {}", index + 1, path, e))?;
+                labeled_json_event(&event)
+            }
+        };
+        let Some((key, timestamp, metadata)) = labeled_event else {
+            continue;
+        };
+        let user_id = user_ids.resolve(&key);
+
+        for class in AnomalyClass::ALL.into_iter().filter(|class| class.labels(&metadata)) {
+            first_events
+                .entry((user_id.clone(), class.anomaly_type()))
+                .and_modify(|first: &mut DateTime<Utc>| *first = (*first).min(timestamp))
+                .or_insert(timestamp);
+        }
+    }
+
+    Ok(first_events)
+}
+
+/// User key, timestamp and labels of a JSON event, if it has all three
+fn labeled_json_event(event: &Value) -> Option<(String, DateTime<Utc>, EventMetadata)> {
+    let metadata = serde_json::from_value::<EventMetadata>(event.get("metadata")?.clone()).ok()?;
+    let key = event.get("user_id").or_else(|| event.get("badge_number")).and_then(Value::as_str)?;
+    let timestamp = event.get("timestamp").and_then(Value::as_str).and_then(parse_timestamp)?;
+    Some((key.to_string(), timestamp, metadata))
+}
+
+/// User key, timestamp and labels of a CSV event, if it has all three
+///
+/// Events written without metadata leave the label columns empty.
+fn labeled_csv_event(columns: &HashMap<String, usize>, values: &[String]) -> Option<(String, DateTime<Utc>, EventMetadata)> {
+    let field = |name: &str| columns.get(name).and_then(|index| values.get(*index)).filter(|value| !value.is_empty());
+    let label = |name: &str| match field(&format!("{}{}", METADATA_COLUMN_PREFIX, name))?.as_str() {
+        "true" => Some(true),
+        "false" => Some(false),
+        _ => None,
+    };
+
+    let mut metadata = EventMetadata::new();
+    metadata.is_curious_attempt = label("is_curious_attempt")?;
+    metadata.is_impossible_traveler = label("is_impossible_traveler")?;
+    metadata.is_night_shift_event = label("is_night_shift_event")?;
+    let key = field("user_id").or_else(|| field("badge_number"))?;
+    let timestamp = parse_timestamp(field("timestamp")?)?;
+    Some((key.clone(), timestamp, metadata))
+}
+
+fn parse_timestamp(timestamp: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(timestamp).ok().map(|timestamp| timestamp.with_timezone(&Utc))
+}
+
+/// Maps the user ids and badge numbers a detector may report to answer key user ids
+struct UserIndex {
+    badge_numbers: HashMap<String, String>,
+}
+
+impl UserIndex {
+    fn new(profiles: &[UserProfile]) -> Self {
+        let badge_numbers = profiles
+            .iter()
+            .filter_map(|profile| {
+                let identity = profile.identity.as_ref()?;
+                Some((identity.badge_number.to_string(), profile.user_id.to_string()))
+            })
+            .collect();
+        Self { badge_numbers }
+    }
+
+    fn resolve(&self, key: &str) -> String {
+        self.badge_numbers.get(key).cloned().unwrap_or_else(|| key.to_string())
+    }
+}
+
+/// Score the detections of every anomaly class against the answer key
+///
+/// `first_labeled_events` holds each user's first labeled event per class (see
+/// `load_first_labeled_events`); without it time-to-detect is not measured.
+pub fn evaluate(
+    detections: &[Detection],
+    profiles: &[UserProfile],
+    first_labeled_events: Option<&HashMap<(String, &'static str), DateTime<Utc>>>,
+) -> Evaluation {
+    let user_ids = UserIndex::new(profiles);
+
+    let classes = AnomalyClass::ALL
+        .into_iter()
+        .map(|class| {
+            let anomaly_type = class.anomaly_type();
+
+            // First time each user was flagged for this class
+            let mut flagged: HashMap<String, Option<DateTime<Utc>>> = HashMap::new();
+            for detection in detections.iter().filter(|detection| detection.anomaly_type == anomaly_type) {
+                let timestamp = detection.timestamp.as_deref().and_then(parse_timestamp);
+                let first = flagged.entry(user_ids.resolve(&detection.user_id)).or_insert(timestamp);
+                if let (Some(earliest), Some(timestamp)) = (first.as_mut(), timestamp) {
+                    *earliest = (*earliest).min(timestamp);
+                }
+            }
+
+            let in_class: HashMap<String, bool> =
+                profiles.iter().map(|profile| (profile.user_id.to_string(), class.is_positive(profile))).collect();
+
+            let mut confusion_matrix = ConfusionMatrix::default();
+            let mut false_positives = Vec::new();
+            let mut delays = Vec::new();
+            for (user_id, detected_at) in &flagged {
+                // Flagged users missing from the answer key are false positives too
+                if !in_class.get(user_id).copied().unwrap_or(false) {
+                    confusion_matrix.false_positives += 1;
+                    false_positives.push(user_id.clone());
+                    continue;
+                }
+                confusion_matrix.true_positives += 1;
+                let first_event = first_labeled_events.and_then(|events| events.get(&(user_id.clone(), anomaly_type)));
+                if let (Some(detected_at), Some(first_event)) = (detected_at, first_event) {
+                    delays.push((*detected_at - *first_event).num_milliseconds() as f64 / 1000.0);
+                }
+            }
+
+            let mut false_negatives = Vec::new();
+            for (user_id, positive) in &in_class {
+                match (positive, flagged.contains_key(user_id)) {
+                    (true, false) => {
+                        confusion_matrix.false_negatives += 1;
+                        false_negatives.push(user_id.clone());
+                    }
+                    (false, false) => confusion_matrix.true_negatives += 1,
+                    _ => {}
+                }
+            }
+            false_positives.sort();
+            false_negatives.sort();
+
+            ClassEvaluation {
+                anomaly_type: anomaly_type.to_string(),
+                confusion_matrix,
+                precision: confusion_matrix.precision(),
+                recall: confusion_matrix.recall(),
+                f1: confusion_matrix.f1(),
+                time_to_detect: TimeToDetect::from_delays(delays),
+                false_positives,
+                false_negatives,
+            }
+        })
+        .collect();
+
+    Evaluation { users: profiles.len(), classes }
+}
+
+/// Print a one-line summary of each class to stderr
+pub fn print_evaluation_summary(evaluation: &Evaluation) {
+    let percent = |value: Option<f64>| value.map_or("n/a".to_string(), |value| format!("{:.1}%", value * 100.0));
+
+    eprintln!("Evaluation against {} users:", evaluation.users);
+    for class in &evaluation.classes {
+        let matrix = &class.confusion_matrix;
+        eprintln!(
+            " {}: TP {} FP {} FN {} TN {} | precision {} recall {} F1 {}{}",
+            class.anomaly_type,
+            matrix.true_positives,
+            matrix.false_positives,
+            matrix.false_negatives,
+            matrix.true_negatives,
+            percent(class.precision),
+            percent(class.recall),
+            percent(class.f1),
+            class
+                .time_to_detect
+                .as_ref()
+                .map(|ttd| format!(" | median time-to-detect {:.0}s", ttd.median_seconds))
+                .unwrap_or_default()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use amzn_career_pathway_activity_rust::facility::FacilityGenerator;
+    use amzn_career_pathway_activity_rust::types::SimulationConfig;
+    use amzn_career_pathway_activity_rust::user::UserGenerator;
+    use chrono::Duration;
+
+    fn profiles(count: usize) -> Vec<UserProfile> {
+        let config = SimulationConfig {
+            user_count: count,
+            location_count: 1,
+            curious_user_percentage: 0.0,
+            cloned_badge_percentage: 0.0,
+            seed: Some(7),
+            ..Default::default()
+        };
+        let registry = FacilityGenerator::with_seed(7).generate_facilities(&config).unwrap();
+        let users = UserGenerator::with_seed(7).generate_users(&config, &registry).unwrap();
+        users
+            .iter()
+            .filter(|user| !user.is_night_shift)
+            .take(count)
+            .map(|user| UserProfile::from_user(user, &config))
+            .collect()
+    }
+
+    fn detection(user_id: &str, anomaly_type: &str, timestamp: DateTime<Utc>) -> Detection {
+        Detection {
+            user_id: user_id.to_string(),
+            anomaly_type: anomaly_type.to_string(),
+            timestamp: Some(timestamp.to_rfc3339()),
+        }
+    }
+
+    #[test]
+    fn test_confusion_matrix_metrics() {
+        let matrix = ConfusionMatrix { true_positives: 3, false_positives: 1, false_negatives: 2, true_negatives: 10 };
+        assert_eq!(matrix.precision(), Some(0.75));
+        assert_eq!(matrix.recall(), Some(0.6));
+        assert_eq!(matrix.f1(), Some(6.0 / 9.0));
+        assert_eq!(ConfusionMatrix::default().precision(), None);
+        assert_eq!(ConfusionMatrix::default().f1(), None);
+    }
+
+    #[test]
+    fn test_evaluate_against_answer_key() {
+        let mut profiles = profiles(4);
+        profiles[0].has_cloned_badge = true;
+        profiles[1].has_cloned_badge = true;
+        profiles[2].is_curious = true;
+        let id = |index: usize| profiles[index].user_id.to_string();
+        let badge_number = profiles[2].identity.as_ref().unwrap().badge_number.to_string();
+        let start = Utc::now();
+
+        let detections = vec![
+            detection(&id(0), "cloned_badge", start + Duration::seconds(90)),
+            detection(&id(0), "cloned_badge", start + Duration::seconds(60)),
+            detection(&id(3), "cloned_badge", start),
+            detection(&badge_number, "curious_access", start),
+            detection("USER_unknown", "curious_access", start),
+        ];
+        let first_labeled_events = HashMap::from([((id(0), "cloned_badge"), start)]);
+
+        let evaluation = evaluate(&detections, &profiles, Some(&first_labeled_events));
+        assert_eq!(evaluation.users, 4);
+
+        let cloned = &evaluation.classes[0];
+        assert_eq!(
+            cloned.confusion_matrix,
+            ConfusionMatrix { true_positives: 1, false_positives: 1, false_negatives: 1, true_negatives: 1 }
+        );
+        assert_eq!(cloned.false_positives, vec![id(3)]);
+        assert_eq!(cloned.false_negatives, vec![id(1)]);
+        let time_to_detect = cloned.time_to_detect.as_ref().unwrap();
+        assert_eq!((time_to_detect.measured, time_to_detect.median_seconds), (1, 60.0));
+
+        let curious = &evaluation.classes[1];
+        assert_eq!(curious.confusion_matrix.true_positives, 1);
+        assert_eq!(curious.false_positives, vec!["USER_unknown".to_string()]);
+        assert_eq!(curious.precision, Some(0.5));
+        assert!(curious.time_to_detect.is_none());
+
+        let night_shift = &evaluation.classes[2];
+        assert_eq!(night_shift.confusion_matrix.true_negatives, 4);
+        assert_eq!(night_shift.recall, None);
+    }
+
+    #[test]
+    fn test_load_detections_from_report_or_json_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let report_path = dir.path().join("anomaly_report.json");
+        let lines_path = dir.path().join("alerts.jsonl");
+        std::fs::write(
+            &report_path,
+            r#"{"anomalies": [{"user_id": "USER_1", "anomaly_type": "cloned_badge", "severity": "high", "timestamp": "2024-01-01T09:00:00+00:00"}], "summary": {}}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            &lines_path,
+            "{\"user_id\": \"USER_1\", \"anomaly_type\": \"curious_access\"}\n\n{\"user_id\": \"USER_2\", \"anomaly_type\": \"cloned_badge\"}\n",
+        )
+        .unwrap();
+
+        let report = load_detections(report_path.to_str().unwrap()).unwrap();
+        assert_eq!(report.len(), 1);
+        assert_eq!(report[0].timestamp.as_deref(), Some("2024-01-01T09:00:00+00:00"));
+
+        let lines = load_detections(lines_path.to_str().unwrap()).unwrap();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[1].user_id, "USER_2");
+        assert!(lines[0].timestamp.is_none());
+    }
+
+    #[test]
+    fn test_load_first_labeled_events_from_json_lines_or_csv() {
+        let profiles = profiles(2);
+        let badge_number = profiles[1].identity.as_ref().unwrap().badge_number.to_string();
+        let id = |index: usize| profiles[index].user_id.to_string();
+        let dir = tempfile::tempdir().unwrap();
+        let json_path = dir.path().join("events.jsonl");
+        let csv_path = dir.path().join("events.csv");
+        let unlabeled_path = dir.path().join("unlabeled.csv");
+        let labels = |curious: bool| {
+            format!(
+                r#"{{"is_curious_attempt": {}, "is_impossible_traveler": false, "is_badge_reader_failure": false, "is_night_shift_event": false, "retry_attempt_number": null, "travel_time_violation": null, "geographical_distance": null}}"#,
+                curious
+            )
+        };
+        std::fs::write(
+            &json_path,
+            format!(
+                "{{\"user_id\": \"{0}\", \"timestamp\": \"2024-01-01T10:00:00Z\", \"metadata\": {1}}}\n{{\"user_id\": \"{0}\", \"timestamp\": \"2024-01-01T09:00:00Z\", \"metadata\": {1}}}\n{{\"user_id\": \"{0}\", \"timestamp\": \"2024-01-01T08:00:00Z\"}}\n",
+                id(0),
+                labels(true)
+            ),
+        )
+        .unwrap();
+        std::fs::write(
+            &csv_path,
+            format!(
+                "timestamp,badge_number,metadata_is_curious_attempt,metadata_is_impossible_traveler,metadata_is_night_shift_event\n\
+                 2024-01-01T09:00:00Z,{0},false,true,false\n\
+                 2024-01-01T08:00:00Z,{0},,,\n",
+                badge_number
+            ),
+        )
+        .unwrap();
+        std::fs::write(&unlabeled_path, format!("timestamp,badge_number\n2024-01-01T09:00:00Z,{}\n", badge_number)).unwrap();
+        let at = |hour: u32| "2024-01-01T00:00:00Z".parse::<DateTime<Utc>>().unwrap() + Duration::hours(hour as i64);
+
+        let json = load_first_labeled_events(json_path.to_str().unwrap(), &profiles).unwrap();
+        assert_eq!(json, HashMap::from([((id(0), "curious_access"), at(9))]));
+
+        let csv = load_first_labeled_events(csv_path.to_str().unwrap(), &profiles).unwrap();
+        assert_eq!(csv, HashMap::from([((id(1), "cloned_badge"), at(9))]));
+
+        assert!(load_first_labeled_events(unlabeled_path.to_str().unwrap(), &profiles).unwrap().is_empty());
+    }
+}
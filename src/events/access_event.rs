@@ -2,11 +2,13 @@
 //!
 //! This module contains access event structures and related functionality.
 
-use chrono::{DateTime, Utc};
-use serde::{Deserialize, Serialize};
+use chrono::{DateTime, FixedOffset, Offset, Utc};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-use crate::types::{BuildingId, UserId, EventType, FailureReason, LocationId, RoomId};
+use crate::events::EventFacilityContext;
+use crate::types::{BuildingId, UserId, EventType, FailureReason, LocationId, RoomId, RoomType, SecurityLevel};
 use crate::types::config::OutputFieldConfig;
+use crate::user::BadgeNumber;
 
 /// Metadata for access events with failure-specific information
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -151,6 +153,9 @@ pub struct AccessEvent {
     pub failure_reason: Option<FailureReason>,
     /// Additional metadata about the event
     pub metadata: Option<EventMetadata>,
+    /// UTC offset in effect at the event's location at the event time (e.g. `+09:00`)
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "utc_offset")]
+    pub local_offset: Option<FixedOffset>,
 }
 
 impl AccessEvent {
@@ -174,10 +179,12 @@ impl AccessEvent {
             event_type,
             failure_reason: None,
             metadata: None,
+            local_offset: None,
         }
     }
 
     /// Create a new access event with failure reason and metadata
+    #[allow(clippy::too_many_arguments)]
     pub fn new_with_failure_info(
         timestamp: DateTime<Utc>,
         user_id: UserId,
@@ -199,6 +206,7 @@ impl AccessEvent {
             event_type,
             failure_reason,
             metadata,
+            local_offset: None,
         }
     }
 
@@ -264,6 +272,34 @@ impl AccessEvent {
     pub fn get_retry_attempt_number(&self) -> Option<u8> {
         self.metadata.as_ref().and_then(|m| m.retry_attempt_number)
     }
+
+    /// Get the local wall-clock time of the event, or UTC when the offset is unknown
+    pub fn local_timestamp(&self) -> DateTime<FixedOffset> {
+        self.timestamp.with_timezone(&self.local_offset.unwrap_or_else(|| Utc.fix()))
+    }
+}
+
+/// Serialize UTC offsets as `+HH:MM` strings
+mod utc_offset {
+    use super::*;
+
+    pub(super) fn serialize<S: Serializer>(
+        offset: &Option<FixedOffset>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        match offset {
+            Some(offset) => serializer.serialize_str(&offset.to_string()),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<FixedOffset>, D::Error> {
+        Option::<String>::deserialize(deserializer)?
+            .map(|offset| offset.parse().map_err(serde::de::Error::custom))
+            .transpose()
+    }
 }
 
 /// Filtered access event structure for custom serialization based on field configuration
@@ -271,8 +307,12 @@ impl AccessEvent {
 pub struct FilteredAccessEvent {
     /// Timestamp when the event occurred (always included)
     pub timestamp: DateTime<Utc>,
-    /// ID of the user who attempted access (always included)
-    pub user_id: UserId,
+    /// ID of the user who attempted access (included unless keyed by badge number)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<UserId>,
+    /// Badge number presented at the reader (replaces user_id when keyed by badge number)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub badge_number: Option<BadgeNumber>,
     /// ID of the room that was accessed (always included)
     pub room_id: RoomId,
     /// ID of the building containing the room (always included)
@@ -290,6 +330,24 @@ pub struct FilteredAccessEvent {
     /// Additional metadata about the event (optional based on configuration)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub metadata: Option<EventMetadata>,
+    /// UTC offset at the event's location (optional based on configuration)
+    #[serde(skip_serializing_if = "Option::is_none", serialize_with = "utc_offset::serialize")]
+    pub local_offset: Option<FixedOffset>,
+    /// Name of the room (optional, requires facility names and a location registry)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub room_name: Option<String>,
+    /// Type of the room (optional, requires facility names and a location registry)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub room_type: Option<RoomType>,
+    /// Security level of the room (optional, requires facility names and a location registry)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub security_level: Option<SecurityLevel>,
+    /// Name of the building (optional, requires facility names and a location registry)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub building_name: Option<String>,
+    /// Name of the location (optional, requires facility names and a location registry)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location_name: Option<String>,
 }
 
 impl FilteredAccessEvent {
@@ -298,7 +356,9 @@ impl FilteredAccessEvent {
         Self {
             // Core fields are always included
             timestamp: event.timestamp,
-            user_id: event.user_id,
+            user_id: (!field_config.key_by_badge_number).then_some(event.user_id),
+            // The badge number is filled in by `with_badge_number`
+            badge_number: None,
             room_id: event.room_id,
             building_id: event.building_id,
             location_id: event.location_id,
@@ -319,9 +379,42 @@ impl FilteredAccessEvent {
             } else {
                 None
             },
+            local_offset: if field_config.include_local_offset || field_config.include_all {
+                event.local_offset
+            } else {
+                None
+            },
+            // Facility names are filled in by `with_facility_names`
+            room_name: None,
+            room_type: None,
+            security_level: None,
+            building_name: None,
+            location_name: None,
         }
     }
 
+    /// Fill in the facility name fields from a resolved facility context
+    ///
+    /// Fields stay empty when facility names are disabled or the record is unknown.
+    pub fn with_facility_names(mut self, context: &EventFacilityContext<'_>, field_config: &OutputFieldConfig) -> Self {
+        if field_config.include_facility_names {
+            self.room_name = context.room_name().map(str::to_string);
+            self.room_type = context.room.map(|room| room.room_type);
+            self.security_level = context.room.map(|room| room.security_level);
+            self.building_name = context.building_name().map(str::to_string);
+            self.location_name = context.location_name().map(str::to_string);
+        }
+        self
+    }
+
+    /// Fill in the badge number when the output is keyed by badge number
+    pub fn with_badge_number(mut self, badge_number: Option<BadgeNumber>, field_config: &OutputFieldConfig) -> Self {
+        if field_config.key_by_badge_number {
+            self.badge_number = badge_number;
+        }
+        self
+    }
+
     /// Get the core field names that are always included in output
     pub fn get_core_field_names() -> Vec<&'static str> {
         vec![
@@ -347,6 +440,12 @@ impl FilteredAccessEvent {
         if field_config.include_metadata || field_config.include_all {
             fields.push("metadata");
         }
+        if field_config.include_local_offset || field_config.include_all {
+            fields.push("local_offset");
+        }
+        if field_config.include_facility_names {
+            fields.extend(["room_name", "room_type", "security_level", "building_name", "location_name"]);
+        }
         
         fields
     }
@@ -354,6 +453,9 @@ impl FilteredAccessEvent {
     /// Get all field names that will be included in output based on configuration
     pub fn get_all_field_names(field_config: &OutputFieldConfig) -> Vec<&'static str> {
         let mut fields = Self::get_core_field_names();
+        if field_config.key_by_badge_number {
+            fields[1] = "badge_number";
+        }
         fields.extend(Self::get_optional_field_names(field_config));
         fields
     }
@@ -363,6 +465,7 @@ impl FilteredAccessEvent {
 mod tests {
     use super::*;
     use crate::types::{BuildingId, UserId, LocationId, RoomId};
+    use chrono::TimeZone;
 
     #[test]
     fn test_access_attempt_creation() {
@@ -571,6 +674,28 @@ mod tests {
         assert_eq!(badge_failure_metadata.retry_attempt_number, Some(2));
     }
 
+    #[test]
+    fn test_local_offset_serialization() {
+        let mut event = AccessEvent::new(
+            Utc.with_ymd_and_hms(2024, 1, 15, 1, 30, 0).unwrap(),
+            UserId::new(),
+            RoomId::new(),
+            BuildingId::new(),
+            LocationId::new(),
+            true,
+            EventType::Success,
+        );
+        assert!(!serde_json::to_string(&event).unwrap().contains("local_offset"));
+
+        event.local_offset = FixedOffset::east_opt(9 * 3600);
+        let json = serde_json::to_string(&event).unwrap();
+        assert!(json.contains("\"local_offset\":\"+09:00\""));
+        assert_eq!(event.local_timestamp().to_rfc3339(), "2024-01-15T10:30:00+09:00");
+
+        let parsed: AccessEvent = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.local_offset, event.local_offset);
+    }
+
     #[test]
     fn test_filtered_access_event_minimal_output() {
         let timestamp = Utc::now();
@@ -597,7 +722,7 @@ mod tests {
 
         // Core fields should be present
         assert_eq!(filtered_event.timestamp, timestamp);
-        assert_eq!(filtered_event.user_id, user_id);
+        assert_eq!(filtered_event.user_id, Some(user_id));
         assert_eq!(filtered_event.room_id, room_id);
         assert_eq!(filtered_event.building_id, building_id);
         assert_eq!(filtered_event.location_id, location_id);
@@ -635,12 +760,15 @@ mod tests {
             include_event_type: false,
             include_metadata: false,
             include_all: false,
+            include_facility_names: false,
+            include_local_offset: false,
+            key_by_badge_number: false,
         };
         let filtered_event = FilteredAccessEvent::from_access_event(&event, &field_config);
 
         // Core fields should be present
         assert_eq!(filtered_event.timestamp, timestamp);
-        assert_eq!(filtered_event.user_id, user_id);
+        assert_eq!(filtered_event.user_id, Some(user_id));
         assert!(!filtered_event.success);
 
         // Only failure_reason should be included
@@ -675,6 +803,9 @@ mod tests {
             include_event_type: true,
             include_metadata: false,
             include_all: false,
+            include_facility_names: false,
+            include_local_offset: false,
+            key_by_badge_number: false,
         };
         let filtered_event = FilteredAccessEvent::from_access_event(&event, &field_config);
 
@@ -711,6 +842,9 @@ mod tests {
             include_event_type: false,
             include_metadata: true,
             include_all: false,
+            include_facility_names: false,
+            include_local_offset: false,
+            key_by_badge_number: false,
         };
         let filtered_event = FilteredAccessEvent::from_access_event(&event, &field_config);
 
@@ -748,6 +882,9 @@ mod tests {
             include_event_type: false,
             include_metadata: false,
             include_all: true,
+            include_facility_names: false,
+            include_local_offset: false,
+            key_by_badge_number: false,
         };
         let filtered_event = FilteredAccessEvent::from_access_event(&event, &field_config);
 
@@ -784,6 +921,9 @@ mod tests {
             include_event_type: false,
             include_metadata: false,
             include_all: true,
+            include_facility_names: false,
+            include_local_offset: false,
+            key_by_badge_number: false,
         };
         let filtered_event = FilteredAccessEvent::from_access_event(&event, &field_config);
 
@@ -812,6 +952,9 @@ mod tests {
             include_event_type: false,
             include_metadata: true,
             include_all: false,
+            include_facility_names: false,
+            include_local_offset: false,
+            key_by_badge_number: false,
         };
         let optional_fields = FilteredAccessEvent::get_optional_field_names(&partial_config);
         assert_eq!(optional_fields, vec!["failure_reason", "metadata"]);
@@ -822,16 +965,51 @@ mod tests {
             include_event_type: false,
             include_metadata: false,
             include_all: true,
+            include_facility_names: false,
+            include_local_offset: false,
+            key_by_badge_number: false,
         };
         let optional_fields = FilteredAccessEvent::get_optional_field_names(&all_config);
-        assert_eq!(optional_fields, vec!["event_type", "failure_reason", "metadata"]);
+        assert_eq!(optional_fields, vec!["event_type", "failure_reason", "metadata", "local_offset"]);
 
         // Test all field names
         let all_fields = FilteredAccessEvent::get_all_field_names(&all_config);
         assert_eq!(all_fields, vec![
             "timestamp", "user_id", "room_id", "building_id", "location_id", "success",
-            "event_type", "failure_reason", "metadata"
+            "event_type", "failure_reason", "metadata", "local_offset"
         ]);
+
+        // Facility names are separate from include_all
+        let names_config = OutputFieldConfig { include_facility_names: true, ..Default::default() };
+        let optional_fields = FilteredAccessEvent::get_optional_field_names(&names_config);
+        assert_eq!(optional_fields, vec!["room_name", "room_type", "security_level", "building_name", "location_name"]);
+    }
+
+    #[test]
+    fn test_filtered_access_event_facility_names() {
+        use crate::facility::FacilityGenerator;
+        use crate::types::SimulationConfig;
+
+        let config = SimulationConfig { location_count: 1, ..Default::default() };
+        let registry = FacilityGenerator::with_seed(9).generate_facilities(&config).unwrap();
+        let location = &registry.locations[0];
+        let building = &location.buildings[0];
+        let room = &building.rooms[0];
+        let event = AccessEvent::new(Utc::now(), UserId::new(), room.id, building.id, location.id, true, EventType::Success);
+        let context = EventFacilityContext::resolve(Some(&registry), &event);
+
+        let hidden = FilteredAccessEvent::from_access_event(&event, &OutputFieldConfig::default())
+            .with_facility_names(&context, &OutputFieldConfig::default());
+        assert!(!serde_json::to_string(&hidden).unwrap().contains("room_name"));
+
+        let names_config = OutputFieldConfig { include_facility_names: true, ..Default::default() };
+        let named = FilteredAccessEvent::from_access_event(&event, &names_config).with_facility_names(&context, &names_config);
+        let value = serde_json::to_value(&named).unwrap();
+        assert_eq!(value["room_name"], room.name.as_str());
+        assert_eq!(value["room_type"], serde_json::to_value(room.room_type).unwrap());
+        assert_eq!(value["security_level"], serde_json::to_value(room.security_level).unwrap());
+        assert_eq!(value["building_name"], building.name.as_str());
+        assert_eq!(value["location_name"], location.name.as_str());
     }
 
     #[test]
@@ -875,6 +1053,9 @@ mod tests {
             include_event_type: false,
             include_metadata: false,
             include_all: true,
+            include_facility_names: false,
+            include_local_offset: false,
+            key_by_badge_number: false,
         };
         let filtered_event_all = FilteredAccessEvent::from_access_event(&event, &all_config);
         let json_all = serde_json::to_string(&filtered_event_all).unwrap();
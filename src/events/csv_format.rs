@@ -0,0 +1,288 @@
+//! CSV event formatting
+//!
+//! This module renders access events as CSV rows for spreadsheet and dataframe tooling.
+//! The header row is derived from `FilteredAccessEvent::get_all_field_names`, so the
+//! same `OutputFieldConfig` toggles apply as for JSON output. The nested metadata field
+//! is flattened into one `metadata_`-prefixed column per `EventMetadata` field.
+
+use chrono::SecondsFormat;
+use serde::Serialize;
+
+use crate::events::{AccessEvent, EventMetadata, FilteredAccessEvent};
+use crate::types::config::OutputFieldConfig;
+
+/// Prefix applied to flattened metadata column names
+pub const METADATA_COLUMN_PREFIX: &str = "metadata_";
+
+/// Metadata fields in the order they are flattened into columns
+const METADATA_FIELD_NAMES: [&str; 7] = [
+    "is_curious_attempt",
+    "is_impossible_traveler",
+    "is_badge_reader_failure",
+    "is_night_shift_event",
+    "retry_attempt_number",
+    "travel_time_violation_seconds",
+    "geographical_distance",
+];
+
+/// Formatter that renders access events as CSV rows according to the output field configuration
+#[derive(Debug, Clone)]
+pub struct CsvEventFormatter {
+    field_config: OutputFieldConfig,
+}
+
+impl CsvEventFormatter {
+    /// Create a new CSV formatter for the given field configuration
+    pub fn new(field_config: OutputFieldConfig) -> Self {
+        Self { field_config }
+    }
+
+    /// Get the column names, with the metadata field expanded into prefixed columns
+    pub fn column_names(&self) -> Vec<String> {
+        let mut columns = Vec::new();
+        for field in FilteredAccessEvent::get_all_field_names(&self.field_config) {
+            if field == "metadata" {
+                columns.extend(
+                    METADATA_FIELD_NAMES
+                        .iter()
+                        .map(|name| format!("{}{}", METADATA_COLUMN_PREFIX, name)),
+                );
+            } else {
+                columns.push(field.to_string());
+            }
+        }
+        columns
+    }
+
+    /// Get the header row (without a trailing newline)
+    pub fn header(&self) -> String {
+        format_csv_row(&self.column_names())
+    }
+
+    /// Format an access event as a CSV row (without a trailing newline)
+    pub fn format_event(&self, event: &AccessEvent) -> String {
+        let filtered_event = FilteredAccessEvent::from_access_event(event, &self.field_config);
+        self.format_filtered_event(&filtered_event)
+    }
+
+    /// Format an already filtered access event as a CSV row (without a trailing newline)
+    pub fn format_filtered_event(&self, event: &FilteredAccessEvent) -> String {
+        let mut values = Vec::new();
+
+        for field in FilteredAccessEvent::get_all_field_names(&self.field_config) {
+            match field {
+                "timestamp" => {
+                    values.push(event.timestamp.to_rfc3339_opts(SecondsFormat::AutoSi, true))
+                }
+                "user_id" => values.push(optional_value(event.user_id.as_ref())),
+                "badge_number" => values.push(optional_value(event.badge_number.as_ref())),
+                "room_id" => values.push(serialized_name(&event.room_id)),
+                "building_id" => values.push(serialized_name(&event.building_id)),
+                "location_id" => values.push(serialized_name(&event.location_id)),
+                "success" => values.push(event.success.to_string()),
+                "event_type" => values.push(optional_value(event.event_type.as_ref())),
+                "failure_reason" => values.push(optional_value(event.failure_reason.as_ref())),
+                "metadata" => values.extend(metadata_values(event.metadata.as_ref())),
+                "local_offset" => values.push(event.local_offset.map(|o| o.to_string()).unwrap_or_default()),
+                "room_name" => values.push(event.room_name.clone().unwrap_or_default()),
+                "room_type" => values.push(optional_value(event.room_type.as_ref())),
+                "security_level" => values.push(optional_value(event.security_level.as_ref())),
+                "building_name" => values.push(event.building_name.clone().unwrap_or_default()),
+                "location_name" => values.push(event.location_name.clone().unwrap_or_default()),
+                _ => values.push(String::new()),
+            }
+        }
+
+        format_csv_row(&values)
+    }
+}
+
+/// Escape a single CSV field
+///
+/// Fields containing commas, double quotes, or line breaks are wrapped in double quotes,
+/// and embedded double quotes are doubled.
+pub fn escape_csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Join fields into a single CSV row, escaping each one
+pub fn format_csv_row<S: AsRef<str>>(fields: &[S]) -> String {
+    fields.iter().map(|field| escape_csv_field(field.as_ref())).collect::<Vec<_>>().join(",")
+}
+
+/// Split a single CSV row into fields, undoing the quoting applied by [`format_csv_row`]
+pub fn parse_csv_row(line: &str) -> Result<Vec<String>, String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut chars = line.trim_end_matches(['\r', '\n']).chars().peekable();
+    let mut in_quotes = false;
+
+    while let Some(c) = chars.next() {
+        match (c, in_quotes) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                chars.next();
+                field.push('"');
+            }
+            ('"', true) => in_quotes = false,
+            ('"', false) if field.is_empty() => in_quotes = true,
+            (',', false) => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+
+    if in_quotes {
+        return Err("Unterminated quoted field".to_string());
+    }
+    fields.push(field);
+    Ok(fields)
+}
+
+/// Render a value using its serialized (JSON) string form so CSV matches JSON output
+fn serialized_name<T: Serialize>(value: &T) -> String {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(s)) => s,
+        Ok(other) => other.to_string(),
+        Err(_) => String::new(),
+    }
+}
+
+/// Render an optional value, leaving the cell empty when absent
+fn optional_value<T: Serialize>(value: Option<&T>) -> String {
+    value.map(serialized_name).unwrap_or_default()
+}
+
+/// Flatten event metadata into column values, leaving cells empty when metadata is absent
+fn metadata_values(metadata: Option<&EventMetadata>) -> Vec<String> {
+    match metadata {
+        Some(metadata) => vec![
+            metadata.is_curious_attempt.to_string(),
+            metadata.is_impossible_traveler.to_string(),
+            metadata.is_badge_reader_failure.to_string(),
+            metadata.is_night_shift_event.to_string(),
+            metadata.retry_attempt_number.map(|n| n.to_string()).unwrap_or_default(),
+            metadata
+                .travel_time_violation
+                .map(|d| d.num_seconds().to_string())
+                .unwrap_or_default(),
+            metadata.geographical_distance.map(|d| d.to_string()).unwrap_or_default(),
+        ],
+        None => vec![String::new(); METADATA_FIELD_NAMES.len()],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{BuildingId, EventType, FailureReason, LocationId, RoomId, UserId};
+    use chrono::{Duration, TimeZone, Utc};
+
+    fn create_failure_event() -> AccessEvent {
+        AccessEvent::new_with_failure_info(
+            Utc.with_ymd_and_hms(2024, 1, 15, 10, 30, 0).unwrap(),
+            UserId::new(),
+            RoomId::new(),
+            BuildingId::new(),
+            LocationId::new(),
+            false,
+            EventType::Suspicious,
+            Some(FailureReason::ImpossibleTraveler),
+            Some(EventMetadata::impossible_traveler(Duration::hours(3), 1234.5)),
+        )
+    }
+
+    #[test]
+    fn test_default_header_matches_core_fields() {
+        let formatter = CsvEventFormatter::new(OutputFieldConfig::default());
+        assert_eq!(formatter.header(), "timestamp,user_id,room_id,building_id,location_id,success");
+    }
+
+    #[test]
+    fn test_header_flattens_metadata_columns() {
+        let formatter = CsvEventFormatter::new(OutputFieldConfig {
+            include_metadata: true,
+            ..Default::default()
+        });
+        let columns = formatter.column_names();
+
+        assert!(!columns.contains(&"metadata".to_string()));
+        assert!(columns.contains(&"metadata_is_curious_attempt".to_string()));
+        assert!(columns.contains(&"metadata_travel_time_violation_seconds".to_string()));
+        assert_eq!(columns.len(), 6 + METADATA_FIELD_NAMES.len());
+    }
+
+    #[test]
+    fn test_row_matches_header_width() {
+        let event = create_failure_event();
+        for field_config in [
+            OutputFieldConfig::default(),
+            OutputFieldConfig { include_failure_reason: true, ..Default::default() },
+            OutputFieldConfig { include_all: true, ..Default::default() },
+        ] {
+            let formatter = CsvEventFormatter::new(field_config);
+            let row = formatter.format_event(&event);
+            assert_eq!(row.split(',').count(), formatter.column_names().len());
+        }
+    }
+
+    #[test]
+    fn test_row_values_use_serialized_names() {
+        let event = create_failure_event();
+        let formatter =
+            CsvEventFormatter::new(OutputFieldConfig { include_all: true, ..Default::default() });
+        let row = formatter.format_event(&event);
+        let values: Vec<&str> = row.split(',').collect();
+
+        assert_eq!(values[0], "2024-01-15T10:30:00Z");
+        assert!(values[1].starts_with("USER_"));
+        assert_eq!(values[5], "false");
+        assert_eq!(values[6], "Suspicious");
+        assert_eq!(values[7], "ImpossibleTraveler");
+        assert_eq!(values[9], "true"); // metadata_is_impossible_traveler
+        assert_eq!(values[13], "10800"); // metadata_travel_time_violation_seconds
+        assert_eq!(values[14], "1234.5");
+    }
+
+    #[test]
+    fn test_missing_optional_values_are_empty_cells() {
+        let event = AccessEvent::new(
+            Utc.with_ymd_and_hms(2024, 1, 15, 10, 30, 0).unwrap(),
+            UserId::new(),
+            RoomId::new(),
+            BuildingId::new(),
+            LocationId::new(),
+            true,
+            EventType::Success,
+        );
+        let formatter =
+            CsvEventFormatter::new(OutputFieldConfig { include_all: true, ..Default::default() });
+        let row = formatter.format_event(&event);
+
+        assert!(row.ends_with(",Success,,,,,,,,,"));
+    }
+
+    #[test]
+    fn test_escape_csv_field() {
+        assert_eq!(escape_csv_field("plain"), "plain");
+        assert_eq!(escape_csv_field("a,b"), "\"a,b\"");
+        assert_eq!(escape_csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(escape_csv_field("line\nbreak"), "\"line\nbreak\"");
+        assert_eq!(escape_csv_field(""), "");
+    }
+
+    #[test]
+    fn test_format_csv_row() {
+        assert_eq!(format_csv_row(&["a", "b,c", "d"]), "a,\"b,c\",d");
+    }
+
+    #[test]
+    fn test_parse_csv_row_round_trips() {
+        let fields = ["a", "b,c", "say \"hi\"", ""];
+        assert_eq!(parse_csv_row(&format_csv_row(&fields)).unwrap(), fields);
+        assert_eq!(parse_csv_row("x,y\r\n").unwrap(), ["x", "y"]);
+        assert!(parse_csv_row("\"open,field").is_err());
+    }
+}
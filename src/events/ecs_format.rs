@@ -0,0 +1,208 @@
+//! Elastic Common Schema (ECS) event formatting
+//!
+//! This module renders access events as ECS documents so Elastic-based detection rules
+//! can run unmodified against simulator output. Each document carries `@timestamp`,
+//! `event.*` categorization with `event.category: physical_access`, the badge holder in
+//! `user.id`, the site of the badge reader in `source.geo`, and the site's UTC offset in
+//! `event.timezone` when it is known. Location coordinates are
+//! written as a `geo_point` object under `source.geo.location`, which supports
+//! geo-distance queries for impossible-travel rules.
+//!
+//! Room, building and location identifiers and names have no ECS field, so they are kept
+//! in the custom `badge_access` namespace. The output field toggles do not apply to this
+//! schema, except that simulation metadata is only added when metadata output is enabled.
+
+use chrono::SecondsFormat;
+use serde_json::{json, Map, Value};
+
+use crate::events::{AccessEvent, EventFacilityContext};
+use crate::types::config::OutputFieldConfig;
+use crate::types::EventType;
+
+/// ECS version the output conforms to
+pub const ECS_VERSION: &str = "8.11.0";
+
+/// Dataset name reported in `event.dataset`
+const DATASET: &str = "badge_access.events";
+
+/// Formatter that renders access events as ECS documents
+#[derive(Debug, Clone)]
+pub struct EcsEventFormatter {
+    field_config: OutputFieldConfig,
+}
+
+impl EcsEventFormatter {
+    /// Create a new ECS formatter for the given field configuration
+    pub fn new(field_config: OutputFieldConfig) -> Self {
+        Self { field_config }
+    }
+
+    /// Map an access event to an ECS document
+    pub fn to_value(&self, event: &AccessEvent, context: &EventFacilityContext<'_>) -> Value {
+        let mut event_fields = Map::new();
+        event_fields.insert("kind".to_string(), json!("event"));
+        event_fields.insert("category".to_string(), json!(["physical_access"]));
+        event_fields.insert(
+            "type".to_string(),
+            json!(["access", if event.success { "allowed" } else { "denied" }]),
+        );
+        event_fields.insert("action".to_string(), json!("badge-access"));
+        event_fields.insert("outcome".to_string(), json!(if event.success { "success" } else { "failure" }));
+        event_fields.insert("code".to_string(), json!(event_code(event.event_type)));
+        event_fields.insert("severity".to_string(), json!(severity(event.event_type)));
+        if let Some(reason) = event.failure_reason {
+            event_fields.insert("reason".to_string(), json!(reason.to_string()));
+        }
+        event_fields.insert("dataset".to_string(), json!(DATASET));
+        if let Some(offset) = event.local_offset {
+            event_fields.insert("timezone".to_string(), json!(offset.to_string()));
+        }
+
+        let mut geo = Map::new();
+        if let Some(location_name) = context.location_name() {
+            geo.insert("name".to_string(), json!(location_name));
+        }
+        if let Some((latitude, longitude)) = context.coordinates() {
+            geo.insert("location".to_string(), json!({ "lat": latitude, "lon": longitude }));
+        }
+
+        let mut observer = Map::new();
+        observer.insert("type".to_string(), json!("badge-reader"));
+        if let Some(room_name) = context.room_name() {
+            observer.insert("name".to_string(), json!(room_name));
+        }
+
+        let mut badge_access = Map::new();
+        badge_access.insert("room_id".to_string(), json!(event.room_id.to_string()));
+        if let Some(room_name) = context.room_name() {
+            badge_access.insert("room_name".to_string(), json!(room_name));
+        }
+        badge_access.insert("building_id".to_string(), json!(event.building_id.to_string()));
+        if let Some(building_name) = context.building_name() {
+            badge_access.insert("building_name".to_string(), json!(building_name));
+        }
+        badge_access.insert("location_id".to_string(), json!(event.location_id.to_string()));
+        if let Some(location_name) = context.location_name() {
+            badge_access.insert("location_name".to_string(), json!(location_name));
+        }
+        if self.field_config.include_metadata || self.field_config.include_all {
+            if let Some(metadata) = &event.metadata {
+                badge_access.insert("metadata".to_string(), json!(metadata));
+            }
+        }
+
+        json!({
+            "@timestamp": event.timestamp.to_rfc3339_opts(SecondsFormat::Millis, true),
+            "ecs": { "version": ECS_VERSION },
+            "event": event_fields,
+            "user": { "id": event.user_id.to_string() },
+            "source": { "geo": geo },
+            "observer": observer,
+            "badge_access": badge_access,
+        })
+    }
+
+    /// Format an access event as a single ECS JSON line (without a trailing newline)
+    pub fn format_event(
+        &self,
+        event: &AccessEvent,
+        context: &EventFacilityContext<'_>,
+    ) -> Result<String, String> {
+        serde_json::to_string(&self.to_value(event, context))
+            .map_err(|e| format!("Failed to serialize event to ECS JSON: {}", e))
+    }
+}
+
+/// Map an event type to the `event.code` value
+fn event_code(event_type: EventType) -> &'static str {
+    match event_type {
+        EventType::Success => "success",
+        EventType::Failure => "failure",
+        EventType::InvalidBadge => "invalid_badge",
+        EventType::OutsideHours => "outside_hours",
+        EventType::Suspicious => "suspicious",
+    }
+}
+
+/// Map an event type to the numeric `event.severity` value
+fn severity(event_type: EventType) -> u8 {
+    match event_type {
+        EventType::Success => 1,
+        EventType::Failure => 2,
+        EventType::InvalidBadge | EventType::OutsideHours => 3,
+        EventType::Suspicious => 4,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::facility::FacilityGenerator;
+    use crate::types::{BuildingId, FailureReason, LocationId, RoomId, SimulationConfig, UserId};
+    use chrono::{TimeZone, Utc};
+
+    fn create_test_event(success: bool, event_type: EventType) -> AccessEvent {
+        AccessEvent::new(
+            Utc.with_ymd_and_hms(2024, 1, 15, 10, 30, 0).unwrap(),
+            UserId::new(),
+            RoomId::new(),
+            BuildingId::new(),
+            LocationId::new(),
+            success,
+            event_type,
+        )
+    }
+
+    #[test]
+    fn test_success_document() {
+        let event = create_test_event(true, EventType::Success);
+        let value = EcsEventFormatter::new(OutputFieldConfig::default())
+            .to_value(&event, &EventFacilityContext::default());
+
+        assert_eq!(value["@timestamp"], "2024-01-15T10:30:00.000Z");
+        assert_eq!(value["ecs"]["version"], ECS_VERSION);
+        assert_eq!(value["event"]["category"][0], "physical_access");
+        assert_eq!(value["event"]["type"][1], "allowed");
+        assert_eq!(value["event"]["outcome"], "success");
+        assert!(value["event"].get("reason").is_none());
+        assert_eq!(value["user"]["id"], event.user_id.to_string());
+        assert_eq!(value["badge_access"]["room_id"], event.room_id.to_string());
+        assert!(value["source"]["geo"].get("location").is_none());
+    }
+
+    #[test]
+    fn test_failure_document() {
+        let mut event = create_test_event(false, EventType::Suspicious);
+        event.failure_reason = Some(FailureReason::ImpossibleTraveler);
+        let value = EcsEventFormatter::new(OutputFieldConfig::default())
+            .to_value(&event, &EventFacilityContext::default());
+
+        assert_eq!(value["event"]["outcome"], "failure");
+        assert_eq!(value["event"]["type"][1], "denied");
+        assert_eq!(value["event"]["code"], "suspicious");
+        assert_eq!(value["event"]["reason"], "Impossible Traveler");
+        assert_eq!(value["event"]["severity"], 4);
+    }
+
+    #[test]
+    fn test_coordinates_go_into_geo_location() {
+        let config = SimulationConfig { location_count: 1, ..Default::default() };
+        let registry = FacilityGenerator::with_seed(5).generate_facilities(&config).unwrap();
+        let location = &registry.locations[0];
+        let building = &location.buildings[0];
+
+        let mut event = create_test_event(true, EventType::Success);
+        event.room_id = building.rooms[0].id;
+        event.building_id = building.id;
+        event.location_id = location.id;
+
+        let context = EventFacilityContext::resolve(Some(&registry), &event);
+        let value = EcsEventFormatter::new(OutputFieldConfig::default()).to_value(&event, &context);
+
+        assert_eq!(value["source"]["geo"]["name"], location.name.as_str());
+        assert_eq!(value["source"]["geo"]["location"]["lat"], location.coordinates.0);
+        assert_eq!(value["source"]["geo"]["location"]["lon"], location.coordinates.1);
+        assert_eq!(value["observer"]["name"], building.rooms[0].name.as_str());
+        assert_eq!(value["badge_access"]["building_name"], building.name.as_str());
+    }
+}
@@ -0,0 +1,90 @@
+//! Facility context for access events
+//!
+//! This module resolves the room, building and location records an access event refers to,
+//! so schema formatters can include names and coordinates alongside the raw identifiers.
+
+use crate::events::AccessEvent;
+use crate::facility::{Building, Location, LocationRegistry, Room};
+
+/// Facility records referenced by an access event
+///
+/// Each record is `None` when no registry is available or the identifier is unknown.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EventFacilityContext<'a> {
+    /// Room the access attempt was made at
+    pub room: Option<&'a Room>,
+    /// Building containing the room
+    pub building: Option<&'a Building>,
+    /// Location containing the building
+    pub location: Option<&'a Location>,
+}
+
+impl<'a> EventFacilityContext<'a> {
+    /// Look up the facility records for an event in an optional registry
+    pub fn resolve(registry: Option<&'a LocationRegistry>, event: &AccessEvent) -> Self {
+        match registry {
+            Some(registry) => Self {
+                room: registry.get_room(event.room_id),
+                building: registry.get_building(event.building_id),
+                location: registry.get_location(event.location_id),
+            },
+            None => Self::default(),
+        }
+    }
+
+    /// Get the room name, if known
+    pub fn room_name(&self) -> Option<&'a str> {
+        self.room.map(|room| room.name.as_str())
+    }
+
+    /// Get the building name, if known
+    pub fn building_name(&self) -> Option<&'a str> {
+        self.building.map(|building| building.name.as_str())
+    }
+
+    /// Get the location name, if known
+    pub fn location_name(&self) -> Option<&'a str> {
+        self.location.map(|location| location.name.as_str())
+    }
+
+    /// Get the location coordinates as (latitude, longitude), if known
+    pub fn coordinates(&self) -> Option<(f64, f64)> {
+        self.location.map(|location| location.coordinates)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::facility::FacilityGenerator;
+    use crate::types::{EventType, SimulationConfig};
+    use chrono::Utc;
+
+    #[test]
+    fn test_resolve_from_registry() {
+        let config = SimulationConfig { location_count: 1, ..Default::default() };
+        let registry = FacilityGenerator::with_seed(7).generate_facilities(&config).unwrap();
+        let location = &registry.locations[0];
+        let building = &location.buildings[0];
+        let room = &building.rooms[0];
+
+        let event = AccessEvent::new(
+            Utc::now(),
+            crate::types::UserId::new(),
+            room.id,
+            building.id,
+            location.id,
+            true,
+            EventType::Success,
+        );
+
+        let context = EventFacilityContext::resolve(Some(&registry), &event);
+        assert_eq!(context.room_name(), Some(room.name.as_str()));
+        assert_eq!(context.building_name(), Some(building.name.as_str()));
+        assert_eq!(context.location_name(), Some(location.name.as_str()));
+        assert_eq!(context.coordinates(), Some(location.coordinates));
+
+        let empty = EventFacilityContext::resolve(None, &event);
+        assert!(empty.room.is_none() && empty.building.is_none() && empty.location.is_none());
+    }
+}
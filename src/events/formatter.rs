@@ -0,0 +1,266 @@
+//! Line-oriented event formatting
+//!
+//! This module renders access events as text lines in the configured output format.
+//! It is shared by every event sink that writes text, so stdout and file output stay
+//! byte-for-byte identical for the same configuration.
+//!
+//! Schema formats (OCSF, ECS, CEF and LEEF) include facility names and coordinates when a location
+//! registry is attached with [`EventFormatter::with_registry`]. JSON and CSV output add facility
+//! name columns only when `include_facility_names` is set in the field configuration, and
+//! replace the user id with the badge number from the directory attached with
+//! [`EventFormatter::with_identities`] when `key_by_badge_number` is set.
+
+use std::sync::Arc;
+
+use crate::events::{
+    AccessEvent, CefEventFormatter, CsvEventFormatter, EcsEventFormatter, EventFacilityContext,
+    FilteredAccessEvent, LeefEventFormatter, OcsfEventFormatter,
+};
+use crate::facility::LocationRegistry;
+use crate::types::config::OutputFieldConfig;
+use crate::types::{OutputFormat, SimulationConfig};
+use crate::user::IdentityDirectory;
+
+/// Formatter that renders access events as lines in a specific output format
+#[derive(Debug, Clone)]
+pub struct EventFormatter {
+    output_format: OutputFormat,
+    field_config: OutputFieldConfig,
+    registry: Option<Arc<LocationRegistry>>,
+    identities: Option<Arc<IdentityDirectory>>,
+}
+
+impl EventFormatter {
+    /// Create a new formatter for the given output format and field configuration
+    pub fn new(output_format: OutputFormat, field_config: OutputFieldConfig) -> Self {
+        Self { output_format, field_config, registry: None, identities: None }
+    }
+
+    /// Attach a location registry used to resolve facility names and coordinates
+    pub fn with_registry(mut self, registry: Arc<LocationRegistry>) -> Self {
+        self.registry = Some(registry);
+        self
+    }
+
+    /// Attach an identity directory used to resolve badge numbers
+    pub fn with_identities(mut self, identities: Arc<IdentityDirectory>) -> Self {
+        self.identities = Some(identities);
+        self
+    }
+
+    /// Create a formatter from the output format and field settings of a simulation configuration
+    pub fn from_config(config: &SimulationConfig) -> Result<Self, String> {
+        Ok(Self::new(config.get_output_format()?, config.output_fields.clone()))
+    }
+
+    /// Get the output format this formatter renders
+    pub fn output_format(&self) -> OutputFormat {
+        self.output_format
+    }
+
+    /// Get the field configuration this formatter applies
+    pub fn field_config(&self) -> &OutputFieldConfig {
+        &self.field_config
+    }
+
+    /// Get the attached location registry, if any
+    pub fn registry(&self) -> Option<&LocationRegistry> {
+        self.registry.as_deref()
+    }
+
+    /// Get the file extension conventionally used for this format
+    pub fn file_extension(&self) -> &'static str {
+        match self.output_format {
+            OutputFormat::Json => "jsonl",
+            OutputFormat::Csv => "csv",
+            OutputFormat::Ocsf | OutputFormat::Ecs => "jsonl",
+            OutputFormat::Cef => "cef",
+            OutputFormat::Leef => "leef",
+        }
+    }
+
+    /// Get the header line for formats that have one (without a trailing newline)
+    pub fn header(&self) -> Option<String> {
+        match self.output_format {
+            OutputFormat::Csv => Some(CsvEventFormatter::new(self.field_config.clone()).header()),
+            _ => None,
+        }
+    }
+
+    /// Filter an access event for the flat JSON and CSV formats, adding facility names and
+    /// badge numbers when enabled
+    pub fn filtered_event(&self, event: &AccessEvent) -> FilteredAccessEvent {
+        let badge_number = self.identities.as_ref().and_then(|identities| identities.badge_number(event.user_id));
+        let filtered_event = FilteredAccessEvent::from_access_event(event, &self.field_config)
+            .with_badge_number(badge_number, &self.field_config);
+        if self.field_config.include_facility_names {
+            let context = EventFacilityContext::resolve(self.registry(), event);
+            filtered_event.with_facility_names(&context, &self.field_config)
+        } else {
+            filtered_event
+        }
+    }
+
+    /// Format an access event as a single line (without a trailing newline)
+    pub fn format_event(&self, event: &AccessEvent) -> Result<String, String> {
+        match self.output_format {
+            OutputFormat::Json => {
+                let filtered_event = self.filtered_event(event);
+                serde_json::to_string(&filtered_event)
+                    .map_err(|e| format!("Failed to serialize event to JSON: {}", e))
+            }
+            OutputFormat::Csv => Ok(CsvEventFormatter::new(self.field_config.clone())
+                .format_filtered_event(&self.filtered_event(event))),
+            OutputFormat::Ocsf => {
+                let context = EventFacilityContext::resolve(self.registry(), event);
+                OcsfEventFormatter::new(self.field_config.clone()).format_event(event, &context)
+            }
+            OutputFormat::Ecs => {
+                let context = EventFacilityContext::resolve(self.registry(), event);
+                EcsEventFormatter::new(self.field_config.clone()).format_event(event, &context)
+            }
+            OutputFormat::Cef => {
+                let context = EventFacilityContext::resolve(self.registry(), event);
+                Ok(CefEventFormatter::new().format_event(event, &context))
+            }
+            OutputFormat::Leef => {
+                let context = EventFacilityContext::resolve(self.registry(), event);
+                Ok(LeefEventFormatter::new().format_event(event, &context))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{BuildingId, EventType, LocationId, RoomId, UserId};
+    use chrono::{TimeZone, Utc};
+
+    fn create_test_event() -> AccessEvent {
+        AccessEvent::new(
+            Utc.with_ymd_and_hms(2024, 1, 15, 10, 30, 0).unwrap(),
+            UserId::new(),
+            RoomId::new(),
+            BuildingId::new(),
+            LocationId::new(),
+            true,
+            EventType::Success,
+        )
+    }
+
+    #[test]
+    fn test_json_format_has_no_header() {
+        let formatter = EventFormatter::new(OutputFormat::Json, OutputFieldConfig::default());
+        assert!(formatter.header().is_none());
+
+        let line = formatter.format_event(&create_test_event()).unwrap();
+        let value: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(value["timestamp"], "2024-01-15T10:30:00Z");
+        assert_eq!(value["success"], true);
+    }
+
+    #[test]
+    fn test_from_config() {
+        let config = SimulationConfig { output_format: "csv".to_string(), ..Default::default() };
+        let formatter = EventFormatter::from_config(&config).unwrap();
+        assert_eq!(formatter.output_format(), OutputFormat::Csv);
+        assert_eq!(formatter.file_extension(), "csv");
+
+        let invalid = SimulationConfig { output_format: "xml".to_string(), ..Default::default() };
+        assert!(EventFormatter::from_config(&invalid).is_err());
+    }
+
+    #[test]
+    fn test_csv_format_matches_csv_formatter() {
+        let field_config = OutputFieldConfig { include_event_type: true, ..Default::default() };
+        let formatter = EventFormatter::new(OutputFormat::Csv, field_config.clone());
+        let csv_formatter = CsvEventFormatter::new(field_config);
+        let event = create_test_event();
+
+        assert_eq!(formatter.header(), Some(csv_formatter.header()));
+        assert_eq!(formatter.format_event(&event).unwrap(), csv_formatter.format_event(&event));
+    }
+
+    #[test]
+    fn test_ocsf_format_uses_attached_registry() {
+        use crate::facility::FacilityGenerator;
+
+        let config = SimulationConfig { location_count: 1, ..Default::default() };
+        let registry = FacilityGenerator::with_seed(3).generate_facilities(&config).unwrap();
+        let location = &registry.locations[0];
+        let room = &location.buildings[0].rooms[0];
+
+        let mut event = create_test_event();
+        event.room_id = room.id;
+        event.building_id = location.buildings[0].id;
+        event.location_id = location.id;
+
+        let plain = EventFormatter::new(OutputFormat::Ocsf, OutputFieldConfig::default());
+        assert!(plain.header().is_none());
+        assert_eq!(plain.file_extension(), "jsonl");
+        let value: serde_json::Value = serde_json::from_str(&plain.format_event(&event).unwrap()).unwrap();
+        assert_eq!(value["class_uid"], 3002);
+        assert!(value["device"].get("name").is_none());
+
+        let enriched = plain.with_registry(Arc::new(registry.clone()));
+        let value: serde_json::Value =
+            serde_json::from_str(&enriched.format_event(&event).unwrap()).unwrap();
+        assert_eq!(value["device"]["name"], room.name.as_str());
+    }
+
+    #[test]
+    fn test_facility_names_in_json_and_csv() {
+        use crate::facility::FacilityGenerator;
+
+        let config = SimulationConfig { location_count: 1, ..Default::default() };
+        let registry = Arc::new(FacilityGenerator::with_seed(4).generate_facilities(&config).unwrap());
+        let location = &registry.locations[0];
+        let room = &location.buildings[0].rooms[0];
+        let mut event = create_test_event();
+        event.room_id = room.id;
+        event.building_id = location.buildings[0].id;
+        event.location_id = location.id;
+
+        let field_config = OutputFieldConfig { include_facility_names: true, ..Default::default() };
+        let json = EventFormatter::new(OutputFormat::Json, field_config.clone()).with_registry(registry.clone());
+        let value: serde_json::Value = serde_json::from_str(&json.format_event(&event).unwrap()).unwrap();
+        assert_eq!(value["room_name"], room.name.as_str());
+        assert_eq!(value["location_name"], location.name.as_str());
+
+        let plain = EventFormatter::new(OutputFormat::Json, OutputFieldConfig::default()).with_registry(registry.clone());
+        let value: serde_json::Value = serde_json::from_str(&plain.format_event(&event).unwrap()).unwrap();
+        assert!(value.get("room_name").is_none());
+
+        let csv = EventFormatter::new(OutputFormat::Csv, field_config).with_registry(registry.clone());
+        assert!(csv.header().unwrap().ends_with("room_name,room_type,security_level,building_name,location_name"));
+        let row = csv.format_event(&event).unwrap();
+        let cells: Vec<&str> = row.split(',').collect();
+        assert_eq!(cells[cells.len() - 5], room.name);
+        assert_eq!(cells[cells.len() - 1], location.name);
+    }
+
+    #[test]
+    fn test_key_by_badge_number() {
+        use crate::permissions::PermissionSet;
+        use crate::user::{assign_identities, User};
+
+        let event = create_test_event();
+        let mut user = User::new(event.location_id, event.building_id, RoomId::new(), PermissionSet::new());
+        user.id = event.user_id;
+        let mut users = vec![user];
+        assign_identities(&mut users);
+        let badge = users[0].identity.as_ref().unwrap().badge_number.to_string();
+        let identities = Arc::new(IdentityDirectory::from_users(&users));
+
+        let field_config = OutputFieldConfig { key_by_badge_number: true, ..Default::default() };
+        let json = EventFormatter::new(OutputFormat::Json, field_config.clone()).with_identities(identities.clone());
+        let value: serde_json::Value = serde_json::from_str(&json.format_event(&event).unwrap()).unwrap();
+        assert_eq!(value["badge_number"], badge.as_str());
+        assert!(value.get("user_id").is_none());
+
+        let csv = EventFormatter::new(OutputFormat::Csv, field_config).with_identities(identities);
+        assert!(csv.header().unwrap().starts_with("timestamp,badge_number,room_id"));
+        assert_eq!(csv.format_event(&event).unwrap().split(',').nth(1), Some(badge.as_str()));
+    }
+}
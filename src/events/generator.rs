@@ -12,12 +12,18 @@ use crate::user::{User, ScheduledActivity};
 use crate::events::metadata::ImpossibleTravelerMetadata;
 use crate::events::{AccessAttempt, AccessEvent, EventMetadata};
 use crate::facility::{LocationRegistry, Room};
-use crate::simulation::{ErrorHandler, SimulationError, SimulationResult, SimulationStatistics, TimeManager, TimeVariance};
+use crate::simulation::{
+    ErrorHandler, RngStream, SimulationError, SimulationResult, SimulationStatistics, TimeManager,
+    TimeVariance,
+};
 use crate::types::{
     UserId, EventType, FailureReason, LocationId, RoomId, RoomType, SecurityLevel,
     SimulationConfig,
 };
 
+/// Longest delay between a badge reader failure and the successful retry
+pub const MAX_READER_RETRY_DELAY_SECONDS: i64 = 30;
+
 /// Event generation system that creates access events from user activities
 #[derive(Debug)]
 pub struct EventGenerator {
@@ -29,7 +35,7 @@ pub struct EventGenerator {
     /// Time management system
     time_manager: TimeManager,
     /// Random number generator for event generation
-    rng: rand::rngs::ThreadRng,
+    rng: rand::rngs::StdRng,
     /// Error handler for graceful error recovery
     #[allow(dead_code)]
     error_handler: ErrorHandler,
@@ -47,12 +53,12 @@ impl EventGenerator {
     ) -> Self {
         info!("Initializing event generator with {} locations", location_registry.location_count());
         Self {
+            rng: RngStream::Events.rng(config.seed),
+            error_handler: ErrorHandler::new(),
+            time_variance: Self::create_time_variance(config.seed),
             config,
             location_registry,
             time_manager,
-            rng: rand::thread_rng(),
-            error_handler: ErrorHandler::new(),
-            time_variance: TimeVariance::new(),
         }
     }
 
@@ -69,12 +75,20 @@ impl EventGenerator {
     ) -> Self {
         info!("Initializing event generator with {} locations (statistics tracking is now centralized)", location_registry.location_count());
         Self {
+            rng: RngStream::Events.rng(config.seed),
+            error_handler: ErrorHandler::new(),
+            time_variance: Self::create_time_variance(config.seed),
             config,
             location_registry,
             time_manager,
-            rng: rand::thread_rng(),
-            error_handler: ErrorHandler::new(),
-            time_variance: TimeVariance::new(),
+        }
+    }
+
+    /// Create the time variance system, seeded from its own stream when a seed is configured
+    fn create_time_variance(seed: Option<u64>) -> TimeVariance {
+        match seed {
+            Some(seed) => TimeVariance::with_seed(RngStream::TimeVariance.derive_seed(seed)),
+            None => TimeVariance::new(),
         }
     }
 
@@ -155,6 +169,14 @@ impl EventGenerator {
         // Ensure unique timestamps after variance application
         self.time_variance.ensure_unique_timestamps(&mut events);
 
+        // Record the UTC offset of each event's location at the final timestamp
+        for event in &mut events {
+            event.local_offset = self
+                .location_registry
+                .get_location(event.location_id)
+                .map(|location| location.utc_offset(event.timestamp));
+        }
+
         // NOTE: Statistics tracking is now handled externally by the caller
         // This removes duplicate statistics tracking from the EventGenerator
 
@@ -203,6 +225,7 @@ impl EventGenerator {
             event_type: if is_authorized { EventType::Success } else { EventType::Failure },
             failure_reason: if is_authorized { None } else { Some(FailureReason::SystemFailure) },
             metadata: None,
+            local_offset: None,
         };
 
         debug!("Created minimal event: success={}, room={}", is_authorized, activity.target_room);
@@ -261,7 +284,7 @@ impl EventGenerator {
                 debug!(
                     "Generated impossible traveler scenario for user {} between locations {} and {}",
                     user.id, 
-                    impossible_events.get(0).map(|e| e.location_id).unwrap_or_default(),
+                    impossible_events.first().map(|e| e.location_id).unwrap_or_default(),
                     impossible_events.get(1).map(|e| e.location_id).unwrap_or_default()
                 );
                 events.extend(impossible_events);
@@ -358,7 +381,8 @@ impl EventGenerator {
             self.determine_event_type_with_room_info(attempt, is_high_security, success);
 
         // Check if this is a night-shift event during off-hours
-        let is_night_shift_event = user.is_night_shift && !self.time_manager.is_business_hours(attempt.timestamp);
+        let is_night_shift_event =
+            user.is_night_shift && !self.is_local_business_hours(attempt.timestamp, attempt.target_room);
         
         // Create metadata if this is a night-shift event
         let metadata = if is_night_shift_event {
@@ -404,7 +428,7 @@ impl EventGenerator {
 
         // Check business hours for certain room types
         if self.requires_business_hours_access_for_type(room_type)
-            && !self.time_manager.is_business_hours(attempt.timestamp)
+            && !self.is_local_business_hours(attempt.timestamp, attempt.target_room)
         {
             return false;
         }
@@ -441,7 +465,7 @@ impl EventGenerator {
             EventType::Success
         } else if !attempt.is_authorized {
             EventType::Failure
-        } else if !self.time_manager.is_business_hours(attempt.timestamp) {
+        } else if !self.is_local_business_hours(attempt.timestamp, attempt.target_room) {
             EventType::OutsideHours
         } else if is_high_security && self.rng.gen::<f64>() < 0.01 {
             EventType::Suspicious
@@ -450,6 +474,14 @@ impl EventGenerator {
         }
     }
 
+    /// Check whether a timestamp falls in business hours at the room's location
+    fn is_local_business_hours(&self, timestamp: DateTime<Utc>, room_id: RoomId) -> bool {
+        match self.location_registry.get_location_for_room(room_id) {
+            Some(location) => self.time_manager.is_location_business_hours(timestamp, location),
+            None => self.time_manager.is_business_hours(timestamp, chrono_tz::Tz::UTC),
+        }
+    }
+
     /// Check if a room type requires business hours access
     fn requires_business_hours_access_for_type(&self, room_type: RoomType) -> bool {
         matches!(room_type, RoomType::ServerRoom | RoomType::ExecutiveOffice | RoomType::Laboratory)
@@ -699,7 +731,7 @@ impl EventGenerator {
         events.push(failure_event);
 
         // Generate the retry event (5-30 seconds later, successful)
-        let retry_delay_seconds = self.rng.gen_range(5..=30);
+        let retry_delay_seconds = self.rng.gen_range(5..=MAX_READER_RETRY_DELAY_SECONDS);
         let retry_time = current_time + Duration::seconds(retry_delay_seconds);
 
         let retry_event = AccessEvent::new_with_failure_info(
@@ -922,6 +954,7 @@ impl EventGenerator {
             event_type: EventType::Success,
             failure_reason: None,
             metadata: None,
+            local_offset: None,
         };
 
         // Check if user is authorized for the remote room
@@ -951,6 +984,7 @@ impl EventGenerator {
             event_type: if is_authorized { EventType::Success } else { EventType::Failure },
             failure_reason: if is_authorized { None } else { Some(FailureReason::ImpossibleTraveler) },
             metadata,
+            local_offset: None,
         };
 
         // Validate that this is indeed an impossible scenario
@@ -1351,7 +1385,7 @@ mod tests {
         // Verify that the event is properly classified
         assert!(badge_reader_failure_event.is_badge_reader_failure());
         assert_eq!(badge_reader_failure_event.failure_reason, Some(FailureReason::BadgeReaderError));
-        assert_eq!(badge_reader_failure_event.success, false);
+        assert!(!badge_reader_failure_event.success);
         assert_eq!(badge_reader_failure_event.event_type, EventType::Failure);
         
         // NOTE: Statistics tracking is now handled centrally by BatchEventGenerator
@@ -1537,11 +1571,9 @@ mod tests {
         let permissions = PermissionSet::new();
         let night_shift_user = User::new_night_shift(location_id, building_id, room_id, permissions, building_id);
 
-        // Create an access attempt during business hours (e.g., 2 PM)
-        let business_hours_timestamp = chrono::Utc::now()
-            .with_hour(14).unwrap()  // 2 PM
-            .with_minute(0).unwrap()
-            .with_second(0).unwrap();
+        // Create an access attempt during business hours (2 PM on a Wednesday, since
+        // weekends have no business hours)
+        let business_hours_timestamp = chrono::TimeZone::with_ymd_and_hms(&Utc, 2024, 1, 10, 14, 0, 0).unwrap();
         let attempt = AccessAttempt::new(user_id, room_id, true, business_hours_timestamp);
 
         // Process the access attempt
@@ -1732,13 +1764,13 @@ mod tests {
         );
 
         // Verify events were created correctly
-        assert_eq!(success_event.success, true);
+        assert!(success_event.success);
         assert_eq!(success_event.event_type, EventType::Success);
         
-        assert_eq!(failure_event.success, false);
+        assert!(!failure_event.success);
         assert_eq!(failure_event.event_type, EventType::Failure);
         
-        assert_eq!(suspicious_event.success, false);
+        assert!(!suspicious_event.success);
         assert_eq!(suspicious_event.event_type, EventType::Suspicious);
         
         // NOTE: Statistics tracking is now handled centrally by BatchEventGenerator
@@ -1766,7 +1798,7 @@ mod tests {
             Duration::hours(1),
         );
 
-        let events = vec![
+        let events = [
             AccessEvent::new(
                 timestamp,
                 user_id,
@@ -1789,9 +1821,9 @@ mod tests {
 
         // Verify events were created correctly
         assert_eq!(events.len(), 2);
-        assert_eq!(events[0].success, false);
+        assert!(!events[0].success);
         assert_eq!(events[0].event_type, EventType::Failure);
-        assert_eq!(events[1].success, false);
+        assert!(!events[1].success);
         assert_eq!(events[1].event_type, EventType::Failure);
         
         // NOTE: Statistics tracking is now handled centrally by BatchEventGenerator
@@ -1820,7 +1852,7 @@ mod tests {
         let building_id = BuildingId::new();
         let location_id = LocationId::new();
 
-        let events = vec![
+        let events = [
             AccessEvent::new(
                 timestamp,
                 user_id,
@@ -1852,11 +1884,11 @@ mod tests {
 
         // Verify all events were created correctly
         assert_eq!(events.len(), 3);
-        assert_eq!(events[0].success, true);
+        assert!(events[0].success);
         assert_eq!(events[0].event_type, EventType::Success);
-        assert_eq!(events[1].success, false);
+        assert!(!events[1].success);
         assert_eq!(events[1].event_type, EventType::OutsideHours);
-        assert_eq!(events[2].success, false);
+        assert!(!events[2].success);
         assert_eq!(events[2].event_type, EventType::InvalidBadge);
         
         // NOTE: Statistics tracking is now handled centrally by BatchEventGenerator
@@ -1884,7 +1916,7 @@ mod tests {
         let building_id = BuildingId::new();
         let location_id = LocationId::new();
 
-        let events = vec![AccessEvent::new(
+        let events = [AccessEvent::new(
             timestamp,
             user_id,
             room_id,
@@ -1896,7 +1928,7 @@ mod tests {
 
         // Verify event was created correctly
         assert_eq!(events.len(), 1);
-        assert_eq!(events[0].success, true);
+        assert!(events[0].success);
         assert_eq!(events[0].event_type, EventType::Success);
 
         // Statistics are now handled centrally in BatchEventGenerator
@@ -1966,7 +1998,7 @@ mod tests {
         let base_time = Utc::now();
 
         // Create impossible traveler events manually to test counting
-        let impossible_events = vec![
+        let impossible_events = [
             AccessEvent::new(
                 base_time,
                 user_id,
@@ -11,6 +11,12 @@
 //! - **EventGenerator**: Creates realistic access events with proper timing
 //! - **ImpossibleTravelerMetadata**: Specialized metadata for anomaly detection
 //! - **AccessAttempt**: Models individual access attempts with context
+//! - **CsvEventFormatter**: Renders events as CSV rows with flattened metadata columns
+//! - **EventFormatter**: Renders events as lines in the configured output format
+//! - **EventFacilityContext**: Resolves the room, building and location an event refers to
+//! - **OcsfEventFormatter**: Maps events onto the OCSF Authentication event class
+//! - **EcsEventFormatter**: Renders events as Elastic Common Schema documents
+//! - **CefEventFormatter** / **LeefEventFormatter**: Render events as CEF and LEEF lines for legacy SIEMs
 //!
 //! # Usage Example
 //!
@@ -40,10 +46,22 @@
 //! ```
 
 pub mod access_event;
+pub mod csv_format;
+pub mod ecs_format;
+pub mod facility_context;
+pub mod formatter;
 pub mod generator;
 pub mod metadata;
+pub mod ocsf_format;
+pub mod siem_format;
 
 // Re-export all public types for convenience
 pub use access_event::*;
+pub use csv_format::*;
+pub use ecs_format::*;
+pub use facility_context::*;
+pub use formatter::*;
 pub use generator::*;
 pub use metadata::*;
+pub use ocsf_format::*;
+pub use siem_format::*;
@@ -0,0 +1,290 @@
+//! OCSF event formatting
+//!
+//! This module maps access events onto the Open Cybersecurity Schema Framework (OCSF)
+//! Authentication class (`class_uid` 3002) so simulated data can be loaded into
+//! OCSF-normalized data lakes. Each badge swipe becomes a Logon activity where the
+//! badge holder is the `user`, the badge reader is the `device`, and the reader's
+//! site is the device `location`. The site's UTC offset, when known, is written to
+//! `timezone_offset` in minutes.
+//!
+//! `EventType` and `FailureReason` map to `status_id`, `status`, `status_code` and
+//! `status_detail`; identifiers without a dedicated OCSF attribute (building and
+//! location) are kept under `unmapped`. The output field toggles do not apply to this
+//! schema, except that simulation metadata is only added to `unmapped` when metadata
+//! output is enabled.
+
+use chrono::SecondsFormat;
+use serde_json::{json, Map, Value};
+
+use crate::events::{AccessEvent, EventFacilityContext};
+use crate::types::config::OutputFieldConfig;
+use crate::types::EventType;
+
+/// OCSF schema version the output conforms to
+pub const OCSF_SCHEMA_VERSION: &str = "1.1.0";
+
+/// OCSF Identity & Access Management category
+const CATEGORY_UID: u32 = 3;
+const CATEGORY_NAME: &str = "Identity & Access Management";
+
+/// OCSF Authentication class
+const CLASS_UID: u32 = 3002;
+const CLASS_NAME: &str = "Authentication";
+
+/// OCSF Authentication Logon activity
+const ACTIVITY_ID: u32 = 1;
+const ACTIVITY_NAME: &str = "Logon";
+
+/// OCSF "Other" enum value, used for the badge reader device type and badge protocol
+const OTHER_ID: u32 = 99;
+
+/// Product name reported in event metadata
+const PRODUCT_NAME: &str = "Badge Access Simulator";
+
+/// Vendor name reported in event metadata
+const VENDOR_NAME: &str = "Badge Access Simulator";
+
+/// Formatter that renders access events as OCSF Authentication events
+#[derive(Debug, Clone)]
+pub struct OcsfEventFormatter {
+    field_config: OutputFieldConfig,
+}
+
+impl OcsfEventFormatter {
+    /// Create a new OCSF formatter for the given field configuration
+    pub fn new(field_config: OutputFieldConfig) -> Self {
+        Self { field_config }
+    }
+
+    /// Map an access event to an OCSF Authentication event
+    pub fn to_value(&self, event: &AccessEvent, context: &EventFacilityContext<'_>) -> Value {
+        let (severity_id, severity) = severity(event.event_type);
+        let (status_id, status) = if event.success { (1, "Success") } else { (2, "Failure") };
+
+        let mut location = Map::new();
+        location.insert(
+            "desc".to_string(),
+            json!(context.location_name().map(str::to_string).unwrap_or_else(|| event.location_id.to_string())),
+        );
+        if let Some((latitude, longitude)) = context.coordinates() {
+            // OCSF coordinates are [longitude, latitude], matching GeoJSON
+            location.insert("coordinates".to_string(), json!([longitude, latitude]));
+        }
+        location.insert("is_on_premises".to_string(), json!(true));
+
+        let mut device = Map::new();
+        device.insert("uid".to_string(), json!(event.room_id.to_string()));
+        if let Some(room_name) = context.room_name() {
+            device.insert("name".to_string(), json!(room_name));
+        }
+        device.insert("type_id".to_string(), json!(OTHER_ID));
+        device.insert("type".to_string(), json!("Badge Reader"));
+        device.insert("location".to_string(), Value::Object(location));
+
+        let mut unmapped = Map::new();
+        unmapped.insert("building_id".to_string(), json!(event.building_id.to_string()));
+        if let Some(building_name) = context.building_name() {
+            unmapped.insert("building_name".to_string(), json!(building_name));
+        }
+        unmapped.insert("location_id".to_string(), json!(event.location_id.to_string()));
+        if let Some(location_name) = context.location_name() {
+            unmapped.insert("location_name".to_string(), json!(location_name));
+        }
+        if self.field_config.include_metadata || self.field_config.include_all {
+            if let Some(metadata) = &event.metadata {
+                unmapped.insert("metadata".to_string(), json!(metadata));
+            }
+        }
+
+        let mut value = json!({
+            "activity_id": ACTIVITY_ID,
+            "activity_name": ACTIVITY_NAME,
+            "category_uid": CATEGORY_UID,
+            "category_name": CATEGORY_NAME,
+            "class_uid": CLASS_UID,
+            "class_name": CLASS_NAME,
+            "type_uid": CLASS_UID * 100 + ACTIVITY_ID,
+            "type_name": format!("{}: {}", CLASS_NAME, ACTIVITY_NAME),
+            "time": event.timestamp.timestamp_millis(),
+            "time_dt": event.timestamp.to_rfc3339_opts(SecondsFormat::Millis, true),
+            "severity_id": severity_id,
+            "severity": severity,
+            "status_id": status_id,
+            "status": status,
+            "status_code": status_code(event.event_type),
+            "status_detail": status_detail(event),
+            "auth_protocol_id": OTHER_ID,
+            "auth_protocol": "Badge",
+            "is_remote": false,
+            "user": {
+                "uid": event.user_id.to_string(),
+                "type_id": 1,
+                "type": "User",
+            },
+            "device": device,
+            "metadata": {
+                "version": OCSF_SCHEMA_VERSION,
+                "product": {
+                    "name": PRODUCT_NAME,
+                    "vendor_name": VENDOR_NAME,
+                    "version": env!("CARGO_PKG_VERSION"),
+                },
+            },
+            "unmapped": unmapped,
+        });
+        if let Some(offset) = event.local_offset {
+            value["timezone_offset"] = json!(offset.local_minus_utc() / 60);
+        }
+        value
+    }
+
+    /// Format an access event as a single OCSF JSON line (without a trailing newline)
+    pub fn format_event(
+        &self,
+        event: &AccessEvent,
+        context: &EventFacilityContext<'_>,
+    ) -> Result<String, String> {
+        serde_json::to_string(&self.to_value(event, context))
+            .map_err(|e| format!("Failed to serialize event to OCSF JSON: {}", e))
+    }
+}
+
+/// Map an event type to an OCSF severity (id, caption)
+fn severity(event_type: EventType) -> (u32, &'static str) {
+    match event_type {
+        EventType::Success => (1, "Informational"),
+        EventType::Failure => (2, "Low"),
+        EventType::InvalidBadge | EventType::OutsideHours => (3, "Medium"),
+        EventType::Suspicious => (4, "High"),
+    }
+}
+
+/// Map an event type to the status code reported by the badge system
+fn status_code(event_type: EventType) -> &'static str {
+    match event_type {
+        EventType::Success => "Success",
+        EventType::Failure => "Failure",
+        EventType::InvalidBadge => "InvalidBadge",
+        EventType::OutsideHours => "OutsideHours",
+        EventType::Suspicious => "Suspicious",
+    }
+}
+
+/// Describe why an event has its status, preferring the recorded failure reason
+fn status_detail(event: &AccessEvent) -> String {
+    match event.failure_reason {
+        Some(reason) => reason.to_string(),
+        None if event.success => "Access granted".to_string(),
+        None => event.event_type.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::facility::FacilityGenerator;
+    use crate::types::{BuildingId, FailureReason, LocationId, RoomId, SimulationConfig, UserId};
+    use chrono::{TimeZone, Utc};
+
+    fn create_test_event(success: bool, event_type: EventType) -> AccessEvent {
+        AccessEvent::new(
+            Utc.with_ymd_and_hms(2024, 1, 15, 10, 30, 0).unwrap(),
+            UserId::new(),
+            RoomId::new(),
+            BuildingId::new(),
+            LocationId::new(),
+            success,
+            event_type,
+        )
+    }
+
+    #[test]
+    fn test_success_maps_to_authentication_logon() {
+        let event = create_test_event(true, EventType::Success);
+        let value = OcsfEventFormatter::new(OutputFieldConfig::default())
+            .to_value(&event, &EventFacilityContext::default());
+
+        assert_eq!(value["class_uid"], 3002);
+        assert_eq!(value["category_uid"], 3);
+        assert_eq!(value["activity_id"], 1);
+        assert_eq!(value["type_uid"], 300201);
+        assert_eq!(value["time"], event.timestamp.timestamp_millis());
+        assert_eq!(value["time_dt"], "2024-01-15T10:30:00.000Z");
+        assert_eq!(value["status_id"], 1);
+        assert_eq!(value["status"], "Success");
+        assert_eq!(value["severity_id"], 1);
+        assert_eq!(value["user"]["uid"], event.user_id.to_string());
+        assert_eq!(value["device"]["uid"], event.room_id.to_string());
+        assert_eq!(value["device"]["location"]["desc"], event.location_id.to_string());
+        assert_eq!(value["unmapped"]["building_id"], event.building_id.to_string());
+        assert_eq!(value["metadata"]["version"], OCSF_SCHEMA_VERSION);
+        assert!(value.get("timezone_offset").is_none());
+    }
+
+    #[test]
+    fn test_local_offset_maps_to_timezone_offset() {
+        let mut event = create_test_event(true, EventType::Success);
+        event.local_offset = chrono::FixedOffset::east_opt(-7 * 3600);
+        let value = OcsfEventFormatter::new(OutputFieldConfig::default())
+            .to_value(&event, &EventFacilityContext::default());
+
+        assert_eq!(value["timezone_offset"], -420);
+    }
+
+    #[test]
+    fn test_failure_maps_to_status_detail() {
+        let mut event = create_test_event(false, EventType::Suspicious);
+        event.failure_reason = Some(FailureReason::ImpossibleTraveler);
+        let value = OcsfEventFormatter::new(OutputFieldConfig::default())
+            .to_value(&event, &EventFacilityContext::default());
+
+        assert_eq!(value["status_id"], 2);
+        assert_eq!(value["status"], "Failure");
+        assert_eq!(value["status_code"], "Suspicious");
+        assert_eq!(value["status_detail"], "Impossible Traveler");
+        assert_eq!(value["severity_id"], 4);
+
+        let invalid_badge = create_test_event(false, EventType::InvalidBadge);
+        let value = OcsfEventFormatter::new(OutputFieldConfig::default())
+            .to_value(&invalid_badge, &EventFacilityContext::default());
+        assert_eq!(value["status_detail"], "Invalid Badge");
+        assert_eq!(value["severity_id"], 3);
+    }
+
+    #[test]
+    fn test_facility_context_fills_names_and_coordinates() {
+        let config = SimulationConfig { location_count: 1, ..Default::default() };
+        let registry = FacilityGenerator::with_seed(11).generate_facilities(&config).unwrap();
+        let location = &registry.locations[0];
+        let building = &location.buildings[0];
+        let room = &building.rooms[0];
+
+        let mut event = create_test_event(true, EventType::Success);
+        event.room_id = room.id;
+        event.building_id = building.id;
+        event.location_id = location.id;
+
+        let context = EventFacilityContext::resolve(Some(&registry), &event);
+        let value = OcsfEventFormatter::new(OutputFieldConfig::default()).to_value(&event, &context);
+
+        assert_eq!(value["device"]["name"], room.name.as_str());
+        assert_eq!(value["device"]["location"]["desc"], location.name.as_str());
+        assert_eq!(value["device"]["location"]["coordinates"][0], location.coordinates.1);
+        assert_eq!(value["device"]["location"]["coordinates"][1], location.coordinates.0);
+        assert_eq!(value["unmapped"]["building_name"], building.name.as_str());
+    }
+
+    #[test]
+    fn test_metadata_follows_field_config() {
+        let mut event = create_test_event(true, EventType::Success);
+        event.metadata = Some(crate::events::EventMetadata::new());
+
+        let without = OcsfEventFormatter::new(OutputFieldConfig::default())
+            .to_value(&event, &EventFacilityContext::default());
+        assert!(without["unmapped"].get("metadata").is_none());
+
+        let with = OcsfEventFormatter::new(OutputFieldConfig { include_metadata: true, ..Default::default() })
+            .to_value(&event, &EventFacilityContext::default());
+        assert!(with["unmapped"]["metadata"].is_object());
+    }
+}
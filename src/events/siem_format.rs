@@ -0,0 +1,300 @@
+//! CEF and LEEF event formatting
+//!
+//! This module renders access events as ArcSight Common Event Format (CEF) and QRadar
+//! Log Event Extended Format (LEEF) lines, the shapes physical access control systems
+//! usually export over syslog to legacy SIEM connectors.
+//!
+//! Both formats map `EventType` to a 0-10 severity and carry the room, building and
+//! location names in custom string fields (`cs1`-`cs3` for CEF, `roomName`,
+//! `buildingName` and `locationName` for LEEF) next to their identifiers. The output
+//! field toggles do not apply to these formats.
+
+use chrono::SecondsFormat;
+
+use crate::events::{AccessEvent, EventFacilityContext};
+use crate::types::EventType;
+
+/// Vendor reported in CEF and LEEF headers
+const DEVICE_VENDOR: &str = "Badge Access Simulator";
+
+/// Product reported in CEF and LEEF headers
+const DEVICE_PRODUCT: &str = "Badge Access Simulator";
+
+/// LEEF time format matching the `devTime` values written
+const LEEF_TIME_FORMAT: &str = "yyyy-MM-dd'T'HH:mm:ss.SSSX";
+
+/// Formatter that renders access events as ArcSight CEF lines
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CefEventFormatter;
+
+impl CefEventFormatter {
+    /// Create a new CEF formatter
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Format an access event as a single CEF line (without a trailing newline)
+    pub fn format_event(&self, event: &AccessEvent, context: &EventFacilityContext<'_>) -> String {
+        let mut extension = vec![
+            ("rt", event.timestamp.timestamp_millis().to_string()),
+            ("suid", event.user_id.to_string()),
+            ("cat", signature_id(event.event_type).to_string()),
+            ("act", if event.success { "allowed" } else { "denied" }.to_string()),
+            ("outcome", if event.success { "success" } else { "failure" }.to_string()),
+        ];
+        if let Some(reason) = event.failure_reason {
+            extension.push(("reason", reason.to_string()));
+        }
+        extension.push(("deviceExternalId", event.room_id.to_string()));
+        if let Some((latitude, longitude)) = context.coordinates() {
+            extension.push(("dlat", latitude.to_string()));
+            extension.push(("dlong", longitude.to_string()));
+        }
+        for (label_key, key, label, name) in [
+            ("cs1Label", "cs1", "Room", context.room_name()),
+            ("cs2Label", "cs2", "Building", context.building_name()),
+            ("cs3Label", "cs3", "Location", context.location_name()),
+        ] {
+            if let Some(name) = name {
+                extension.push((label_key, label.to_string()));
+                extension.push((key, name.to_string()));
+            }
+        }
+        extension.extend([
+            ("cs4Label", "Room ID".to_string()),
+            ("cs4", event.room_id.to_string()),
+            ("cs5Label", "Building ID".to_string()),
+            ("cs5", event.building_id.to_string()),
+            ("cs6Label", "Location ID".to_string()),
+            ("cs6", event.location_id.to_string()),
+        ]);
+
+        let extension = extension
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, escape_cef_extension(value)))
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        format!(
+            "CEF:0|{}|{}|{}|{}|{}|{}|{}",
+            escape_header(DEVICE_VENDOR),
+            escape_header(DEVICE_PRODUCT),
+            escape_header(env!("CARGO_PKG_VERSION")),
+            escape_header(signature_id(event.event_type)),
+            escape_header(&event_name(event.event_type)),
+            severity(event.event_type),
+            extension
+        )
+    }
+}
+
+/// Formatter that renders access events as QRadar LEEF 1.0 lines
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LeefEventFormatter;
+
+impl LeefEventFormatter {
+    /// Create a new LEEF formatter
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Format an access event as a single tab-delimited LEEF line (without a trailing newline)
+    pub fn format_event(&self, event: &AccessEvent, context: &EventFacilityContext<'_>) -> String {
+        let mut attributes = vec![
+            ("devTime", event.timestamp.to_rfc3339_opts(SecondsFormat::Millis, true)),
+            ("devTimeFormat", LEEF_TIME_FORMAT.to_string()),
+            ("cat", event.event_type.to_string()),
+            ("sev", severity(event.event_type).to_string()),
+            ("usrName", event.user_id.to_string()),
+            ("result", if event.success { "success" } else { "failure" }.to_string()),
+        ];
+        if let Some(reason) = event.failure_reason {
+            attributes.push(("reason", reason.to_string()));
+        }
+        attributes.push(("roomId", event.room_id.to_string()));
+        if let Some(room_name) = context.room_name() {
+            attributes.push(("roomName", room_name.to_string()));
+        }
+        attributes.push(("buildingId", event.building_id.to_string()));
+        if let Some(building_name) = context.building_name() {
+            attributes.push(("buildingName", building_name.to_string()));
+        }
+        attributes.push(("locationId", event.location_id.to_string()));
+        if let Some(location_name) = context.location_name() {
+            attributes.push(("locationName", location_name.to_string()));
+        }
+        if let Some((latitude, longitude)) = context.coordinates() {
+            attributes.push(("latitude", latitude.to_string()));
+            attributes.push(("longitude", longitude.to_string()));
+        }
+
+        let attributes = attributes
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, sanitize_leef_value(value)))
+            .collect::<Vec<_>>()
+            .join("\t");
+
+        format!(
+            "LEEF:1.0|{}|{}|{}|{}|{}",
+            escape_header(DEVICE_VENDOR),
+            escape_header(DEVICE_PRODUCT),
+            escape_header(env!("CARGO_PKG_VERSION")),
+            escape_header(signature_id(event.event_type)),
+            attributes
+        )
+    }
+}
+
+/// Map an event type to a 0-10 SIEM severity
+fn severity(event_type: EventType) -> u8 {
+    match event_type {
+        EventType::Success => 1,
+        EventType::Failure => 4,
+        EventType::OutsideHours => 5,
+        EventType::InvalidBadge => 6,
+        EventType::Suspicious => 9,
+    }
+}
+
+/// Map an event type to the signature/event ID
+fn signature_id(event_type: EventType) -> &'static str {
+    match event_type {
+        EventType::Success => "badge-access-success",
+        EventType::Failure => "badge-access-failure",
+        EventType::InvalidBadge => "badge-access-invalid-badge",
+        EventType::OutsideHours => "badge-access-outside-hours",
+        EventType::Suspicious => "badge-access-suspicious",
+    }
+}
+
+/// Human-readable event name for the CEF header
+fn event_name(event_type: EventType) -> String {
+    format!("Badge Access {}", event_type)
+}
+
+/// Escape a CEF or LEEF header field (pipes and backslashes)
+fn escape_header(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('|', "\\|")
+}
+
+/// Escape a CEF extension value (backslashes, equals signs and line breaks)
+fn escape_cef_extension(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('=', "\\=")
+        .replace('\r', "\\r")
+        .replace('\n', "\\n")
+}
+
+/// Keep a LEEF attribute value on one line and free of the tab delimiter
+fn sanitize_leef_value(value: &str) -> String {
+    value.replace(['\t', '\r', '\n'], " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::facility::FacilityGenerator;
+    use crate::types::{BuildingId, FailureReason, LocationId, RoomId, SimulationConfig, UserId};
+    use chrono::{TimeZone, Utc};
+
+    fn create_test_event(success: bool, event_type: EventType) -> AccessEvent {
+        AccessEvent::new(
+            Utc.with_ymd_and_hms(2024, 1, 15, 10, 30, 0).unwrap(),
+            UserId::new(),
+            RoomId::new(),
+            BuildingId::new(),
+            LocationId::new(),
+            success,
+            event_type,
+        )
+    }
+
+    /// Split a CEF line into its seven header fields and the extension
+    fn split_cef(line: &str) -> Vec<&str> {
+        line.splitn(8, '|').collect()
+    }
+
+    #[test]
+    fn test_cef_header_and_severity() {
+        let mut event = create_test_event(false, EventType::Suspicious);
+        event.failure_reason = Some(FailureReason::ImpossibleTraveler);
+        let line = CefEventFormatter::new().format_event(&event, &EventFacilityContext::default());
+        let parts = split_cef(&line);
+
+        assert_eq!(parts[0], "CEF:0");
+        assert_eq!(parts[4], "badge-access-suspicious");
+        assert_eq!(parts[5], "Badge Access Suspicious");
+        assert_eq!(parts[6], "9");
+        assert!(parts[7].contains(&format!("rt={}", event.timestamp.timestamp_millis())));
+        assert!(parts[7].contains(&format!("suid={}", event.user_id)));
+        assert!(parts[7].contains("outcome=failure"));
+        assert!(parts[7].contains("reason=Impossible Traveler"));
+        assert!(parts[7].contains(&format!("cs4={}", event.room_id)));
+    }
+
+    #[test]
+    fn test_cef_names_in_custom_strings() {
+        let config = SimulationConfig { location_count: 1, ..Default::default() };
+        let registry = FacilityGenerator::with_seed(9).generate_facilities(&config).unwrap();
+        let location = &registry.locations[0];
+        let building = &location.buildings[0];
+        let room = &building.rooms[0];
+
+        let mut event = create_test_event(true, EventType::Success);
+        event.room_id = room.id;
+        event.building_id = building.id;
+        event.location_id = location.id;
+
+        let context = EventFacilityContext::resolve(Some(&registry), &event);
+        let line = CefEventFormatter::new().format_event(&event, &context);
+        let extension = split_cef(&line)[7];
+
+        assert_eq!(split_cef(&line)[6], "1");
+        assert!(extension.contains(&format!("cs1Label=Room cs1={} ", room.name)));
+        assert!(extension.contains(&format!("cs2Label=Building cs2={} ", building.name)));
+        assert!(extension.contains(&format!("cs3Label=Location cs3={} ", location.name)));
+        assert!(extension.contains(&format!("dlat={}", location.coordinates.0)));
+    }
+
+    #[test]
+    fn test_cef_escaping() {
+        assert_eq!(escape_header("a|b\\c"), "a\\|b\\\\c");
+        assert_eq!(escape_cef_extension("a=b\\c\nd"), "a\\=b\\\\c\\nd");
+    }
+
+    #[test]
+    fn test_leef_line() {
+        let config = SimulationConfig { location_count: 1, ..Default::default() };
+        let registry = FacilityGenerator::with_seed(9).generate_facilities(&config).unwrap();
+        let location = &registry.locations[0];
+        let building = &location.buildings[0];
+
+        let mut event = create_test_event(false, EventType::InvalidBadge);
+        event.room_id = building.rooms[0].id;
+        event.building_id = building.id;
+        event.location_id = location.id;
+
+        let context = EventFacilityContext::resolve(Some(&registry), &event);
+        let line = LeefEventFormatter::new().format_event(&event, &context);
+        let (header, attributes) = line.split_at(line.match_indices('|').nth(4).unwrap().0 + 1);
+
+        assert_eq!(
+            header,
+            format!("LEEF:1.0|{}|{}|{}|badge-access-invalid-badge|", DEVICE_VENDOR, DEVICE_PRODUCT, env!("CARGO_PKG_VERSION"))
+        );
+        let attributes: Vec<&str> = attributes.split('\t').collect();
+        assert!(attributes.contains(&"devTime=2024-01-15T10:30:00.000Z"));
+        assert!(attributes.contains(&"sev=6"));
+        assert!(attributes.contains(&"cat=Invalid Badge"));
+        assert!(attributes.contains(&format!("usrName={}", event.user_id).as_str()));
+        assert!(attributes.contains(&format!("roomName={}", building.rooms[0].name).as_str()));
+        assert!(attributes.contains(&format!("buildingName={}", building.name).as_str()));
+        assert!(attributes.contains(&format!("locationName={}", location.name).as_str()));
+    }
+
+    #[test]
+    fn test_leef_values_stay_on_one_line() {
+        assert_eq!(sanitize_leef_value("a\tb\nc"), "a b c");
+    }
+}
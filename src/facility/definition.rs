@@ -0,0 +1,331 @@
+//! Declarative facility definitions
+//!
+//! This module loads a real facility layout from a JSON or YAML file instead of generating
+//! random locations. The file names each site with its coordinates, the buildings at the
+//! site, and the rooms in each building with their type, security level and the checkpoint
+//! rooms that must be badged through first:
+//!
+//! ```yaml
+//! locations:
+//!   - name: Seattle HQ
+//!     latitude: 47.6062
+//!     longitude: -122.3321
+//!     time_zone: America/Los_Angeles
+//!     holidays: [2024-07-04, 2024-12-25]
+//!     half_days: [2024-12-24]
+//!     buildings:
+//!       - name: Tower A
+//!         rooms:
+//!           - { name: Main Lobby, room_type: Lobby, security_level: Public }
+//!           - { name: Floor 3 Desks, room_type: Workspace, security_level: Standard }
+//!           - { name: Security Desk, room_type: Storage, security_level: Restricted }
+//!           - name: Data Center
+//!             room_type: ServerRoom
+//!             security_level: HighSecurity
+//!             requires: [Security Desk]
+//! ```
+//!
+//! Room types and security levels accept the same spellings as on the command line
+//! (`ServerRoom`, `server room`, `high`). Time zones are IANA names; sites without one get a
+//! fixed offset approximated from their longitude. `holidays` and `half_days` list the site's
+//! own non-work days and early-closing days in addition to the configured calendar. Ids are
+//! derived from the location, building and room names, so the same file always produces the
+//! same ids.
+
+use chrono::NaiveDate;
+use chrono_tz::Tz;
+use serde::{Deserialize, Deserializer};
+use std::collections::{HashMap, HashSet};
+use std::io::Read;
+use std::path::Path;
+use std::str::FromStr;
+use uuid::Uuid;
+
+use crate::facility::gazetteer::approximate_time_zone;
+use crate::facility::{Building, Location, LocationRegistry, Room};
+use crate::simulation::open_input_file;
+use crate::types::{BuildingId, Compression, LocationId, RoomId, RoomType, SecurityLevel};
+
+/// Namespace for the name-based ids of defined facilities
+const ID_NAMESPACE: Uuid = Uuid::from_u128(0x6f1c_2a8e_4b3d_5e7f_9a0b_1c2d_3e4f_5a6b);
+
+/// Facility layout loaded from a definition file
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FacilityDefinition {
+    /// Sites in the layout
+    pub locations: Vec<LocationDefinition>,
+}
+
+/// Site with its coordinates and buildings
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct LocationDefinition {
+    /// Site name, unique within the file
+    pub name: String,
+    /// Latitude in degrees
+    pub latitude: f64,
+    /// Longitude in degrees
+    pub longitude: f64,
+    /// IANA time zone name, approximated from the longitude when absent
+    #[serde(default)]
+    pub time_zone: Option<Tz>,
+    /// Public holidays observed at the site (YYYY-MM-DD)
+    #[serde(default)]
+    pub holidays: Vec<NaiveDate>,
+    /// Days when the site closes at midday (YYYY-MM-DD)
+    #[serde(default)]
+    pub half_days: Vec<NaiveDate>,
+    /// Buildings at the site
+    pub buildings: Vec<BuildingDefinition>,
+}
+
+/// Building with its rooms
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BuildingDefinition {
+    /// Building name, unique within the site
+    pub name: String,
+    /// Rooms in the building, including at least one lobby
+    pub rooms: Vec<RoomDefinition>,
+}
+
+/// Room with its type, security level and checkpoint requirements
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RoomDefinition {
+    /// Room name, unique within the building
+    pub name: String,
+    /// Type of room
+    #[serde(deserialize_with = "deserialize_from_str")]
+    pub room_type: RoomType,
+    /// Security level required to access the room
+    #[serde(deserialize_with = "deserialize_from_str")]
+    pub security_level: SecurityLevel,
+    /// Names of rooms in the same building that must be accessed first
+    #[serde(default)]
+    pub requires: Vec<String>,
+}
+
+impl FacilityDefinition {
+    /// Load a definition file, reading YAML for `.yaml` and `.yml` files and JSON otherwise
+    ///
+    /// Compressed files (`.gz`, `.zst`) are decompressed transparently.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let path = path.as_ref();
+        let mut content = String::new();
+        open_input_file(path)
+            .and_then(|mut reader| reader.read_to_string(&mut content))
+            .map_err(|e| format!("Failed to read facility definition {}: {}", path.display(), e))?;
+
+        let uncompressed = match Compression::from_path(path) {
+            Compression::None => path.to_path_buf(),
+            _ => path.with_extension(""),
+        };
+        let is_yaml = matches!(
+            uncompressed.extension().and_then(|ext| ext.to_str()).map(str::to_ascii_lowercase).as_deref(),
+            Some("yaml" | "yml")
+        );
+
+        let definition = if is_yaml { Self::from_yaml_str(&content) } else { Self::from_json_str(&content) };
+        definition.map_err(|e| format!("Invalid facility definition {}: {}", path.display(), e))
+    }
+
+    /// Parse a JSON definition
+    pub fn from_json_str(content: &str) -> Result<Self, String> {
+        serde_json::from_str(content).map_err(|e| e.to_string())
+    }
+
+    /// Parse a YAML definition
+    pub fn from_yaml_str(content: &str) -> Result<Self, String> {
+        serde_yaml::from_str(content).map_err(|e| e.to_string())
+    }
+
+    /// Build and validate the location registry described by the definition
+    pub fn into_registry(&self) -> Result<LocationRegistry, String> {
+        let mut registry = LocationRegistry::new();
+        let mut location_names = HashSet::new();
+
+        for location_definition in &self.locations {
+            if !location_names.insert(location_definition.name.as_str()) {
+                return Err(format!("Duplicate location name '{}'", location_definition.name));
+            }
+            registry.add_location(location_definition.build()?);
+        }
+
+        registry.validate()?;
+        Ok(registry)
+    }
+}
+
+impl LocationDefinition {
+    /// Build the location with its buildings and rooms
+    fn build(&self) -> Result<Location, String> {
+        if !(-90.0..=90.0).contains(&self.latitude) || !(-180.0..=180.0).contains(&self.longitude) {
+            return Err(format!(
+                "Location '{}' has invalid coordinates ({}, {})",
+                self.name, self.latitude, self.longitude
+            ));
+        }
+
+        let time_zone = self.time_zone.unwrap_or_else(|| approximate_time_zone(self.longitude));
+        let mut location = Location::new(self.name.clone(), (self.latitude, self.longitude))
+            .with_time_zone(time_zone)
+            .with_holidays(self.holidays.clone(), self.half_days.clone());
+        location.id = LocationId(name_based_id(&[&self.name]));
+
+        let mut building_names = HashSet::new();
+        for building_definition in &self.buildings {
+            if !building_names.insert(building_definition.name.as_str()) {
+                return Err(format!("Duplicate building name '{}' in location '{}'", building_definition.name, self.name));
+            }
+            location.add_building(building_definition.build(&self.name, location.id)?);
+        }
+
+        location.validate().map_err(|e| format!("Location '{}' is invalid: {}", self.name, e))?;
+        Ok(location)
+    }
+}
+
+impl BuildingDefinition {
+    /// Build the building with its rooms, resolving checkpoint requirements by name
+    fn build(&self, location_name: &str, location_id: LocationId) -> Result<Building, String> {
+        let mut building = Building::new(location_id, self.name.clone());
+        building.id = BuildingId(name_based_id(&[location_name, &self.name]));
+
+        let mut room_ids = HashMap::new();
+        for room_definition in &self.rooms {
+            let room_id = RoomId(name_based_id(&[location_name, &self.name, &room_definition.name]));
+            if room_ids.insert(room_definition.name.as_str(), room_id).is_some() {
+                return Err(format!("Duplicate room name '{}' in building '{}'", room_definition.name, self.name));
+            }
+        }
+
+        for room_definition in &self.rooms {
+            let mut room = Room::new(
+                building.id,
+                room_definition.name.clone(),
+                room_definition.room_type,
+                room_definition.security_level,
+            );
+            room.id = room_ids[room_definition.name.as_str()];
+            for required in &room_definition.requires {
+                let required_id = room_ids.get(required.as_str()).ok_or_else(|| {
+                    format!(
+                        "Room '{}' in building '{}' requires unknown room '{}'",
+                        room_definition.name, self.name, required
+                    )
+                })?;
+                if *required_id == room.id {
+                    return Err(format!("Room '{}' in building '{}' requires itself", room_definition.name, self.name));
+                }
+                room.add_intermediate_access(*required_id);
+            }
+            building.add_room(room);
+        }
+
+        building.validate().map_err(|e| format!("Building '{}' is invalid: {}", self.name, e))?;
+        Ok(building)
+    }
+}
+
+/// Derive a stable id from the names on the path to a facility record
+fn name_based_id(names: &[&str]) -> Uuid {
+    Uuid::new_v5(&ID_NAMESPACE, names.join("/").as_bytes())
+}
+
+/// Deserialize an enum through its `FromStr` implementation
+fn deserialize_from_str<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr<Err = String>,
+{
+    let s = String::deserialize(deserializer)?;
+    s.parse().map_err(serde::de::Error::custom)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const YAML: &str = "
+locations:
+  - name: Seattle HQ
+    latitude: 47.6062
+    longitude: -122.3321
+    time_zone: America/Los_Angeles
+    holidays: [2024-07-04, 2024-12-25]
+    half_days: [2024-12-24]
+    buildings:
+      - name: Tower A
+        rooms:
+          - { name: Main Lobby, room_type: Lobby, security_level: Public }
+          - { name: Floor 3 Desks, room_type: Workspace, security_level: Standard }
+          - { name: Security Desk, room_type: Storage, security_level: Restricted }
+          - name: Data Center
+            room_type: server room
+            security_level: high
+            requires: [Security Desk]
+";
+
+    #[test]
+    fn test_yaml_definition_builds_registry() {
+        let registry = FacilityDefinition::from_yaml_str(YAML).unwrap().into_registry().unwrap();
+        assert_eq!(registry.location_count(), 1);
+        assert_eq!(registry.total_room_count(), 4);
+
+        let location = &registry.locations[0];
+        assert_eq!(location.coordinates, (47.6062, -122.3321));
+        assert_eq!(location.time_zone, chrono_tz::America::Los_Angeles);
+        assert_eq!(location.holidays.len(), 2);
+        assert_eq!(location.half_days, vec![NaiveDate::from_ymd_opt(2024, 12, 24).unwrap()]);
+        let building = &location.buildings[0];
+        assert!(building.has_lobby());
+        let data_center = building.rooms.iter().find(|room| room.name == "Data Center").unwrap();
+        let security_desk = building.rooms.iter().find(|room| room.name == "Security Desk").unwrap();
+        assert_eq!(data_center.room_type, RoomType::ServerRoom);
+        assert_eq!(data_center.security_level, SecurityLevel::HighSecurity);
+        assert_eq!(data_center.required_intermediate_access, vec![security_desk.id]);
+
+        // Ids are derived from names, so loading again gives the same ids
+        let again = FacilityDefinition::from_yaml_str(YAML).unwrap().into_registry().unwrap();
+        assert_eq!(again.locations[0].buildings[0].rooms[3].id, data_center.id);
+    }
+
+    #[test]
+    fn test_json_definition_from_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("facilities.json");
+        let json = r#"{"locations": [{"name": "Austin", "latitude": 30.27, "longitude": -97.74, "buildings": [
+            {"name": "Main", "rooms": [
+                {"name": "Lobby", "room_type": "Lobby", "security_level": "Public"},
+                {"name": "Desks", "room_type": "Workspace", "security_level": "Standard"}]}]}]}"#;
+        std::fs::write(&path, json).unwrap();
+
+        let registry = FacilityDefinition::from_path(&path).unwrap().into_registry().unwrap();
+        assert_eq!(registry.locations[0].name, "Austin");
+        assert_eq!(registry.locations[0].time_zone, Tz::Etc__GMTPlus7);
+        assert_eq!(registry.total_room_count(), 2);
+    }
+
+    #[test]
+    fn test_invalid_definitions_are_rejected() {
+        let no_lobby = YAML.replace("room_type: Lobby", "room_type: Workspace");
+        let error = FacilityDefinition::from_yaml_str(&no_lobby).unwrap().into_registry().unwrap_err();
+        assert!(error.contains("lobby"), "{}", error);
+
+        let unknown_checkpoint = YAML.replace("requires: [Security Desk]", "requires: [Mantrap]");
+        let error = FacilityDefinition::from_yaml_str(&unknown_checkpoint).unwrap().into_registry().unwrap_err();
+        assert!(error.contains("unknown room 'Mantrap'"), "{}", error);
+
+        let duplicate_room = YAML.replace("Floor 3 Desks", "Security Desk");
+        assert!(FacilityDefinition::from_yaml_str(&duplicate_room).unwrap().into_registry().is_err());
+
+        let bad_coordinates = YAML.replace("47.6062", "147.6062");
+        assert!(FacilityDefinition::from_yaml_str(&bad_coordinates).unwrap().into_registry().is_err());
+
+        assert!(FacilityDefinition::from_yaml_str(&YAML.replace("server room", "vault")).is_err());
+        assert!(FacilityDefinition::from_yaml_str(&YAML.replace("America/Los_Angeles", "Mars/Olympus")).is_err());
+        assert!(FacilityDefinition::from_yaml_str("locations: []").unwrap().into_registry().is_err());
+    }
+}
@@ -0,0 +1,131 @@
+//! Embedded gazetteer of office cities
+//!
+//! Generated locations are named after the cities in this table and placed at their real
+//! coordinates and time zones, so distances between offices (and the travel times and
+//! impossible traveler checks derived from them) and local working hours match what
+//! production data would show.
+
+use chrono_tz::{Africa, America, Asia, Australia, Europe, Tz};
+
+/// A city with its real-world coordinates and time zone
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct City {
+    /// City name as used in generated location names
+    pub name: &'static str,
+    /// ISO 3166-1 alpha-2 country code
+    pub country_code: &'static str,
+    /// Latitude of the city center in degrees
+    pub latitude: f64,
+    /// Longitude of the city center in degrees
+    pub longitude: f64,
+    /// IANA time zone of the city
+    pub time_zone: Tz,
+}
+
+impl City {
+    /// Coordinates as a `(latitude, longitude)` pair
+    pub fn coordinates(&self) -> (f64, f64) {
+        (self.latitude, self.longitude)
+    }
+}
+
+/// Cities used for generated locations, in the order they are assigned
+pub const CITIES: [City; 40] = [
+    City { name: "Seattle", country_code: "US", latitude: 47.6062, longitude: -122.3321, time_zone: America::Los_Angeles },
+    City { name: "Portland", country_code: "US", latitude: 45.5152, longitude: -122.6784, time_zone: America::Los_Angeles },
+    City { name: "San Francisco", country_code: "US", latitude: 37.7749, longitude: -122.4194, time_zone: America::Los_Angeles },
+    City { name: "Los Angeles", country_code: "US", latitude: 34.0522, longitude: -118.2437, time_zone: America::Los_Angeles },
+    City { name: "Denver", country_code: "US", latitude: 39.7392, longitude: -104.9903, time_zone: America::Denver },
+    City { name: "Chicago", country_code: "US", latitude: 41.8781, longitude: -87.6298, time_zone: America::Chicago },
+    City { name: "Austin", country_code: "US", latitude: 30.2672, longitude: -97.7431, time_zone: America::Chicago },
+    City { name: "Dallas", country_code: "US", latitude: 32.7767, longitude: -96.7970, time_zone: America::Chicago },
+    City { name: "Atlanta", country_code: "US", latitude: 33.7490, longitude: -84.3880, time_zone: America::New_York },
+    City { name: "Miami", country_code: "US", latitude: 25.7617, longitude: -80.1918, time_zone: America::New_York },
+    City { name: "Boston", country_code: "US", latitude: 42.3601, longitude: -71.0589, time_zone: America::New_York },
+    City { name: "New York", country_code: "US", latitude: 40.7128, longitude: -74.0060, time_zone: America::New_York },
+    City { name: "Philadelphia", country_code: "US", latitude: 39.9526, longitude: -75.1652, time_zone: America::New_York },
+    City { name: "Washington DC", country_code: "US", latitude: 38.9072, longitude: -77.0369, time_zone: America::New_York },
+    City { name: "Toronto", country_code: "CA", latitude: 43.6532, longitude: -79.3832, time_zone: America::Toronto },
+    City { name: "Vancouver", country_code: "CA", latitude: 49.2827, longitude: -123.1207, time_zone: America::Vancouver },
+    City { name: "London", country_code: "GB", latitude: 51.5074, longitude: -0.1278, time_zone: Europe::London },
+    City { name: "Paris", country_code: "FR", latitude: 48.8566, longitude: 2.3522, time_zone: Europe::Paris },
+    City { name: "Berlin", country_code: "DE", latitude: 52.5200, longitude: 13.4050, time_zone: Europe::Berlin },
+    City { name: "Amsterdam", country_code: "NL", latitude: 52.3676, longitude: 4.9041, time_zone: Europe::Amsterdam },
+    City { name: "Stockholm", country_code: "SE", latitude: 59.3293, longitude: 18.0686, time_zone: Europe::Stockholm },
+    City { name: "Copenhagen", country_code: "DK", latitude: 55.6761, longitude: 12.5683, time_zone: Europe::Copenhagen },
+    City { name: "Dublin", country_code: "IE", latitude: 53.3498, longitude: -6.2603, time_zone: Europe::Dublin },
+    City { name: "Madrid", country_code: "ES", latitude: 40.4168, longitude: -3.7038, time_zone: Europe::Madrid },
+    City { name: "Rome", country_code: "IT", latitude: 41.9028, longitude: 12.4964, time_zone: Europe::Rome },
+    City { name: "Zurich", country_code: "CH", latitude: 47.3769, longitude: 8.5417, time_zone: Europe::Zurich },
+    City { name: "Vienna", country_code: "AT", latitude: 48.2082, longitude: 16.3738, time_zone: Europe::Vienna },
+    City { name: "Tokyo", country_code: "JP", latitude: 35.6762, longitude: 139.6503, time_zone: Asia::Tokyo },
+    City { name: "Seoul", country_code: "KR", latitude: 37.5665, longitude: 126.9780, time_zone: Asia::Seoul },
+    City { name: "Singapore", country_code: "SG", latitude: 1.3521, longitude: 103.8198, time_zone: Asia::Singapore },
+    City { name: "Sydney", country_code: "AU", latitude: -33.8688, longitude: 151.2093, time_zone: Australia::Sydney },
+    City { name: "Melbourne", country_code: "AU", latitude: -37.8136, longitude: 144.9631, time_zone: Australia::Melbourne },
+    City { name: "Mumbai", country_code: "IN", latitude: 19.0760, longitude: 72.8777, time_zone: Asia::Kolkata },
+    City { name: "Bangalore", country_code: "IN", latitude: 12.9716, longitude: 77.5946, time_zone: Asia::Kolkata },
+    City { name: "Tel Aviv", country_code: "IL", latitude: 32.0853, longitude: 34.7818, time_zone: Asia::Jerusalem },
+    City { name: "Dubai", country_code: "AE", latitude: 25.2048, longitude: 55.2708, time_zone: Asia::Dubai },
+    City { name: "São Paulo", country_code: "BR", latitude: -23.5505, longitude: -46.6333, time_zone: America::Sao_Paulo },
+    City { name: "Mexico City", country_code: "MX", latitude: 19.4326, longitude: -99.1332, time_zone: America::Mexico_City },
+    City { name: "Buenos Aires", country_code: "AR", latitude: -34.6037, longitude: -58.3816, time_zone: America::Argentina::Buenos_Aires },
+    City { name: "Cape Town", country_code: "ZA", latitude: -33.9249, longitude: 18.4241, time_zone: Africa::Johannesburg },
+];
+
+/// Look up a city by name (case-insensitive)
+pub fn find_city(name: &str) -> Option<&'static City> {
+    CITIES.iter().find(|city| city.name.eq_ignore_ascii_case(name.trim()))
+}
+
+/// Look up the city a location name refers to, such as "Tokyo Office" or "Tokyo"
+pub fn city_for_location_name(location_name: &str) -> Option<&'static City> {
+    let name = location_name.trim();
+    find_city(name.strip_suffix(" Office").unwrap_or(name))
+}
+
+/// Approximate the time zone of a place without a gazetteer entry from its longitude
+///
+/// Returns a fixed-offset `Etc/GMT` zone with one hour per 15 degrees of longitude, which
+/// keeps local working hours roughly in daylight without modelling daylight saving time.
+pub fn approximate_time_zone(longitude: f64) -> Tz {
+    let offset_hours = (longitude / 15.0).round().clamp(-12.0, 12.0) as i32;
+    // `Etc/GMT` zone names use the POSIX sign convention, so UTC+9 is `Etc/GMT-9`
+    let name = match offset_hours {
+        0 => "Etc/GMT".to_string(),
+        hours => format!("Etc/GMT{:+}", -hours),
+    };
+    name.parse().unwrap_or(Tz::UTC)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_city_lookup() {
+        let tokyo = city_for_location_name("Tokyo Office").unwrap();
+        assert_eq!(tokyo.country_code, "JP");
+        assert_eq!(tokyo.coordinates(), (35.6762, 139.6503));
+        assert_eq!(find_city("new york").map(|city| city.name), Some("New York"));
+        assert!(city_for_location_name("Location 41").is_none());
+        assert_eq!(find_city("Mumbai").unwrap().time_zone, Asia::Kolkata);
+    }
+
+    #[test]
+    fn test_approximate_time_zone() {
+        assert_eq!(approximate_time_zone(0.0), Tz::Etc__GMT);
+        assert_eq!(approximate_time_zone(139.65), Tz::Etc__GMTMinus9);
+        assert_eq!(approximate_time_zone(-122.33), Tz::Etc__GMTPlus8);
+        assert_eq!(approximate_time_zone(179.9), Tz::Etc__GMTMinus12);
+    }
+
+    #[test]
+    fn test_cities_are_valid_and_unique() {
+        for (i, city) in CITIES.iter().enumerate() {
+            assert!((-90.0..=90.0).contains(&city.latitude), "{}", city.name);
+            assert!((-180.0..=180.0).contains(&city.longitude), "{}", city.name);
+            assert!(CITIES[i + 1..].iter().all(|other| other.name != city.name), "{}", city.name);
+        }
+    }
+}
@@ -5,9 +5,14 @@
 //! realistic geographical distribution.
 
 use crate::facility::{
-    building::Building, location::Location, registry::LocationRegistry, room::Room,
+    building::Building,
+    gazetteer::{approximate_time_zone, city_for_location_name, CITIES},
+    location::Location,
+    registry::LocationRegistry,
+    room::Room,
 };
-use crate::types::{LocationId, RoomType, SecurityLevel, SimulationConfig};
+use crate::simulation::RngStream;
+use crate::types::{BuildingId, LocationId, RoomId, RoomType, SecurityLevel, SimulationConfig};
 use rand::{prelude::*, rngs::StdRng, RngCore, SeedableRng};
 use serde::{Deserialize, Serialize};
 use std::fmt;
@@ -38,10 +43,24 @@ impl LocationGenerator {
     }
 
     /// Generate a single location with realistic coordinates
+    ///
+    /// Locations named after a gazetteer city (such as "Tokyo Office") are placed at that
+    /// city's real coordinates and time zone; other names get random coordinates in a
+    /// populated region and a time zone approximated from the longitude.
     pub fn generate_location(&mut self, name: String) -> Location {
-        let coordinates = self.generate_realistic_coordinates();
+        let (coordinates, time_zone) = match city_for_location_name(&name) {
+            Some(city) if !self.used_coordinates.contains(&city.coordinates()) => {
+                (city.coordinates(), city.time_zone)
+            }
+            _ => {
+                let coordinates = self.generate_realistic_coordinates();
+                (coordinates, approximate_time_zone(coordinates.1))
+            }
+        };
         self.used_coordinates.push(coordinates);
-        Location::new(name, coordinates)
+        let mut location = Location::new(name, coordinates).with_time_zone(time_zone);
+        location.id = LocationId::from_rng(&mut self.rng);
+        location
     }
 
     /// Generate multiple locations with minimum distance between them
@@ -126,53 +145,9 @@ impl LocationGenerator {
 
     /// Generate a realistic location name
     fn generate_location_name(&mut self, index: usize) -> String {
-        let city_names = [
-            "Seattle",
-            "Portland",
-            "San Francisco",
-            "Los Angeles",
-            "Denver",
-            "Chicago",
-            "Austin",
-            "Dallas",
-            "Atlanta",
-            "Miami",
-            "Boston",
-            "New York",
-            "Philadelphia",
-            "Washington DC",
-            "Toronto",
-            "Vancouver",
-            "London",
-            "Paris",
-            "Berlin",
-            "Amsterdam",
-            "Stockholm",
-            "Copenhagen",
-            "Dublin",
-            "Madrid",
-            "Rome",
-            "Zurich",
-            "Vienna",
-            "Tokyo",
-            "Seoul",
-            "Singapore",
-            "Sydney",
-            "Melbourne",
-            "Mumbai",
-            "Bangalore",
-            "Tel Aviv",
-            "Dubai",
-            "São Paulo",
-            "Mexico City",
-            "Buenos Aires",
-            "Cape Town",
-        ];
-
-        if index < city_names.len() {
-            format!("{} Office", city_names[index])
-        } else {
-            format!("Location {}", index + 1)
+        match CITIES.get(index) {
+            Some(city) => format!("{} Office", city.name),
+            None => format!("Location {}", index + 1),
         }
     }
 }
@@ -213,14 +188,16 @@ impl BuildingGenerator {
         room_count: usize,
     ) -> Building {
         let mut building = Building::new(location_id, name);
+        building.id = BuildingId::from_rng(&mut self.rng);
 
         // Always create a lobby first (required for building access)
-        let lobby = Room::new(
+        let mut lobby = Room::new(
             building.id,
             "Main Lobby".to_string(),
             RoomType::Lobby,
             SecurityLevel::Public,
         );
+        lobby.id = RoomId::from_rng(&mut self.rng);
         building.add_room(lobby);
 
         // Generate remaining rooms
@@ -254,12 +231,13 @@ impl BuildingGenerator {
     }
 
     /// Generate a room for a building
-    fn generate_room(&mut self, building_id: crate::types::BuildingId, room_number: usize) -> Room {
+    fn generate_room(&mut self, building_id: BuildingId, room_number: usize) -> Room {
         let room_type = self.select_room_type();
         let security_level = self.select_security_level(&room_type);
         let name = self.generate_room_name(&room_type, room_number);
 
-        let room = Room::new(building_id, name, room_type, security_level);
+        let mut room = Room::new(building_id, name, room_type, security_level);
+        room.id = RoomId::from_rng(&mut self.rng);
 
         // Add intermediate access requirements for high-security rooms
         if matches!(security_level, SecurityLevel::HighSecurity | SecurityLevel::MaxSecurity) {
@@ -422,12 +400,13 @@ impl RoomGenerator {
     /// Generate a room with specific type and security level
     pub fn generate_room(
         &mut self,
-        building_id: crate::types::BuildingId,
+        building_id: BuildingId,
         room_type: RoomType,
         security_level: SecurityLevel,
         name: String,
     ) -> Room {
         let mut room = Room::new(building_id, name, room_type, security_level);
+        room.id = RoomId::from_rng(&mut self.rng);
 
         // Add intermediate access requirements for high-security rooms
         if matches!(security_level, SecurityLevel::HighSecurity | SecurityLevel::MaxSecurity) {
@@ -440,7 +419,7 @@ impl RoomGenerator {
     /// Generate a room with automatic type and security level selection
     pub fn generate_random_room(
         &mut self,
-        building_id: crate::types::BuildingId,
+        building_id: BuildingId,
         room_number: usize,
     ) -> Room {
         let room_type = self.select_random_room_type();
@@ -453,32 +432,30 @@ impl RoomGenerator {
     /// Generate multiple rooms for a building
     pub fn generate_rooms(
         &mut self,
-        building_id: crate::types::BuildingId,
+        building_id: BuildingId,
         room_count: usize,
         include_lobby: bool,
     ) -> Vec<Room> {
         let mut rooms = Vec::with_capacity(room_count);
 
-        let mut room_counter = 1;
-
         // Add lobby if requested
         if include_lobby {
-            let lobby = Room::new(
+            let mut lobby = Room::new(
                 building_id,
                 "Main Lobby".to_string(),
                 RoomType::Lobby,
                 SecurityLevel::Public,
             );
+            lobby.id = RoomId::from_rng(&mut self.rng);
             rooms.push(lobby);
         }
 
         // Generate remaining rooms
         let remaining_count = if include_lobby { room_count.saturating_sub(1) } else { room_count };
 
-        for _ in 0..remaining_count {
-            let room = self.generate_random_room(building_id, room_counter);
+        for room_number in 1..=remaining_count {
+            let room = self.generate_random_room(building_id, room_number);
             rooms.push(room);
-            room_counter += 1;
         }
 
         rooms
@@ -612,11 +589,13 @@ impl FacilityGenerator {
     }
 
     /// Create a new facility generator with a specific seed
+    ///
+    /// Each sub-generator draws from its own stream derived from the seed.
     pub fn with_seed(seed: u64) -> Self {
         Self {
-            location_generator: LocationGenerator::with_seed(seed),
-            building_generator: BuildingGenerator::with_seed(seed + 1),
-            room_generator: RoomGenerator::with_seed(seed + 2),
+            location_generator: LocationGenerator::with_seed(RngStream::Locations.derive_seed(seed)),
+            building_generator: BuildingGenerator::with_seed(RngStream::Buildings.derive_seed(seed)),
+            room_generator: RoomGenerator::with_seed(RngStream::Rooms.derive_seed(seed)),
         }
     }
 
@@ -635,7 +614,9 @@ impl FacilityGenerator {
 
         for mut location in locations {
             // Generate buildings for this location
-            let building_count = thread_rng()
+            let building_count = self
+                .building_generator
+                .rng
                 .gen_range(config.min_buildings_per_location..=config.max_buildings_per_location);
 
             let buildings = self.building_generator.generate_buildings(
@@ -773,6 +754,23 @@ mod tests {
         assert_eq!(location1.coordinates, location2.coordinates);
     }
 
+    #[test]
+    fn test_city_locations_use_gazetteer_coordinates() {
+        let mut generator = LocationGenerator::with_seed(7);
+        let locations = generator.generate_locations(CITIES.len() + 1);
+
+        assert_eq!(locations[0].name, "Seattle Office");
+        assert_eq!(locations[0].coordinates, (47.6062, -122.3321));
+        assert_eq!(locations[27].name, "Tokyo Office");
+        assert_eq!(locations[27].coordinates, (35.6762, 139.6503));
+        assert_eq!(locations[27].time_zone, chrono_tz::Asia::Tokyo);
+
+        // Locations beyond the gazetteer fall back to random coordinates
+        let extra = &locations[CITIES.len()];
+        assert_eq!(extra.name, format!("Location {}", CITIES.len() + 1));
+        assert!(CITIES.iter().all(|city| city.coordinates() != extra.coordinates));
+    }
+
     #[test]
     fn test_multiple_locations_generation() {
         let mut generator = LocationGenerator::new();
@@ -828,7 +826,7 @@ mod tests {
     #[test]
     fn test_room_generator() {
         let mut generator = RoomGenerator::new();
-        let building_id = crate::types::BuildingId::new();
+        let building_id = BuildingId::new();
 
         let room = generator.generate_room(
             building_id,
@@ -846,7 +844,7 @@ mod tests {
     #[test]
     fn test_room_generator_random_rooms() {
         let mut generator = RoomGenerator::new();
-        let building_id = crate::types::BuildingId::new();
+        let building_id = BuildingId::new();
         let rooms = generator.generate_rooms(building_id, 5, true);
 
         assert_eq!(rooms.len(), 5);
@@ -8,7 +8,8 @@ use crate::facility::{building::Building, room::Room};
 use crate::permissions::access_flow::AccessFlow;
 use crate::simulation::time_manager::TimeManager;
 use crate::types::{BuildingId, LocationId, RoomId, RoomType, SecurityLevel};
-use chrono::Duration;
+use chrono::{DateTime, Duration, FixedOffset, NaiveDate, NaiveDateTime, Offset, TimeZone, Utc};
+use chrono_tz::Tz;
 use rand::Rng;
 use serde::{Deserialize, Serialize};
 
@@ -23,12 +24,71 @@ pub struct Location {
     pub buildings: Vec<Building>,
     /// Geographical coordinates (latitude, longitude) for impossible traveler calculations
     pub coordinates: (f64, f64),
+    /// IANA time zone that schedules and business hours at this location follow
+    #[serde(default = "default_time_zone")]
+    pub time_zone: Tz,
+    /// Public holidays observed at this location
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub holidays: Vec<NaiveDate>,
+    /// Days when this location closes at midday
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub half_days: Vec<NaiveDate>,
+}
+
+/// Time zone of locations that do not name one
+fn default_time_zone() -> Tz {
+    Tz::UTC
 }
 
 impl Location {
-    /// Create a new location
+    /// Create a new location in UTC
     pub fn new(name: String, coordinates: (f64, f64)) -> Self {
-        Self { id: LocationId::new(), name, buildings: Vec::new(), coordinates }
+        Self {
+            id: LocationId::new(),
+            name,
+            buildings: Vec::new(),
+            coordinates,
+            time_zone: default_time_zone(),
+            holidays: Vec::new(),
+            half_days: Vec::new(),
+        }
+    }
+
+    /// Set the time zone of the location
+    pub fn with_time_zone(mut self, time_zone: Tz) -> Self {
+        self.time_zone = time_zone;
+        self
+    }
+
+    /// Set the public holidays and half-days observed at the location
+    pub fn with_holidays(mut self, holidays: Vec<NaiveDate>, half_days: Vec<NaiveDate>) -> Self {
+        self.holidays = holidays;
+        self.half_days = half_days;
+        self
+    }
+
+    /// Convert a UTC timestamp to local time at this location
+    pub fn local_time(&self, timestamp: DateTime<Utc>) -> DateTime<Tz> {
+        timestamp.with_timezone(&self.time_zone)
+    }
+
+    /// Get the UTC offset in effect at this location at the given time
+    pub fn utc_offset(&self, timestamp: DateTime<Utc>) -> FixedOffset {
+        self.local_time(timestamp).offset().fix()
+    }
+
+    /// Convert a local wall-clock time at this location to UTC
+    ///
+    /// Times repeated when clocks go back resolve to the first occurrence, and times skipped
+    /// when clocks go forward are shifted by the offset in effect just before the change.
+    pub fn local_to_utc(&self, local: NaiveDateTime) -> DateTime<Utc> {
+        match self.time_zone.from_local_datetime(&local).earliest() {
+            Some(time) => time.with_timezone(&Utc),
+            None => {
+                let offset = self.time_zone.offset_from_utc_datetime(&local).fix();
+                (local - Duration::seconds(offset.local_minus_utc() as i64)).and_utc()
+            }
+        }
     }
 
     /// Add a building to the location
@@ -303,6 +363,28 @@ mod tests {
         assert_eq!(location.name, "Seattle Office");
         assert_eq!(location.coordinates, coordinates);
         assert!(location.buildings.is_empty());
+        assert_eq!(location.time_zone, Tz::UTC);
+    }
+
+    #[test]
+    fn test_local_time_conversion() {
+        use chrono::NaiveDate;
+
+        let tokyo = Location::new("Tokyo Office".to_string(), (35.6762, 139.6503))
+            .with_time_zone(chrono_tz::Asia::Tokyo);
+        let nine_am = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap().and_hms_opt(9, 0, 0).unwrap();
+        let utc = tokyo.local_to_utc(nine_am);
+        assert_eq!(utc, Utc.with_ymd_and_hms(2024, 1, 15, 0, 0, 0).unwrap());
+        assert_eq!(tokyo.local_time(utc).naive_local(), nine_am);
+        assert_eq!(tokyo.utc_offset(utc).to_string(), "+09:00");
+
+        // Daylight saving time shifts the offset, and skipped times still convert
+        let seattle = Location::new("Seattle Office".to_string(), (47.6062, -122.3321))
+            .with_time_zone(chrono_tz::America::Los_Angeles);
+        let summer = NaiveDate::from_ymd_opt(2024, 7, 1).unwrap().and_hms_opt(9, 0, 0).unwrap();
+        assert_eq!(seattle.local_to_utc(summer), Utc.with_ymd_and_hms(2024, 7, 1, 16, 0, 0).unwrap());
+        let skipped = NaiveDate::from_ymd_opt(2024, 3, 10).unwrap().and_hms_opt(2, 30, 0).unwrap();
+        assert_eq!(seattle.local_to_utc(skipped), Utc.with_ymd_and_hms(2024, 3, 10, 10, 30, 0).unwrap());
     }
 
     #[test]
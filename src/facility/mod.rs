@@ -12,6 +12,8 @@
 //! - **Room**: Individual rooms with security levels and access requirements
 //! - **LocationRegistry**: Efficient lookup and search functionality
 //! - **Generators**: Create realistic facility layouts with proper relationships
+//! - **FacilityTopology**: JSON and CSV export of the layout, usable as a room-type answer key
+//! - **FacilityDefinition**: Real facility layouts loaded from JSON or YAML files
 //!
 //! # Usage Example
 //!
@@ -43,16 +45,22 @@
 //! ```
 
 pub mod building;
+pub mod definition;
+pub mod gazetteer;
 pub mod generator;
 pub mod location;
 pub mod registry;
 pub mod room;
+pub mod topology;
 
 // Re-export all public types for convenience
 pub use building::Building;
+pub use definition::{BuildingDefinition, FacilityDefinition, LocationDefinition, RoomDefinition};
+pub use gazetteer::{City, CITIES};
 pub use generator::{
     BuildingGenerator, FacilityGenerator, FacilityStats, LocationGenerator, RoomGenerator,
 };
 pub use location::Location;
 pub use registry::{AccessComplexityStats, LocationRegistry};
 pub use room::Room;
+pub use topology::{FacilityExportFormat, FacilityTopology, FACILITY_CSV_COLUMNS};
@@ -0,0 +1,222 @@
+//! Facility topology export
+//!
+//! This module serializes the generated facility layout so the opaque ids in event output
+//! can be resolved to locations, buildings and rooms. The JSON form keeps the nested
+//! location → building → room hierarchy and can be loaded back into a `LocationRegistry`;
+//! the CSV form flattens it to one row per room for spreadsheets and joins.
+//!
+//! The export includes each room's `RoomType` and `SecurityLevel`, which makes it the
+//! answer key for the room-type inference challenge.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::path::Path;
+
+use crate::events::format_csv_row;
+use crate::facility::{Location, LocationRegistry};
+use crate::types::Compression;
+
+/// Column names of the flat CSV export, in order
+pub const FACILITY_CSV_COLUMNS: [&str; 13] = [
+    "location_id",
+    "location_name",
+    "latitude",
+    "longitude",
+    "time_zone",
+    "building_id",
+    "building_name",
+    "room_id",
+    "room_name",
+    "room_type",
+    "security_level",
+    "is_lobby",
+    "required_intermediate_access",
+];
+
+/// Serialization format of a facility export
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FacilityExportFormat {
+    /// Nested JSON document
+    #[default]
+    Json,
+    /// One CSV row per room
+    Csv,
+}
+
+impl FacilityExportFormat {
+    /// Detect the format from a file name, ignoring any compression extension
+    ///
+    /// Files ending in `.csv` (optionally followed by `.gz` or `.zst`) are CSV; everything
+    /// else is JSON.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Self {
+        let path = path.as_ref();
+        let path = match Compression::from_path(path) {
+            Compression::None => path.to_path_buf(),
+            _ => path.with_extension(""),
+        };
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some(ext) if ext.eq_ignore_ascii_case("csv") => FacilityExportFormat::Csv,
+            _ => FacilityExportFormat::Json,
+        }
+    }
+}
+
+impl fmt::Display for FacilityExportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FacilityExportFormat::Json => write!(f, "json"),
+            FacilityExportFormat::Csv => write!(f, "csv"),
+        }
+    }
+}
+
+/// Serializable snapshot of every location, building and room in a registry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FacilityTopology {
+    /// Number of locations
+    pub location_count: usize,
+    /// Number of buildings across all locations
+    pub building_count: usize,
+    /// Number of rooms across all buildings
+    pub room_count: usize,
+    /// Locations with their buildings and rooms
+    pub locations: Vec<Location>,
+}
+
+impl FacilityTopology {
+    /// Take a snapshot of a location registry
+    pub fn from_registry(registry: &LocationRegistry) -> Self {
+        Self {
+            location_count: registry.location_count(),
+            building_count: registry.total_building_count(),
+            room_count: registry.total_room_count(),
+            locations: registry.locations.clone(),
+        }
+    }
+
+    /// Rebuild a location registry from the snapshot
+    pub fn into_registry(self) -> LocationRegistry {
+        let mut registry = LocationRegistry::new();
+        for location in self.locations {
+            registry.add_location(location);
+        }
+        registry
+    }
+
+    /// Read a JSON export
+    pub fn from_json_reader<R: Read>(reader: R) -> serde_json::Result<Self> {
+        serde_json::from_reader(reader)
+    }
+
+    /// Write the export in the given format
+    pub fn write<W: Write>(&self, writer: &mut W, format: FacilityExportFormat) -> io::Result<()> {
+        match format {
+            FacilityExportFormat::Json => {
+                serde_json::to_writer_pretty(&mut *writer, self)?;
+                writeln!(writer)
+            }
+            FacilityExportFormat::Csv => {
+                writeln!(writer, "{}", format_csv_row(&FACILITY_CSV_COLUMNS))?;
+                for row in self.csv_rows() {
+                    writeln!(writer, "{}", format_csv_row(&row))?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Flatten the hierarchy into one CSV row per room
+    ///
+    /// Required intermediate rooms are joined with `;` into a single cell.
+    pub fn csv_rows(&self) -> Vec<Vec<String>> {
+        let mut rows = Vec::with_capacity(self.room_count);
+        for location in &self.locations {
+            let (latitude, longitude) = location.coordinates;
+            for building in &location.buildings {
+                for room in &building.rooms {
+                    let required: Vec<String> =
+                        room.required_intermediate_access.iter().map(|id| id.to_string()).collect();
+                    rows.push(vec![
+                        location.id.to_string(),
+                        location.name.clone(),
+                        latitude.to_string(),
+                        longitude.to_string(),
+                        location.time_zone.name().to_string(),
+                        building.id.to_string(),
+                        building.name.clone(),
+                        room.id.to_string(),
+                        room.name.clone(),
+                        format!("{:?}", room.room_type),
+                        format!("{:?}", room.security_level),
+                        (building.lobby_room_id == Some(room.id)).to_string(),
+                        required.join(";"),
+                    ]);
+                }
+            }
+        }
+        rows
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::facility::FacilityGenerator;
+    use crate::types::SimulationConfig;
+
+    fn create_test_registry() -> LocationRegistry {
+        let config = SimulationConfig { location_count: 2, seed: Some(11), ..Default::default() };
+        FacilityGenerator::with_seed(11).generate_facilities(&config).unwrap()
+    }
+
+    #[test]
+    fn test_format_from_path() {
+        assert_eq!(FacilityExportFormat::from_path("facilities.json"), FacilityExportFormat::Json);
+        assert_eq!(FacilityExportFormat::from_path("facilities.csv"), FacilityExportFormat::Csv);
+        assert_eq!(FacilityExportFormat::from_path("facilities.csv.gz"), FacilityExportFormat::Csv);
+        assert_eq!(FacilityExportFormat::from_path("facilities.json.zst"), FacilityExportFormat::Json);
+        assert_eq!(FacilityExportFormat::from_path("facilities"), FacilityExportFormat::Json);
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let registry = create_test_registry();
+        let topology = FacilityTopology::from_registry(&registry);
+
+        let mut buffer = Vec::new();
+        topology.write(&mut buffer, FacilityExportFormat::Json).unwrap();
+        let loaded = FacilityTopology::from_json_reader(buffer.as_slice()).unwrap();
+        assert_eq!(loaded.room_count, registry.total_room_count());
+
+        let rebuilt = loaded.into_registry();
+        assert_eq!(rebuilt.total_building_count(), registry.total_building_count());
+        let room = &registry.locations[0].buildings[0].rooms[1];
+        let rebuilt_room = rebuilt.get_room(room.id).unwrap();
+        assert_eq!(rebuilt_room.room_type, room.room_type);
+        assert_eq!(rebuilt_room.security_level, room.security_level);
+        assert_eq!(rebuilt_room.required_intermediate_access, room.required_intermediate_access);
+    }
+
+    #[test]
+    fn test_csv_has_one_row_per_room() {
+        let registry = create_test_registry();
+        let topology = FacilityTopology::from_registry(&registry);
+
+        let mut buffer = Vec::new();
+        topology.write(&mut buffer, FacilityExportFormat::Csv).unwrap();
+        let output = String::from_utf8(buffer).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines[0], FACILITY_CSV_COLUMNS.join(","));
+        assert_eq!(lines.len(), registry.total_room_count() + 1);
+
+        let building = &registry.locations[0].buildings[0];
+        let lobby_row = topology
+            .csv_rows()
+            .into_iter()
+            .find(|row| Some(row[7].as_str()) == building.lobby_room_id.map(|id| id.to_string()).as_deref())
+            .unwrap();
+        assert_eq!(lobby_row[9], "Lobby");
+        assert_eq!(lobby_row[11], "true");
+    }
+}
@@ -103,6 +103,7 @@ pub use types::{
     SecurityLevel,
     // Configuration
     SimulationConfig,
+    WorkCalendarConfig,
 };
 
 // Permissions and access control
@@ -126,6 +127,6 @@ pub use events::{AccessAttempt, AccessEvent, EventGenerator, ImpossibleTravelerM
 
 // Simulation types and functionality
 pub use simulation::{
-    AccessComplexityStats as SimulationAccessComplexityStats, BehaviorEngine, RuntimeStatistics,
-    SimulationError, SimulationOrchestrator, SimulationStatistics, TimeManager,
+    AccessComplexityStats as SimulationAccessComplexityStats, BehaviorEngine, DayKind, RuntimeStatistics,
+    SimulationError, SimulationOrchestrator, SimulationStatistics, TimeManager, WorkCalendar,
 };
@@ -11,15 +11,25 @@
 //
 
 mod analysis;
-use analysis::{detect_cloned_badges, detect_curious_users, detect_night_shift_users, generate_report, simulate_s3_upload, UserAnomaly};
+mod evaluation;
+mod room_inference;
+mod streaming;
+use analysis::{
+    detect_cloned_badges, detect_curious_users, detect_night_shift_users, generate_report, simulate_s3_upload,
+    EventDetectors, EventObserver, ObservingEventSink, UserAnomaly,
+};
+use room_inference::{infer_room_types, RoomBehaviorCollector};
 
-use amzn_career_pathway_activity_rust::user::UserGenerator;
-use amzn_career_pathway_activity_rust::facility::FacilityGenerator;
+use amzn_career_pathway_activity_rust::user::{IdentityDirectory, UserGenerator, UserRoster};
+use amzn_career_pathway_activity_rust::facility::{FacilityDefinition, FacilityGenerator};
+use amzn_career_pathway_activity_rust::events::EventFormatter;
 use amzn_career_pathway_activity_rust::simulation::{
-    BatchEventGenerator, LoggingConfig, SimulationOrchestrator, SimulationStatistics,
+    create_output_file, write_user_profiles_parquet, BatchEventGenerator, DirectoryEventSink, EventSink, HttpEventSink,
+    LoggingConfig, ParquetEventSink, RetryPolicy, RngStream, RotationPolicy, SimulationOrchestrator, SimulationStatistics,
+    SqliteEventSink, StdoutEventSink, SyslogEventSink, WorkCalendar, USER_PROFILES_PARQUET_FILE_NAME,
 };
 use amzn_career_pathway_activity_rust::types::config::CliArgs;
-use amzn_career_pathway_activity_rust::types::SimulationConfig;
+use amzn_career_pathway_activity_rust::types::{Compression, SimulationConfig};
 use clap::Parser;
 use std::process;
 use tracing::{error, info};
@@ -77,6 +87,24 @@ fn main() {
 
     info!("Configuration loaded and validated successfully");
 
+    // Handle evaluation mode: score an existing anomaly report instead of simulating
+    if let Some(report_path) = &args.evaluate {
+        if let Err(e) = run_evaluation(&config, report_path, args.evaluate_events.as_deref()) {
+            error!("Evaluation failed: {}", e);
+            process::exit(1);
+        }
+        return;
+    }
+
+    // Handle streaming detection mode: run the detectors over existing events instead of simulating
+    if !args.detect.is_empty() {
+        if let Err(e) = run_streaming_detection(&config, &args.detect) {
+            error!("Streaming detection failed: {}", e);
+            process::exit(1);
+        }
+        return;
+    }
+
     // Handle dry run mode
     if args.dry_run {
         eprintln!("Configuration validation successful!");
@@ -101,9 +129,23 @@ fn main() {
     // Run the simulation
     info!("Starting simulation");
     // NOTE: pass clones so we keep ownership of `users` and `location_registry` for post-analysis
-    if let Err(e) =
-        run_simulation(config.clone(), location_registry.clone(), users.clone(), orchestrator)
-    {
+    // The detectors (and the room behavior collector, when asked for) watch the events as they are written
+    let mut detectors = EventDetectors::new(&config, location_registry.clone());
+    let mut room_behavior = args
+        .infer_room_types
+        .as_ref()
+        .map(|_| RoomBehaviorCollector::new(location_registry.clone(), WorkCalendar::new(config.calendar.clone())));
+    let mut observers: Vec<&mut dyn EventObserver> = vec![&mut detectors];
+    if let Some(room_behavior) = room_behavior.as_mut() {
+        observers.push(room_behavior);
+    }
+    if let Err(e) = run_simulation(
+        config.clone(),
+        location_registry.clone(),
+        users.clone(),
+        orchestrator,
+        observers,
+    ) {
         error!("Simulation failed: {}", e);
         process::exit(1);
     }
@@ -115,10 +157,7 @@ fn main() {
 info!("Starting anomaly analysis on generated users");
 
     // Path to JSON, same as what we just generated
-let user_profiles_path = config
-    .user_profiles_output
-    .clone()
-    .unwrap_or_else(|| "user_profiles.json".to_string());
+let (user_profiles_path, _) = config.resolved_user_profiles_output();
 
 // Check that the file exists before analyzing
 if !std::path::Path::new(&user_profiles_path).exists() {
@@ -129,34 +168,162 @@ if !std::path::Path::new(&user_profiles_path).exists() {
     // Detect anomalies from JSON
     let mut all_anomalies: Vec<UserAnomaly> = Vec::new();
 
-    // Run cloned-badge detection
-    let cloned_badges = detect_cloned_badges(&user_profiles_path);
+    // Run cloned-badge detection on the impossible travel seen in the event stream
+    let cloned_badges = detect_cloned_badges(detectors.cloned_badges.findings());
     info!("Detected {} cloned-badge anomalies", cloned_badges.len());
     all_anomalies.extend(cloned_badges);
 
-    // Run curious/unauthorized-access detection
-    let curious_users = detect_curious_users(&user_profiles_path);
+    // Run curious/unauthorized-access detection on denied attempts scored against building peers
+    let curious_users = detect_curious_users(&detectors.curious_users.ranking());
     info!(
         "Detected {} curious/unauthorized-access anomalies",
         curious_users.len()
     );
     all_anomalies.extend(curious_users);
-    let night_shift_users = detect_night_shift_users(&user_profiles_path);
+    let night_shift_users = match detect_night_shift_users(&user_profiles_path) {
+        Ok(anomalies) => anomalies,
+        Err(e) => {
+            error!("Night-shift detection failed: {}", e);
+            process::exit(1);
+        }
+    };
     info!("Detected {} night-shift anomalies", night_shift_users.len());
     
     all_anomalies.extend(night_shift_users);
     // Generate a JSON report file (anomaly_report.json) in project root
     let report_path = "anomaly_report.json";
-    generate_report(report_path, &all_anomalies);
-    simulate_s3_upload("anomaly_report.json", "security-anomaly-logs-demo");
+    if let Err(e) = generate_report(report_path, &all_anomalies)
+        .and_then(|_| simulate_s3_upload(report_path, "security-anomaly-logs-demo"))
+    {
+        error!("Failed to write anomaly report: {}", e);
+        process::exit(1);
+    }
     info!(" Analysis complete! Report saved to {}", report_path);
 
+    if let (Some(collector), Some(output_path)) = (&room_behavior, &args.infer_room_types) {
+        if let Err(e) = write_room_inference(collector, &location_registry, output_path) {
+            error!("Room type inference failed: {}", e);
+            process::exit(1);
+        }
+    }
+
     
 }
 
+/// Score an anomaly report against the user profiles answer key and print the results as JSON
+fn run_evaluation(config: &SimulationConfig, report_path: &str, events_path: Option<&str>) -> Result<(), String> {
+    let (user_profiles_path, _) = config.resolved_user_profiles_output();
+    eprintln!("Evaluating {} against {}", report_path, user_profiles_path);
+
+    let detections = evaluation::load_detections(report_path)?;
+    let profiles = evaluation::load_answer_key(&user_profiles_path)?;
+    let first_labeled_events = match events_path {
+        Some(events_path) => {
+            let first_labeled_events = evaluation::load_first_labeled_events(events_path, &profiles)?;
+            if first_labeled_events.is_empty() {
+                eprintln!(
+                    "Warning: no labeled anomalous events in {}; time-to-detect needs events written with --include-metadata",
+                    events_path
+                );
+            }
+            Some(first_labeled_events)
+        }
+        None => None,
+    };
+
+    let results = evaluation::evaluate(&detections, &profiles, first_labeled_events.as_ref());
+    evaluation::print_evaluation_summary(&results);
+    let json = serde_json::to_string_pretty(&results).map_err(|e| format!("Failed to serialize evaluation: {}", e))?;
+    println!("{}", json);
+    Ok(())
+}
+
+/// Predict room types from the collected behavior, score them against the facility layout
+/// and write the report as JSON
+fn write_room_inference(
+    collector: &RoomBehaviorCollector,
+    location_registry: &amzn_career_pathway_activity_rust::facility::LocationRegistry,
+    output_path: &str,
+) -> Result<(), String> {
+    let report = infer_room_types(collector, location_registry);
+    let percent = |accuracy: Option<f64>| accuracy.map_or("n/a".to_string(), |a| format!("{:.1}%", a * 100.0));
+    eprintln!(
+        "Room type inference: {} of {} rooms observed in {} clusters, room type accuracy {}, security level accuracy {}",
+        report.rooms_observed,
+        report.rooms_total,
+        report.clusters.len(),
+        percent(report.room_type_accuracy),
+        percent(report.security_level_accuracy)
+    );
+
+    let json = serde_json::to_string_pretty(&report).map_err(|e| format!("Failed to serialize room inference: {}", e))?;
+    std::fs::write(output_path, json).map_err(|e| format!("Failed to write {}: {}", output_path, e))?;
+    info!("Room type inference saved to {}", output_path);
+    Ok(())
+}
+
+/// Run the detectors over events read from files or stdin, writing alerts to stdout as they fire
+fn run_streaming_detection(config: &SimulationConfig, inputs: &[String]) -> Result<(), String> {
+    // A layout generated without a seed never matches the events' rooms and locations
+    if config.seed.is_none() && config.facility_definition.is_none() {
+        return Err(
+            "--detect needs the facility layout of the events: pass --facility-definition, or the --seed and scale options used for the simulation"
+                .to_string(),
+        );
+    }
+    let location_registry = load_facilities(config)?;
+    let mut detector = streaming::StreamingDetector::new(config, location_registry);
+
+    let stdout = std::io::stdout();
+    let mut alerts = stdout.lock();
+    streaming::detect_streams(&mut detector, inputs, &mut alerts)?;
+
+    let statistics = detector.statistics();
+    eprintln!(
+        "Streaming detection: {} events, {} alerts, {} invalid lines skipped, {} users tracked at peak, {} evicted",
+        statistics.events,
+        statistics.alerts,
+        statistics.invalid_lines,
+        statistics.peak_tracked_users,
+        statistics.evicted_users
+    );
+
+    if statistics.unresolved_events > 0 {
+        return Err(format!(
+            "{} of {} events reference rooms or locations missing from the facility layout; check that --facility-definition, or --seed and the scale options, match the simulation that wrote them",
+            statistics.unresolved_events, statistics.events
+        ));
+    }
+    Ok(())
+}
+
+/// Load facilities from a definition file, or generate them (locations, buildings, rooms)
+fn load_facilities(
+    config: &SimulationConfig,
+) -> Result<amzn_career_pathway_activity_rust::facility::LocationRegistry, String> {
+    match &config.facility_definition {
+        Some(definition_path) => {
+            eprintln!("Loading facilities from {}...", definition_path);
+            FacilityDefinition::from_path(definition_path)
+                .and_then(|definition| definition.into_registry())
+                .map_err(|e| format!("Failed to load facilities: {}", e))
+        }
+        None => {
+            eprintln!("Generating facilities...");
+            let mut facility_generator = match config.seed {
+                Some(seed) => FacilityGenerator::with_seed(seed),
+                None => FacilityGenerator::new(),
+            };
+            facility_generator
+                .generate_facilities(config)
+                .map_err(|e| format!("Failed to generate facilities: {}", e))
+        }
+    }
+}
+
 /// Initialize the complete simulation system
 fn initialize_simulation(
-    config: SimulationConfig,
+    mut config: SimulationConfig,
 ) -> Result<
     (
         SimulationOrchestrator,
@@ -167,26 +334,41 @@ fn initialize_simulation(
 > {
     info!("Initializing simulation components...");
 
-    // Generate facilities (locations, buildings, rooms)
-    eprintln!("Generating facilities...");
-    let mut facility_generator = FacilityGenerator::new();
-    let location_registry = facility_generator
-        .generate_facilities(&config)
-        .map_err(|e| format!("Failed to generate facilities: {}", e))?;
+    let location_registry = load_facilities(&config)?;
 
     info!(
-        "Generated {} locations with {} total buildings and {} total rooms",
+        "Set up {} locations with {} total buildings and {} total rooms",
         location_registry.location_count(),
         location_registry.total_building_count(),
         location_registry.total_room_count()
     );
 
-    // Generate users with permissions
-    eprintln!("Generating users...");
-    let mut user_generator = UserGenerator::new();
-    let users = user_generator
-        .generate_users(&config, &location_registry)
-        .map_err(|e| format!("Failed to generate users: {}", e))?;
+    if let Some((facility_path, facility_compression)) = config.resolved_facility_output() {
+        generate_facility_output(&location_registry, &facility_path, facility_compression)?;
+        eprintln!("Facility topology successfully written to: {}", facility_path);
+    }
+
+    // Build users from a roster file, or generate them with permissions
+    let mut user_generator = match config.seed {
+        Some(seed) => UserGenerator::with_seed(RngStream::Users.derive_seed(seed)),
+        None => UserGenerator::new(),
+    };
+    let users = match &config.user_roster {
+        Some(roster_path) => {
+            eprintln!("Loading users from {}...", roster_path);
+            let users = UserRoster::from_path(roster_path)
+                .and_then(|roster| user_generator.generate_users_from_roster(&roster, &config, &location_registry))
+                .map_err(|e| format!("Failed to load users: {}", e))?;
+            config.user_count = users.len();
+            users
+        }
+        None => {
+            eprintln!("Generating users...");
+            user_generator
+                .generate_users(&config, &location_registry)
+                .map_err(|e| format!("Failed to generate users: {}", e))?
+        }
+    };
 
     info!(
         "Generated {} users ({} curious, {} with cloned badges)",
@@ -222,13 +404,10 @@ fn initialize_simulation(
     );
 
     // Always generate user profiles JSON, defaulting to "user_profiles.json" if not set
-let user_profiles_path = config
-    .user_profiles_output
-    .clone()
-    .unwrap_or_else(|| "user_profiles.json".to_string());
+let (user_profiles_path, user_profiles_compression) = config.resolved_user_profiles_output();
 
 eprintln!("Generating user profiles output at {}", user_profiles_path);
-if let Err(e) = generate_user_profiles_output(&config, &users, &user_profiles_path) {
+if let Err(e) = generate_user_profiles_output(&config, &users, &user_profiles_path, user_profiles_compression) {
     error!("Failed to generate user profiles output: {}", e);
     return Err(format!(
         "Failed to generate user profiles output: {}",
@@ -238,6 +417,12 @@ if let Err(e) = generate_user_profiles_output(&config, &users, &user_profiles_pa
 info!("User profiles successfully written to: {}", user_profiles_path);
 eprintln!("User profiles successfully written to: {}", user_profiles_path);
 
+    if let (true, Some(parquet_dir)) = (config.parquet_user_profiles, &config.parquet_dir) {
+        let parquet_path = std::path::Path::new(parquet_dir).join(USER_PROFILES_PARQUET_FILE_NAME);
+        generate_user_profiles_parquet(&config, &users, &parquet_path)?;
+        eprintln!("User profiles successfully written to: {}", parquet_path.display());
+    }
+
     // Print actual configuration summary with real statistics
     eprintln!("\nActual Generation Results:");
     print_configuration_summary_with_stats(&config, Some(&stats));
@@ -252,6 +437,7 @@ fn run_simulation(
     location_registry: amzn_career_pathway_activity_rust::facility::LocationRegistry,
     users: Vec<amzn_career_pathway_activity_rust::user::User>,
     _orchestrator: SimulationOrchestrator,
+    observers: Vec<&mut dyn EventObserver>,
 ) -> Result<(), String> {
     use std::time::Instant;
 
@@ -261,11 +447,13 @@ fn run_simulation(
     info!("Running batch simulation for {} days", config.days);
     // Create batch event generator
     eprintln!("Initializing batch event generator...");
+    let mut event_sink =
+        ObservingEventSink::new(create_event_sink(&config, &location_registry, &users)?, observers);
     let mut batch_generator = BatchEventGenerator::new(config.clone(), location_registry, users);
     // Generate events for the specified number of days
     eprintln!("Generating events for {} days...", config.days);
     batch_generator
-        .generate_events_for_days(config.days)
+        .generate_events_with_sink(config.days, &mut event_sink)
         .map_err(|e| format!("Batch event generation failed: {}", e))?;
     eprintln!("Batch event generation completed!");
 
@@ -279,6 +467,86 @@ fn run_simulation(
     Ok(())
 }
 
+/// Create the event sink selected by the configuration (stdout by default)
+fn create_event_sink(
+    config: &SimulationConfig,
+    location_registry: &amzn_career_pathway_activity_rust::facility::LocationRegistry,
+    users: &[amzn_career_pathway_activity_rust::user::User],
+) -> Result<Box<dyn EventSink>, String> {
+    let registry = std::sync::Arc::new(location_registry.clone());
+    let identities = std::sync::Arc::new(IdentityDirectory::from_users(users));
+    let formatter = EventFormatter::from_config(config)?
+        .with_registry(registry.clone())
+        .with_identities(identities.clone());
+    let compression = config.get_compression()?;
+
+    if let Some(url) = &config.http_url {
+        eprintln!("Posting events to {}", url);
+        let mut sink = HttpEventSink::new(url.clone(), formatter)
+            .map_err(|e| format!("Failed to set up HTTP output: {}", e))?
+            .with_body_format(config.get_http_body_format()?);
+        if let Some(batch_size) = config.http_batch_size {
+            sink = sink.with_batch_size(batch_size);
+        }
+        if let Some((name, value)) = config.get_http_auth_header()? {
+            sink = sink.with_header(name, value);
+        }
+        if let Some(max_retries) = config.http_max_retries {
+            sink = sink.with_retry_policy(RetryPolicy { max_retries, ..Default::default() });
+        }
+        if let Some(dead_letter) = &config.http_dead_letter {
+            sink = sink.with_dead_letter(dead_letter);
+        }
+        return Ok(Box::new(sink));
+    }
+
+    if let Some(path) = &config.sqlite {
+        eprintln!("Writing facilities, users and events to SQLite database {}", path);
+        let sink = SqliteEventSink::create(path, location_registry, users, config.output_fields.clone())
+            .map_err(|e| format!("Failed to set up SQLite output: {}", e))?;
+        return Ok(Box::new(sink));
+    }
+
+    if let Some(parquet_dir) = &config.parquet_dir {
+        eprintln!("Writing events to Parquet files in {}", parquet_dir);
+        let sink = ParquetEventSink::create(parquet_dir, config.output_fields.clone())
+            .map_err(|e| format!("Failed to set up Parquet output: {}", e))?
+            .with_registry(registry)
+            .with_identities(identities);
+        return Ok(Box::new(sink));
+    }
+
+    if let Some(target) = &config.syslog {
+        let protocol = config.get_syslog_protocol()?;
+        eprintln!("Sending events to syslog collector {} over {}", target, protocol);
+        let mut sink = SyslogEventSink::connect(target, protocol, formatter)
+            .map_err(|e| format!("Failed to set up syslog output: {}", e))?;
+        if let Some(speed) = config.syslog_speed {
+            sink = sink.with_pacing(speed);
+        }
+        return Ok(Box::new(sink));
+    }
+
+    match &config.output_dir {
+        Some(output_dir) => {
+            let rotation = RotationPolicy {
+                max_bytes: config.rotate_max_bytes,
+                max_events: config.rotate_max_events,
+            };
+            eprintln!("Writing events to directory {}", output_dir);
+            let sink = DirectoryEventSink::create(output_dir, formatter, rotation)
+                .map_err(|e| format!("Failed to set up directory output: {}", e))?
+                .with_compression(compression);
+            Ok(Box::new(sink))
+        }
+        None => {
+            let sink = StdoutEventSink::stdout_compressed(formatter, compression)
+                .map_err(|e| format!("Failed to set up compressed output: {}", e))?;
+            Ok(Box::new(sink))
+        }
+    }
+}
+
 /// Print startup banner and configuration summary
 fn print_startup_banner(config: &SimulationConfig) {
     eprintln!("Badge Access Simulator");
@@ -300,7 +568,10 @@ fn print_configuration_summary_with_stats(
     stats: Option<&SimulationStatistics>,
 ) {
     eprintln!("Configuration:");
-    eprintln!(" User Count: {}", config.user_count);
+    match &config.user_roster {
+        Some(user_roster) if stats.is_none() => eprintln!(" User Roster: {}", user_roster),
+        _ => eprintln!(" User Count: {}", config.user_count),
+    }
     eprintln!(" Location Count: {}", config.location_count);
     eprintln!(
         " Buildings per Location: {} - {}",
@@ -322,9 +593,38 @@ fn print_configuration_summary_with_stats(
         config.different_location_travel * 100.0
     );
     eprintln!(" Output Format: {}", config.output_format);
+    if let Some(output_dir) = &config.output_dir {
+        eprintln!(" Output Directory: {}", output_dir);
+    }
+    if let Some(compress) = &config.compress {
+        eprintln!(" Compression: {}", compress);
+    }
+    if let Some(http_url) = &config.http_url {
+        eprintln!(" HTTP Endpoint: {}", http_url);
+    }
+    if let Some(sqlite) = &config.sqlite {
+        eprintln!(" SQLite Output: {}", sqlite);
+    }
+    if let Some(parquet_dir) = &config.parquet_dir {
+        eprintln!(" Parquet Directory: {}", parquet_dir);
+    }
+    if let Some(facility_output) = &config.facility_output {
+        eprintln!(" Facility Output: {}", facility_output);
+    }
+    if let Some(facility_definition) = &config.facility_definition {
+        eprintln!(" Facility Definition: {}", facility_definition);
+    }
+    if let Some(syslog) = &config.syslog {
+        eprintln!(
+            " Syslog Target: {} ({})",
+            syslog,
+            config.get_syslog_protocol().unwrap_or_default()
+        );
+    }
     if let Some(seed) = config.seed {
         eprintln!(" Random Seed: {}", seed);
     }
+    eprintln!(" Start Date: {}", config.simulation_start_date());
 
     if let Some(stats) = stats {
         eprintln!("\nActual Scale:");
@@ -389,21 +689,20 @@ fn generate_user_profiles_output(
     config: &SimulationConfig,
     users: &[amzn_career_pathway_activity_rust::user::User],
     output_path: &str,
+    compression: Compression,
 ) -> Result<(), String> {
     use amzn_career_pathway_activity_rust::user::UserProfile;
-    use std::fs::File;
-    use std::io::{BufWriter, Write};
+    use std::io::Write;
 
-    info!("Generating user profiles output to: {}", output_path);
+    info!("Generating user profiles output to: {} (compression: {})", output_path, compression);
 
     // Create output file
-    let file = File::create(output_path).map_err(|e| {
+    let mut writer = create_output_file(output_path, compression).map_err(|e| {
         format!(
             "Failed to create user profiles output file '{}': {}",
             output_path, e
         )
     })?;
-    let mut writer = BufWriter::new(file);
 
     // Generate user profile for each user and write as JSONL
     for user in users {
@@ -417,15 +716,61 @@ fn generate_user_profiles_output(
             .map_err(|e| format!("Failed to write user profile line: {}", e))?;
     }
 
-    // Ensure all data is written
+    // Ensure all data is written and the compressed stream is finalized
     writer
-        .flush()
+        .finish()
         .map_err(|e| format!("Failed to flush user profiles output: {}", e))?;
 
     info!("Successfully wrote {} user profiles to {}", users.len(), output_path);
     Ok(())
 }
 
+/// Generate the facility topology output file (JSON, or CSV for `.csv` paths)
+///
+/// This resolves the opaque ids in event output to locations, buildings and rooms,
+/// including each room's type and security level.
+fn generate_facility_output(
+    location_registry: &amzn_career_pathway_activity_rust::facility::LocationRegistry,
+    output_path: &str,
+    compression: Compression,
+) -> Result<(), String> {
+    use amzn_career_pathway_activity_rust::facility::{FacilityExportFormat, FacilityTopology};
+
+    let format = FacilityExportFormat::from_path(output_path);
+    info!("Generating facility topology output to: {} (format: {}, compression: {})", output_path, format, compression);
+
+    let mut writer = create_output_file(output_path, compression).map_err(|e| {
+        format!("Failed to create facility output file '{}': {}", output_path, e)
+    })?;
+    FacilityTopology::from_registry(location_registry)
+        .write(&mut writer, format)
+        .map_err(|e| format!("Failed to write facility topology: {}", e))?;
+    writer
+        .finish()
+        .map_err(|e| format!("Failed to flush facility output: {}", e))?;
+
+    info!("Successfully wrote {} rooms to {}", location_registry.total_room_count(), output_path);
+    Ok(())
+}
+
+/// Generate the user profiles answer key as a Parquet file
+fn generate_user_profiles_parquet(
+    config: &SimulationConfig,
+    users: &[amzn_career_pathway_activity_rust::user::User],
+    output_path: &std::path::Path,
+) -> Result<(), String> {
+    use amzn_career_pathway_activity_rust::user::UserProfile;
+
+    std::fs::create_dir_all(output_path.parent().unwrap_or(std::path::Path::new(".")))
+        .map_err(|e| format!("Failed to create Parquet output directory: {}", e))?;
+    let profiles: Vec<UserProfile> = users.iter().map(|user| UserProfile::from_user(user, config)).collect();
+    write_user_profiles_parquet(output_path, &profiles)
+        .map_err(|e| format!("Failed to write Parquet user profiles: {}", e))?;
+
+    info!("Successfully wrote {} user profiles to {}", users.len(), output_path.display());
+    Ok(())
+}
+
 /// Print final statistics with enhanced event tracking from orchestrator (legacy)
 ///
 /// This function is kept for backward compatibility but should be replaced
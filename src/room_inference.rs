@@ -0,0 +1,584 @@
+// Room type inference from access behavior
+//
+// Collects behavioral features for every room from the event stream, clusters rooms with
+// similar features, and names each cluster with the RoomType and SecurityLevel its
+// behavior points to. Only the events' rooms, users, success flags and timestamps are
+// used; the facility layout only supplies local time zones and holidays, and
+// its room types and security levels serve as the answer key for accuracy scoring.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use amzn_career_pathway_activity_rust::events::AccessEvent;
+use amzn_career_pathway_activity_rust::facility::LocationRegistry;
+use amzn_career_pathway_activity_rust::simulation::WorkCalendar;
+use amzn_career_pathway_activity_rust::types::{BuildingId, RoomId, RoomType, SecurityLevel, UserId};
+use chrono::{DateTime, Timelike, Utc};
+use serde::Serialize;
+
+use crate::analysis::EventObserver;
+
+/// Upper bounds (in minutes) of the dwell time histogram buckets; longer gaps are dropped
+const DWELL_BUCKET_BOUNDS: [f64; 16] =
+    [1.0, 2.0, 3.0, 5.0, 7.5, 10.0, 15.0, 20.0, 30.0, 45.0, 60.0, 90.0, 120.0, 180.0, 360.0, 720.0];
+
+/// Rooms with fewer events are too noisy to shape the clusters and only join the nearest one
+const MIN_CLUSTERED_EVENTS: f64 = 10.0;
+
+/// Most clusters formed, before empty ones are dropped
+const MAX_CLUSTERS: usize = 16;
+
+/// Rounds of k-means refinement
+const MAX_KMEANS_ITERATIONS: usize = 50;
+
+/// Behavior observed at one room
+#[derive(Debug, Default)]
+struct RoomObservations {
+    building_id: Option<BuildingId>,
+    events: usize,
+    failures: usize,
+    users: HashSet<UserId>,
+    users_granted: HashSet<UserId>,
+    hourly_events: [usize; 24],
+    non_work_day_events: usize,
+    dwell_histogram: [usize; DWELL_BUCKET_BOUNDS.len()],
+}
+
+/// Collects room features from the event stream
+///
+/// Dwell time is the gap between a successful entry and the same user's next event
+/// anywhere, kept as a histogram so memory does not grow with the number of events.
+#[derive(Debug)]
+pub struct RoomBehaviorCollector {
+    location_registry: LocationRegistry,
+    calendar: WorkCalendar,
+    rooms: HashMap<RoomId, RoomObservations>,
+    building_users: HashMap<BuildingId, HashSet<UserId>>,
+    last_entries: HashMap<UserId, (RoomId, DateTime<Utc>)>,
+}
+
+impl RoomBehaviorCollector {
+    pub fn new(location_registry: LocationRegistry, calendar: WorkCalendar) -> Self {
+        Self {
+            location_registry,
+            calendar,
+            rooms: HashMap::new(),
+            building_users: HashMap::new(),
+            last_entries: HashMap::new(),
+        }
+    }
+
+    /// Features of every room with at least one event
+    pub fn features(&self) -> Vec<(RoomId, RoomFeatures)> {
+        let mut features: Vec<(RoomId, RoomFeatures)> = self
+            .rooms
+            .iter()
+            .map(|(room_id, room)| {
+                let building_users = room
+                    .building_id
+                    .and_then(|building_id| self.building_users.get(&building_id))
+                    .map_or(0, HashSet::len);
+                (*room_id, RoomFeatures::from_observations(room, building_users))
+            })
+            .collect();
+        features.sort_by_key(|(room_id, _)| room_id.0);
+        features
+    }
+}
+
+impl EventObserver for RoomBehaviorCollector {
+    fn observe(&mut self, event: &AccessEvent) {
+        if let Some((room_id, entered_at)) = self.last_entries.remove(&event.user_id) {
+            let minutes = (event.timestamp - entered_at).num_seconds() as f64 / 60.0;
+            if let Some(bucket) = DWELL_BUCKET_BOUNDS.iter().position(|bound| minutes < *bound) {
+                if let Some(room) = self.rooms.get_mut(&room_id) {
+                    room.dwell_histogram[bucket] += 1;
+                }
+            }
+        }
+
+        let location = self.location_registry.get_location(event.location_id);
+        let local_time = match location {
+            Some(location) => location.local_time(event.timestamp).naive_local(),
+            None => event.local_timestamp().naive_local(),
+        };
+
+        let room = self.rooms.entry(event.room_id).or_default();
+        room.building_id = Some(event.building_id);
+        room.events += 1;
+        room.users.insert(event.user_id);
+        room.hourly_events[local_time.hour() as usize] += 1;
+        if !self.calendar.day_kind(local_time.date(), location).is_work_day() {
+            room.non_work_day_events += 1;
+        }
+        if event.success {
+            room.users_granted.insert(event.user_id);
+            self.last_entries.insert(event.user_id, (event.room_id, event.timestamp));
+        } else {
+            room.failures += 1;
+        }
+        self.building_users.entry(event.building_id).or_default().insert(event.user_id);
+    }
+}
+
+/// Behavioral features of a room (or the mean features of a cluster)
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct RoomFeatures {
+    pub events: f64,
+    /// Events per distinct user who badged at the room
+    pub events_per_user: f64,
+    /// Share of the building's users who got into the room
+    pub user_share: f64,
+    /// Median minutes between entering the room and the user's next event
+    pub median_dwell_minutes: f64,
+    /// Share of events between 11:00 and 14:00 local time
+    pub lunch_share: f64,
+    /// Share of events between 22:00 and 06:00 local time
+    pub night_share: f64,
+    /// Share of events on weekends and holidays
+    pub non_work_day_share: f64,
+    pub failure_rate: f64,
+    /// Share of events in each local hour
+    pub hourly_histogram: Vec<f64>,
+}
+
+impl RoomFeatures {
+    fn from_observations(room: &RoomObservations, building_users: usize) -> Self {
+        let events = room.events.max(1) as f64;
+        let share_of_hours = |hours: &mut dyn Iterator<Item = usize>| {
+            hours.map(|hour| room.hourly_events[hour]).sum::<usize>() as f64 / events
+        };
+
+        Self {
+            events: room.events as f64,
+            events_per_user: room.events as f64 / room.users.len().max(1) as f64,
+            user_share: room.users_granted.len() as f64 / building_users.max(1) as f64,
+            median_dwell_minutes: median_dwell(&room.dwell_histogram),
+            lunch_share: share_of_hours(&mut (11..14)),
+            night_share: share_of_hours(&mut (0..6).chain(22..24)),
+            non_work_day_share: room.non_work_day_events as f64 / events,
+            failure_rate: room.failures as f64 / events,
+            hourly_histogram: room.hourly_events.iter().map(|count| *count as f64 / events).collect(),
+        }
+    }
+
+    /// Features used for clustering, on comparable scales
+    fn clustering_vector(&self) -> Vec<f64> {
+        vec![
+            self.events_per_user.ln_1p(),
+            self.user_share,
+            self.median_dwell_minutes.ln_1p(),
+            self.lunch_share,
+            self.night_share,
+            self.non_work_day_share,
+            self.failure_rate,
+        ]
+    }
+
+    /// Mean of several rooms' features
+    fn mean<'a>(features: impl Iterator<Item = &'a RoomFeatures>) -> Self {
+        let mut mean = RoomFeatures { hourly_histogram: vec![0.0; 24], ..Default::default() };
+        let mut count = 0.0;
+        for room in features {
+            mean.events += room.events;
+            mean.events_per_user += room.events_per_user;
+            mean.user_share += room.user_share;
+            mean.median_dwell_minutes += room.median_dwell_minutes;
+            mean.lunch_share += room.lunch_share;
+            mean.night_share += room.night_share;
+            mean.non_work_day_share += room.non_work_day_share;
+            mean.failure_rate += room.failure_rate;
+            for (total, share) in mean.hourly_histogram.iter_mut().zip(&room.hourly_histogram) {
+                *total += share;
+            }
+            count += 1.0;
+        }
+
+        if count > 0.0 {
+            for value in [
+                &mut mean.events,
+                &mut mean.events_per_user,
+                &mut mean.user_share,
+                &mut mean.median_dwell_minutes,
+                &mut mean.lunch_share,
+                &mut mean.night_share,
+                &mut mean.non_work_day_share,
+                &mut mean.failure_rate,
+            ] {
+                *value /= count;
+            }
+            mean.hourly_histogram.iter_mut().for_each(|share| *share /= count);
+        }
+        mean
+    }
+
+    /// Room type whose usual behavior matches these features
+    ///
+    /// Most attempts refused: a restricted room, a server room when it is mostly visited at
+    /// night (by patrols), otherwise an executive office; storage when refusals are frequent
+    /// but not the rule. Laboratories are refused as consistently as those rooms, at the same
+    /// hours, so they are never predicted. Open rooms: a lobby when users move on
+    /// within minutes, a cafeteria when lunch dominates, a bathroom or meeting room when
+    /// most or many of the building's users get in, a kitchen when it sees night and
+    /// weekend use, and a workspace otherwise.
+    pub fn room_type(&self) -> RoomType {
+        if self.failure_rate >= 0.5 {
+            if self.night_share >= 0.5 {
+                RoomType::ServerRoom
+            } else {
+                RoomType::ExecutiveOffice
+            }
+        } else if self.failure_rate >= 0.15 && self.user_share < 0.05 {
+            RoomType::Storage
+        } else if self.median_dwell_minutes < 5.0 {
+            RoomType::Lobby
+        } else if self.lunch_share >= 0.5 {
+            RoomType::Cafeteria
+        } else if self.user_share >= 0.75 {
+            RoomType::Bathroom
+        } else if self.user_share >= 0.35 {
+            RoomType::MeetingRoom
+        } else if self.night_share + self.non_work_day_share >= 0.3 {
+            RoomType::Kitchen
+        } else {
+            RoomType::Workspace
+        }
+    }
+
+    /// Security level of a room type, raised to Restricted when most attempts are refused
+    pub fn security_level(&self, room_type: RoomType) -> SecurityLevel {
+        let typical = match room_type {
+            RoomType::Lobby | RoomType::Bathroom | RoomType::Cafeteria | RoomType::Kitchen => SecurityLevel::Public,
+            RoomType::Workspace | RoomType::MeetingRoom | RoomType::Storage => SecurityLevel::Standard,
+            RoomType::ExecutiveOffice => SecurityLevel::Restricted,
+            RoomType::ServerRoom | RoomType::Laboratory => SecurityLevel::HighSecurity,
+        };
+        if self.failure_rate >= 0.5 && matches!(typical, SecurityLevel::Public | SecurityLevel::Standard) {
+            SecurityLevel::Restricted
+        } else {
+            typical
+        }
+    }
+}
+
+/// Median of a dwell histogram, taken as the upper bound of the bucket it falls in
+fn median_dwell(histogram: &[usize; DWELL_BUCKET_BOUNDS.len()]) -> f64 {
+    let total: usize = histogram.iter().sum();
+    if total == 0 {
+        return 0.0;
+    }
+    let mut seen = 0;
+    for (bucket, count) in histogram.iter().enumerate() {
+        seen += count;
+        if seen * 2 >= total {
+            return DWELL_BUCKET_BOUNDS[bucket];
+        }
+    }
+    DWELL_BUCKET_BOUNDS[DWELL_BUCKET_BOUNDS.len() - 1]
+}
+
+/// Group rooms with k-means on standardized features, seeded by farthest-point selection
+///
+/// Only rooms with enough events take part in fitting the clusters (all of them when none
+/// has); the others are assigned to the nearest cluster at the end. Returns the cluster of
+/// each room; cluster numbers are dense and start at 0.
+fn cluster_rooms(features: &[RoomFeatures], max_clusters: usize) -> Vec<usize> {
+    if features.is_empty() {
+        return Vec::new();
+    }
+
+    // Standardize every dimension so no single feature dominates the distances
+    let vectors: Vec<Vec<f64>> = features.iter().map(RoomFeatures::clustering_vector).collect();
+    let dimensions = vectors[0].len();
+    let count = vectors.len() as f64;
+    let points: Vec<Vec<f64>> = {
+        let means: Vec<f64> = (0..dimensions).map(|d| vectors.iter().map(|v| v[d]).sum::<f64>() / count).collect();
+        let deviations: Vec<f64> = (0..dimensions)
+            .map(|d| {
+                let variance = vectors.iter().map(|v| (v[d] - means[d]).powi(2)).sum::<f64>() / count;
+                variance.sqrt().max(1e-9)
+            })
+            .collect();
+        vectors
+            .iter()
+            .map(|v| (0..dimensions).map(|d| (v[d] - means[d]) / deviations[d]).collect())
+            .collect()
+    };
+    let distance = |a: &[f64], b: &[f64]| a.iter().zip(b).map(|(x, y)| (x - y).powi(2)).sum::<f64>();
+    let nearest_centroid = |point: &[f64], centroids: &[Vec<f64>]| {
+        (0..centroids.len())
+            .min_by(|a, b| distance(point, &centroids[*a]).total_cmp(&distance(point, &centroids[*b])))
+            .unwrap_or(0)
+    };
+
+    let mut fitted: Vec<usize> = (0..features.len()).filter(|i| features[*i].events >= MIN_CLUSTERED_EVENTS).collect();
+    if fitted.is_empty() {
+        fitted = (0..features.len()).collect();
+    }
+
+    // Start from the busiest room and keep adding the room farthest from all centroids
+    let busiest = *fitted.iter().max_by(|a, b| features[**a].events.total_cmp(&features[**b].events)).expect("fitted is not empty");
+    let mut centroids = vec![points[busiest].clone()];
+    while centroids.len() < max_clusters.min(fitted.len()) {
+        let nearest = |point: &Vec<f64>| centroids.iter().map(|c| distance(point, c)).fold(f64::INFINITY, f64::min);
+        let (farthest, gap) = fitted
+            .iter()
+            .map(|index| (*index, nearest(&points[*index])))
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .expect("fitted is not empty");
+        if gap <= 0.0 {
+            break;
+        }
+        centroids.push(points[farthest].clone());
+    }
+
+    let mut fitted_assignments = vec![0; fitted.len()];
+    for _ in 0..MAX_KMEANS_ITERATIONS {
+        let next: Vec<usize> = fitted.iter().map(|index| nearest_centroid(&points[*index], &centroids)).collect();
+        let converged = next == fitted_assignments;
+        fitted_assignments = next;
+        if converged {
+            break;
+        }
+        for (cluster, centroid) in centroids.iter_mut().enumerate() {
+            let members: Vec<&Vec<f64>> = fitted
+                .iter()
+                .zip(&fitted_assignments)
+                .filter(|(_, a)| **a == cluster)
+                .map(|(index, _)| &points[*index])
+                .collect();
+            if !members.is_empty() {
+                *centroid = (0..dimensions)
+                    .map(|d| members.iter().map(|p| p[d]).sum::<f64>() / members.len() as f64)
+                    .collect();
+            }
+        }
+    }
+    let assignments: Vec<usize> = points.iter().map(|point| nearest_centroid(point, &centroids)).collect();
+
+    // Renumber the clusters that kept members
+    let mut renumbered = BTreeMap::new();
+    assignments
+        .iter()
+        .map(|cluster| {
+            let next = renumbered.len();
+            *renumbered.entry(*cluster).or_insert(next)
+        })
+        .collect()
+}
+
+/// Predicted and actual type of one room
+#[derive(Debug, Clone, Serialize)]
+pub struct RoomPrediction {
+    pub room_id: RoomId,
+    pub building_id: Option<BuildingId>,
+    pub cluster: usize,
+    pub predicted_room_type: RoomType,
+    pub predicted_security_level: SecurityLevel,
+    pub actual_room_type: Option<RoomType>,
+    pub actual_security_level: Option<SecurityLevel>,
+    pub features: RoomFeatures,
+}
+
+/// A cluster of rooms with the type its mean behavior points to
+#[derive(Debug, Clone, Serialize)]
+pub struct RoomCluster {
+    pub cluster: usize,
+    pub rooms: usize,
+    pub room_type: RoomType,
+    pub security_level: SecurityLevel,
+    pub mean_features: RoomFeatures,
+}
+
+/// Room type predictions scored against the facility layout
+#[derive(Debug, Clone, Serialize)]
+pub struct RoomInferenceReport {
+    pub rooms_total: usize,
+    /// Rooms with at least one event, which are the ones predicted
+    pub rooms_observed: usize,
+    pub room_type_accuracy: Option<f64>,
+    pub security_level_accuracy: Option<f64>,
+    /// Number of rooms of each actual type (outer key) predicted as each type (inner key)
+    pub room_type_confusion: BTreeMap<String, BTreeMap<String, usize>>,
+    pub clusters: Vec<RoomCluster>,
+    pub rooms: Vec<RoomPrediction>,
+}
+
+/// Cluster the observed rooms, predict their types and score them against the layout
+pub fn infer_room_types(collector: &RoomBehaviorCollector, answer_key: &LocationRegistry) -> RoomInferenceReport {
+    let (room_ids, features): (Vec<RoomId>, Vec<RoomFeatures>) = collector.features().into_iter().unzip();
+    let assignments = cluster_rooms(&features, MAX_CLUSTERS);
+    let cluster_count = assignments.iter().max().map_or(0, |max| max + 1);
+
+    let clusters: Vec<RoomCluster> = (0..cluster_count)
+        .map(|cluster| {
+            let members = || features.iter().zip(&assignments).filter(|(_, a)| **a == cluster).map(|(f, _)| f);
+            let mean_features = RoomFeatures::mean(members());
+            let room_type = mean_features.room_type();
+            RoomCluster {
+                cluster,
+                rooms: members().count(),
+                room_type,
+                security_level: mean_features.security_level(room_type),
+                mean_features,
+            }
+        })
+        .collect();
+
+    let rooms: Vec<RoomPrediction> = room_ids
+        .into_iter()
+        .zip(features)
+        .zip(&assignments)
+        .map(|((room_id, features), cluster)| {
+            let actual = answer_key.get_room(room_id);
+            RoomPrediction {
+                room_id,
+                building_id: collector.rooms.get(&room_id).and_then(|room| room.building_id),
+                cluster: *cluster,
+                predicted_room_type: clusters[*cluster].room_type,
+                predicted_security_level: clusters[*cluster].security_level,
+                actual_room_type: actual.map(|room| room.room_type),
+                actual_security_level: actual.map(|room| room.security_level),
+                features,
+            }
+        })
+        .collect();
+
+    let scored: Vec<&RoomPrediction> = rooms.iter().filter(|room| room.actual_room_type.is_some()).collect();
+    let accuracy = |correct: usize| (!scored.is_empty()).then(|| correct as f64 / scored.len() as f64);
+    let mut room_type_confusion: BTreeMap<String, BTreeMap<String, usize>> = BTreeMap::new();
+    for room in &scored {
+        if let Some(actual) = room.actual_room_type {
+            *room_type_confusion
+                .entry(format!("{:?}", actual))
+                .or_default()
+                .entry(format!("{:?}", room.predicted_room_type))
+                .or_default() += 1;
+        }
+    }
+
+    RoomInferenceReport {
+        rooms_total: answer_key.total_room_count(),
+        rooms_observed: rooms.len(),
+        room_type_accuracy: accuracy(
+            scored.iter().filter(|room| room.actual_room_type == Some(room.predicted_room_type)).count(),
+        ),
+        security_level_accuracy: accuracy(
+            scored.iter().filter(|room| room.actual_security_level == Some(room.predicted_security_level)).count(),
+        ),
+        room_type_confusion,
+        clusters,
+        rooms,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use amzn_career_pathway_activity_rust::facility::{FacilityDefinition, Room};
+    use amzn_career_pathway_activity_rust::types::{EventType, LocationId};
+    use chrono::{Duration, TimeZone};
+
+    const OFFICE: &str = r#"
+locations:
+  - name: Austin
+    latitude: 30.2672
+    longitude: -97.7431
+    time_zone: America/Chicago
+    buildings:
+      - name: Tower
+        rooms:
+          - { name: Lobby, room_type: Lobby, security_level: Public }
+          - { name: Desks, room_type: Workspace, security_level: Standard }
+          - { name: Vault, room_type: Storage, security_level: Restricted }
+"#;
+
+    fn badge(user_id: UserId, room: &Room, location_id: LocationId, timestamp: DateTime<Utc>, success: bool) -> AccessEvent {
+        AccessEvent::new(
+            timestamp,
+            user_id,
+            room.id,
+            room.building_id,
+            location_id,
+            success,
+            if success { EventType::Success } else { EventType::Failure },
+        )
+    }
+
+    fn features(events_per_user: f64, user_share: f64, dwell: f64, night_share: f64, failure_rate: f64) -> RoomFeatures {
+        RoomFeatures {
+            events: 100.0,
+            events_per_user,
+            user_share,
+            median_dwell_minutes: dwell,
+            lunch_share: 0.1,
+            night_share,
+            failure_rate,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_collector_measures_room_behavior() {
+        let registry = FacilityDefinition::from_yaml_str(OFFICE).unwrap().into_registry().unwrap();
+        let location_id = registry.get_all_locations()[0].id;
+        let room = |name: &str| registry.get_all_rooms().into_iter().find(|room| room.name == name).unwrap().clone();
+        let (lobby, desks, vault) = (room("Lobby"), room("Desks"), room("Vault"));
+        let mut collector = RoomBehaviorCollector::new(registry.clone(), WorkCalendar::default());
+
+        // Monday 2024-01-08 at 08:00 in Austin
+        let start = Utc.with_ymd_and_hms(2024, 1, 8, 14, 0, 0).unwrap();
+        let (worker, visitor) = (UserId::new(), UserId::new());
+        collector.observe(&badge(worker, &lobby, location_id, start, true));
+        collector.observe(&badge(visitor, &lobby, location_id, start + Duration::seconds(30), true));
+        collector.observe(&badge(worker, &desks, location_id, start + Duration::minutes(1), true));
+        collector.observe(&badge(visitor, &vault, location_id, start + Duration::minutes(4), false));
+        collector.observe(&badge(worker, &lobby, location_id, start + Duration::hours(3), true));
+
+        let features: HashMap<RoomId, RoomFeatures> = collector.features().into_iter().collect();
+        let (lobby, desks, vault) = (&features[&lobby.id], &features[&desks.id], &features[&vault.id]);
+        assert_eq!(lobby.events, 3.0);
+        assert_eq!(lobby.events_per_user, 1.5);
+        assert_eq!(lobby.user_share, 1.0);
+        assert_eq!(lobby.median_dwell_minutes, 2.0);
+        assert_eq!(lobby.hourly_histogram[8], 2.0 / 3.0);
+        assert_eq!(desks.user_share, 0.5);
+        assert_eq!(desks.median_dwell_minutes, 180.0);
+        assert_eq!(vault.failure_rate, 1.0);
+        assert_eq!(vault.user_share, 0.0);
+        assert_eq!(vault.night_share, 0.0);
+    }
+
+    #[test]
+    fn test_rooms_with_the_same_behavior_share_a_cluster() {
+        let rooms = vec![
+            features(40.0, 0.9, 1.0, 0.05, 0.0),
+            features(11.0, 0.08, 60.0, 0.04, 0.01),
+            features(6.0, 0.0, 0.0, 0.6, 1.0),
+            features(38.0, 0.85, 2.0, 0.04, 0.0),
+            features(10.0, 0.07, 90.0, 0.03, 0.02),
+            features(5.0, 0.0, 0.0, 0.55, 1.0),
+        ];
+        let clusters = cluster_rooms(&rooms, 3);
+        assert_eq!(clusters, vec![0, 1, 2, 0, 1, 2]);
+
+        let types: Vec<RoomType> = (0..3)
+            .map(|cluster| {
+                RoomFeatures::mean(rooms.iter().zip(&clusters).filter(|(_, c)| **c == cluster).map(|(room, _)| room))
+                    .room_type()
+            })
+            .collect();
+        assert_eq!(types, vec![RoomType::Lobby, RoomType::Workspace, RoomType::ServerRoom]);
+    }
+
+    #[test]
+    fn test_security_level_follows_room_type_and_refusals() {
+        let open = features(3.0, 0.6, 90.0, 0.0, 0.01);
+        assert_eq!(open.room_type(), RoomType::MeetingRoom);
+        assert_eq!(open.security_level(RoomType::MeetingRoom), SecurityLevel::Standard);
+        assert_eq!(open.security_level(RoomType::Laboratory), SecurityLevel::HighSecurity);
+
+        let refused = features(3.0, 0.0, 0.0, 0.1, 0.9);
+        assert_eq!(refused.room_type(), RoomType::ExecutiveOffice);
+        assert_eq!(refused.security_level(RoomType::Kitchen), SecurityLevel::Restricted);
+    }
+}
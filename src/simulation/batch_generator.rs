@@ -4,13 +4,21 @@
 //! system with a simple batch-based approach for generating events day by day.
 
 use chrono::NaiveDate;
+use std::collections::BTreeMap;
+use std::sync::Arc;
 use tracing::{debug, info, instrument, warn};
 
 use crate::user::User;
-use crate::events::{AccessEvent, EventGenerator};
+use crate::events::{AccessEvent, EventFormatter, EventGenerator};
 use crate::facility::LocationRegistry;
-use crate::simulation::{BehaviorEngine, SimulationResult, SimulationStatistics, TimeManager};
-use crate::types::SimulationConfig;
+use crate::simulation::{
+    BehaviorEngine, EventSink, SimulationResult, SimulationStatistics, StdoutEventSink, TimeManager,
+    WorkCalendar,
+};
+use crate::types::{OutputFormat, SimulationConfig};
+
+/// Events keyed by the date they belong to, iterated in date order
+type EventsByDate = BTreeMap<NaiveDate, Vec<AccessEvent>>;
 
 /// Batch event generator that processes events day by day sequentially
 /// 
@@ -29,6 +37,8 @@ pub struct BatchEventGenerator {
     users: Vec<User>,
     /// Consolidated statistics tracker (single source of truth)
     statistics: SimulationStatistics,
+    /// Formatter used by the default stdout sink
+    formatter: EventFormatter,
 }
 
 impl BatchEventGenerator {
@@ -54,7 +64,7 @@ impl BatchEventGenerator {
         );
 
         // Create time manager for batch processing
-        let time_manager = TimeManager::new();
+        let time_manager = TimeManager::new().with_calendar(WorkCalendar::new(config.calendar.clone()));
 
         // Create behavior engine for daily schedule generation
         let behavior_engine = BehaviorEngine::new(config.clone(), time_manager.clone());
@@ -74,6 +84,14 @@ impl BatchEventGenerator {
             night_shift_count,
         );
 
+        // Resolve the output format (validated configs always parse)
+        let output_format = config.get_output_format().unwrap_or_else(|e| {
+            warn!("{}, falling back to JSON output", e);
+            OutputFormat::Json
+        });
+        let formatter = EventFormatter::new(output_format, config.output_fields.clone())
+            .with_registry(Arc::new(location_registry.clone()));
+
         // Create event generator (statistics are now handled centrally)
         let event_generator = EventGenerator::new(
             config.clone(),
@@ -87,23 +105,44 @@ impl BatchEventGenerator {
             location_registry,
             users,
             statistics,
+            formatter,
         }
     }
 
-    /// Generate events for the specified number of days sequentially
+    /// Generate events for the specified number of days and write them to stdout
+    /// 
+    /// Events are written in the configured output format. See
+    /// [`generate_events_with_sink`](Self::generate_events_with_sink) to send them elsewhere.
+    /// 
+    /// # Arguments
+    /// * `num_days` - Number of days to simulate (must be > 0)
+    /// 
+    /// # Returns
+    /// Result indicating success or failure of the batch generation process
+    pub fn generate_events_for_days(&mut self, num_days: usize) -> SimulationResult<()> {
+        let mut sink = StdoutEventSink::stdout(self.formatter.clone());
+        self.generate_events_with_sink(num_days, &mut sink)
+    }
+
+    /// Generate events for the specified number of days sequentially into an event sink
     /// 
     /// This method processes each day completely before moving to the next day,
     /// ensuring proper chronological ordering of events within and across day boundaries.
-    /// Events that span across day boundaries are properly carried forward and merged
-    /// with the next day's events to maintain chronological order.
+    /// Events are buffered by UTC date and each date is written once no later local day
+    /// can add to it, so output stays in chronological order across time zones.
     /// 
     /// # Arguments
     /// * `num_days` - Number of days to simulate (must be > 0)
+    /// * `sink` - Destination that receives each day's events in chronological order
     /// 
     /// # Returns
     /// Result indicating success or failure of the batch generation process
-    #[instrument(skip(self), fields(num_days = num_days, user_count = self.users.len()))]
-    pub fn generate_events_for_days(&mut self, num_days: usize) -> SimulationResult<()> {
+    #[instrument(skip(self, sink), fields(num_days = num_days, user_count = self.users.len()))]
+    pub fn generate_events_with_sink<S: EventSink + ?Sized>(
+        &mut self,
+        num_days: usize,
+        sink: &mut S,
+    ) -> SimulationResult<()> {
         if num_days == 0 {
             return Err(crate::simulation::SimulationError::behavior_engine_error(
                 "Number of days must be greater than 0"
@@ -113,10 +152,11 @@ impl BatchEventGenerator {
         info!("Starting batch event generation for {} days with {} users", num_days, self.users.len());
 
         let start_time = std::time::Instant::now();
-        let base_date = chrono::Utc::now().date_naive();
+        let base_date = self.behavior_engine.get_config().simulation_start_date();
         
-        // Track events that span into future days for proper ordering
-        let mut pending_events_by_date: std::collections::HashMap<NaiveDate, Vec<AccessEvent>> = std::collections::HashMap::new();
+        // Events waiting to be written, keyed by UTC date. Schedules follow each location's
+        // local day, so one local day can produce events on the UTC dates either side of it.
+        let mut pending_events_by_date = EventsByDate::new();
 
         // Process each day sequentially
         for day_index in 0..num_days {
@@ -125,65 +165,35 @@ impl BatchEventGenerator {
             debug!("Processing day {} of {} (date: {})", day_index + 1, num_days, current_date);
             
             // Generate events for this specific day
-            let (day_events, future_events) = self.generate_events_for_single_day(current_date)?;
-            
-            // Collect any pending events from previous days that belong to this day
-            let mut all_events_for_day = day_events;
-            if let Some(pending_events) = pending_events_by_date.remove(&current_date) {
-                debug!("Adding {} pending events from previous days to {}", pending_events.len(), current_date);
-                all_events_for_day.extend(pending_events);
-                
-                // Re-sort all events for this day to maintain chronological order
-                all_events_for_day.sort_by_key(|event| event.timestamp);
+            for (event_date, events) in self.generate_events_for_single_day(current_date)? {
+                pending_events_by_date.entry(event_date).or_default().extend(events);
             }
-            
-            // Store future events for processing on their respective days
-            for (future_date, events) in future_events {
-                debug!("Storing {} events for future date {}", events.len(), future_date);
-                pending_events_by_date.entry(future_date).or_default().extend(events);
+
+            // No later local day starts before the current UTC date, so every earlier UTC
+            // date is complete and can be written out in chronological order
+            let completed_dates: Vec<NaiveDate> =
+                pending_events_by_date.range(..current_date).map(|(date, _)| *date).collect();
+            for date in completed_dates {
+                if let Some(events) = pending_events_by_date.remove(&date) {
+                    self.output_pending_events(sink, events, date)?;
+                }
             }
             
-            // Output events for this day (sorted chronologically)
-            self.output_events_for_day(all_events_for_day, current_date)?;
-            
             info!("Completed day {} of {} - generated events for {}", day_index + 1, num_days, current_date);
         }
         
-        // Handle any remaining events that extend beyond the simulation period
+        // Output the remaining UTC dates, including events that extend beyond the simulation period
         if !pending_events_by_date.is_empty() {
             let total_remaining = pending_events_by_date.values().map(|v| v.len()).sum::<usize>();
-            debug!("Simulation ended with {} events extending beyond the {} day period", total_remaining, num_days);
-            
-            // Output these events sorted by date and time
-            let mut all_remaining_events: Vec<AccessEvent> = pending_events_by_date
-                .into_values()
-                .flatten()
-                .collect();
-            all_remaining_events.sort_by_key(|event| event.timestamp);
-            
-            if !all_remaining_events.is_empty() {
-                use crate::events::FilteredAccessEvent;
-                
-                info!("Outputting {} events that extend beyond simulation period", all_remaining_events.len());
-                
-                // Get the output field configuration
-                let field_config = &self.behavior_engine.get_config().output_fields;
-                
-                for event in &all_remaining_events {
-                    // Create filtered event based on configuration
-                    let filtered_event = FilteredAccessEvent::from_access_event(event, field_config);
-                    
-                    match serde_json::to_string(&filtered_event) {
-                        Ok(json_line) => println!("{}", json_line),
-                        Err(e) => warn!("Failed to serialize remaining event to JSON: {}", e),
-                    }
-                }
-                
-                // Update statistics with these remaining events
-                self.update_statistics_with_events(&all_remaining_events);
+            debug!("Outputting {} events for the final {} dates", total_remaining, pending_events_by_date.len());
+
+            for (date, remaining_events) in pending_events_by_date {
+                self.output_pending_events(sink, remaining_events, date)?;
             }
         }
 
+        sink.finish()?;
+
         // Update final statistics
         self.statistics.set_days_simulated(num_days);
         self.statistics.set_simulation_duration(start_time.elapsed());
@@ -202,22 +212,19 @@ impl BatchEventGenerator {
     /// 
     /// This method leverages the existing BehaviorEngine to generate daily schedules
     /// and the EventGenerator to create realistic events from those schedules.
-    /// Events that span across day boundaries are properly separated and returned
-    /// for processing on their respective days.
+    /// Schedules follow each user's local day, so the events are grouped by the UTC
+    /// date they fall on rather than assumed to belong to `date`.
     /// 
     /// # Arguments
-    /// * `date` - The date to generate events for
+    /// * `date` - The local date to generate events for
     /// 
     /// # Returns
-    /// Tuple containing:
-    /// - Vector of access events for the current day, sorted by timestamp
-    /// - HashMap of future events organized by date for proper day boundary handling
+    /// Map of UTC dates to the events falling on them, each sorted by timestamp
     #[instrument(skip(self), fields(date = %date, user_count = self.users.len()))]
-    fn generate_events_for_single_day(&mut self, date: NaiveDate) -> SimulationResult<(Vec<AccessEvent>, std::collections::HashMap<NaiveDate, Vec<AccessEvent>>)> {
+    fn generate_events_for_single_day(&mut self, date: NaiveDate) -> SimulationResult<EventsByDate> {
         debug!("Generating events for date: {}", date);
 
-        let mut all_day_events = Vec::new();
-        let mut future_events_by_date: std::collections::HashMap<NaiveDate, Vec<AccessEvent>> = std::collections::HashMap::new();
+        let mut events_by_date = EventsByDate::new();
 
         // Process each user for this day
         for user in &self.users {
@@ -243,108 +250,63 @@ impl BatchEventGenerator {
                     activity.start_time,
                 )?;
 
-                // Separate events by day boundary for proper chronological ordering
                 for event in activity_events {
-                    let event_date = event.timestamp.date_naive();
-                    
-                    if event_date == date {
-                        // Event belongs to current day
-                        all_day_events.push(event);
-                    } else if event_date > date {
-                        // Event spans into future day - store for proper day boundary handling
-                        debug!(
-                            "Event for user {} at {} spans into future day ({}), storing for later processing",
-                            user.id, event.timestamp, event_date
-                        );
-                        future_events_by_date.entry(event_date).or_default().push(event);
-                    } else {
-                        // Event from previous day - this shouldn't happen in normal operation
-                        warn!(
-                            "Event for user {} at {} belongs to previous day ({}), this may indicate a scheduling issue",
-                            user.id, event.timestamp, event_date
-                        );
-                        // Still include it in current day to avoid losing events
-                        all_day_events.push(event);
-                    }
+                    events_by_date.entry(event.timestamp.date_naive()).or_default().push(event);
                 }
             }
         }
 
-        // Log summary of events that span into future days
-        if !future_events_by_date.is_empty() {
-            let total_future_events: usize = future_events_by_date.values().map(|v| v.len()).sum();
-            debug!(
-                "Generated {} events from {} that span into {} future days",
-                total_future_events,
-                date,
-                future_events_by_date.len()
-            );
-            
-            // Log details for each future date
-            for (future_date, events) in &future_events_by_date {
-                debug!(
-                    "  {} events for future date {}",
-                    events.len(),
-                    future_date
-                );
-            }
-        }
-
-        // Sort all events for this day by timestamp to ensure chronological order
-        all_day_events.sort_by_key(|event| event.timestamp);
-
-        // Sort future events by timestamp within each date for consistency
-        for events in future_events_by_date.values_mut() {
+        // Sort events by timestamp within each date to ensure chronological order
+        for events in events_by_date.values_mut() {
             events.sort_by_key(|event| event.timestamp);
         }
 
-        // Update statistics with the events generated for this day only
-        // Future events will be counted when they are processed on their respective days
-        self.update_statistics_with_events(&all_day_events);
-
         info!(
-            "Generated {} events for {} (sorted chronologically), {} events span into future days",
-            all_day_events.len(),
+            "Generated {} events for {} across {} UTC dates",
+            events_by_date.values().map(|v| v.len()).sum::<usize>(),
             date,
-            future_events_by_date.values().map(|v| v.len()).sum::<usize>()
+            events_by_date.len()
         );
 
-        Ok((all_day_events, future_events_by_date))
+        Ok(events_by_date)
+    }
+
+    /// Sort a completed UTC date's events, write them to the sink and count them in the statistics
+    fn output_pending_events<S: EventSink + ?Sized>(
+        &mut self,
+        sink: &mut S,
+        mut events: Vec<AccessEvent>,
+        date: NaiveDate,
+    ) -> SimulationResult<()> {
+        events.sort_by_key(|event| event.timestamp);
+        self.output_events_for_day(sink, &events, date)?;
+        self.update_statistics_with_events(&events);
+        Ok(())
     }
 
-    /// Output events for a day to stdout in the configured format
+    /// Output events for a day to the event sink
     /// 
     /// # Arguments
-    /// * `events` - Vector of events to output (should be sorted by timestamp)
-    /// * `date` - The date these events are for (used for logging)
+    /// * `sink` - Destination for the events
+    /// * `events` - Events to output (should be sorted by timestamp)
+    /// * `date` - The date these events are for
     /// 
     /// # Returns
     /// Result indicating success or failure of the output operation
-    #[instrument(skip(self, events), fields(event_count = events.len(), date = %date))]
-    fn output_events_for_day(&self, events: Vec<AccessEvent>, date: NaiveDate) -> SimulationResult<()> {
-        use crate::events::FilteredAccessEvent;
-        
+    #[instrument(skip(self, sink, events), fields(event_count = events.len(), date = %date))]
+    fn output_events_for_day<S: EventSink + ?Sized>(
+        &self,
+        sink: &mut S,
+        events: &[AccessEvent],
+        date: NaiveDate,
+    ) -> SimulationResult<()> {
         debug!("Outputting {} events for {}", events.len(), date);
 
-        // Get the output field configuration from behavior engine's config
-        let field_config = &self.behavior_engine.get_config().output_fields;
-
-        // Output each event in the configured format
-        for event in &events {
-            // Create filtered event based on configuration
-            let filtered_event = FilteredAccessEvent::from_access_event(event, field_config);
-            
-            // Convert filtered event to JSON and output to stdout
-            match serde_json::to_string(&filtered_event) {
-                Ok(json_line) => {
-                    println!("{}", json_line);
-                }
-                Err(e) => {
-                    warn!("Failed to serialize event to JSON: {}", e);
-                    // Continue with other events rather than failing completely
-                }
-            }
+        sink.begin_day(date)?;
+        for event in events {
+            sink.write_event(event)?;
         }
+        sink.end_day(date)?;
 
         debug!("Successfully output {} events for {}", events.len(), date);
         Ok(())
@@ -3,8 +3,11 @@
 //! This module contains the BehaviorEngine for generating user activities.
 
 use crate::user::{BehaviorProfile, User, ScheduledActivity};
-use crate::facility::LocationRegistry;
-use crate::simulation::{ErrorHandler, SimulationError, SimulationResult, TimeManager};
+use crate::facility::{Location, LocationRegistry};
+use crate::simulation::{
+    DayKind, ErrorHandler, RngStream, SimulationError, SimulationResult, TimeManager, WorkCalendar,
+    HALF_DAY_END_HOUR,
+};
 use crate::types::{ActivityType, RoomType, SecurityLevel, SimulationConfig};
 use crate::types::{BuildingId, UserId, LocationId, RoomId};
 use chrono::{DateTime, Duration, NaiveDate, Timelike, Utc};
@@ -53,7 +56,7 @@ pub struct BehaviorEngine {
     /// Configuration parameters for the simulation
     config: SimulationConfig,
     /// Random number generator for behavioral decisions
-    rng: rand::rngs::ThreadRng,
+    rng: rand::rngs::StdRng,
     /// Time manager for realistic timing calculations
     #[allow(dead_code)]
     time_manager: TimeManager,
@@ -64,6 +67,8 @@ pub struct BehaviorEngine {
     travel_time_constants: TravelTimeConstants,
     /// Track user daily locations for cross-location travel persistence
     user_daily_locations: HashMap<UserId, LocationId>,
+    /// Work calendar deciding when regular users come in
+    calendar: WorkCalendar,
 }
 
 impl BehaviorEngine {
@@ -71,8 +76,9 @@ impl BehaviorEngine {
     pub fn new(config: SimulationConfig, time_manager: TimeManager) -> Self {
         info!("Initializing behavior engine with {} users", config.user_count);
         Self {
+            rng: RngStream::Behavior.rng(config.seed),
+            calendar: WorkCalendar::new(config.calendar.clone()),
             config,
-            rng: rand::thread_rng(),
             time_manager,
             error_handler: ErrorHandler::new(),
             travel_time_constants: TravelTimeConstants::default(),
@@ -90,6 +96,8 @@ impl BehaviorEngine {
     /// Creates a schedule with arrival, meetings, bathroom breaks, lunch, and departure
     /// based on realistic timing patterns and the user's behavior profile.
     /// For night-shift users, generates an inverted schedule with patrol activities.
+    /// On weekends and holidays at the user's location, regular users usually stay home
+    /// and otherwise make a short visit; on half-days they leave around midday.
     ///
     /// # Arguments
     /// * `user` - The user to generate a schedule for
@@ -113,7 +121,21 @@ impl BehaviorEngine {
         // Check if this is a night-shift user and handle accordingly
         if user.is_night_shift {
             debug!("Generating night-shift schedule for user {}", user.id);
-            return self.generate_night_shift_schedule(user, date, registry);
+            let night_building = user.assigned_night_building.unwrap_or(user.primary_building);
+            let schedule = self.generate_night_shift_schedule(user, date, registry)?;
+            return Ok(Self::localize_schedule(schedule, registry.get_location_for_building(night_building)));
+        }
+
+        let location = registry.get_location(user.primary_location);
+        let day_kind = self.calendar.day_kind(date, location);
+        if !day_kind.is_work_day() {
+            let schedule = if self.rng.gen::<f64>() < self.calendar.non_work_day_attendance() {
+                debug!("User {} comes in on non-work day {} ({:?})", user.id, date, day_kind);
+                self.generate_non_work_day_schedule(user, date)?
+            } else {
+                Vec::new()
+            };
+            return Ok(Self::localize_schedule(schedule, location));
         }
 
         // Generate schedule with error handling
@@ -141,7 +163,86 @@ impl BehaviorEngine {
             self.generate_minimal_schedule(user, date, registry)?
         };
 
-        Ok(schedule)
+        let schedule = if day_kind == DayKind::HalfDay {
+            self.shorten_for_half_day(schedule, date)
+        } else {
+            schedule
+        };
+
+        Ok(Self::localize_schedule(schedule, location))
+    }
+
+    /// Generate a short visit to the user's workspace on a weekend or holiday
+    fn generate_non_work_day_schedule(
+        &mut self,
+        user: &User,
+        date: NaiveDate,
+    ) -> SimulationResult<Vec<ScheduledActivity>> {
+        let arrival_time = date
+            .and_hms_opt(self.rng.gen_range(9..=12), self.rng.gen_range(0..60), 0)
+            .ok_or_else(|| SimulationError::behavior_engine_error("Invalid arrival time"))?
+            .and_utc();
+        let departure_time = arrival_time + Duration::minutes(self.rng.gen_range(60..=240));
+
+        Ok(vec![
+            ScheduledActivity::new(ActivityType::Arrival, user.primary_workspace, arrival_time, Duration::minutes(15)),
+            ScheduledActivity::new(
+                ActivityType::Departure,
+                user.primary_workspace,
+                departure_time,
+                Duration::minutes(10),
+            ),
+        ])
+    }
+
+    /// Cut a work-day schedule short for a half-day, moving departure to around midday
+    ///
+    /// Users who would only arrive after leaving skip the day, so the schedule is empty.
+    fn shorten_for_half_day(
+        &mut self,
+        mut schedule: Vec<ScheduledActivity>,
+        date: NaiveDate,
+    ) -> Vec<ScheduledActivity> {
+        let Some(closing_time) = date.and_hms_opt(HALF_DAY_END_HOUR, 0, 0) else {
+            return schedule;
+        };
+        // Most people leave in the hour before closing, a few stay a little longer
+        let departure_time = closing_time.and_utc() + Duration::minutes(self.rng.gen_range(-60..=30));
+        let arrives_too_late = schedule.iter().any(|activity| {
+            activity.activity_type == ActivityType::Arrival && activity.start_time >= departure_time
+        });
+        if arrives_too_late {
+            return Vec::new();
+        }
+
+        schedule.retain(|activity| {
+            activity.activity_type == ActivityType::Departure || activity.start_time < departure_time
+        });
+        for activity in &mut schedule {
+            if activity.activity_type == ActivityType::Departure {
+                activity.start_time = departure_time;
+            }
+        }
+        schedule.sort_by_key(|a| a.start_time);
+        schedule
+    }
+
+    /// Convert a schedule built on local wall-clock times into UTC
+    ///
+    /// Schedules are generated as if the local time were UTC, so each start time is
+    /// reinterpreted in the time zone of the location where the user works. Locations
+    /// missing from the registry leave the schedule unchanged.
+    fn localize_schedule(
+        mut schedule: Vec<ScheduledActivity>,
+        location: Option<&Location>,
+    ) -> Vec<ScheduledActivity> {
+        if let Some(location) = location {
+            for activity in &mut schedule {
+                activity.start_time = location.local_to_utc(activity.start_time.naive_utc());
+            }
+            schedule.sort_by_key(|a| a.start_time);
+        }
+        schedule
     }
 
     /// Internal schedule generation with error handling
@@ -256,7 +357,7 @@ impl BehaviorEngine {
 
         // Sort schedule by start time and remove any overlapping activities
         schedule.sort_by_key(|a| a.start_time);
-        Ok(self.resolve_schedule_conflicts(schedule, user, registry)?)
+        self.resolve_schedule_conflicts(schedule, user, registry)
     }
 
     /// Generate a minimal schedule when full schedule generation fails
@@ -399,7 +500,7 @@ impl BehaviorEngine {
         // Get the location of the current activity
         let activity_location =
             self.get_room_location(activity.target_room, registry).ok_or_else(|| {
-                SimulationError::behavior_engine_error(&format!(
+                SimulationError::behavior_engine_error(format!(
                     "Cannot determine location for room {}",
                     activity.target_room
                 ))
@@ -409,7 +510,7 @@ impl BehaviorEngine {
         if let Some(last_activity) = current_schedule.last() {
             let last_location =
                 self.get_room_location(last_activity.target_room, registry).ok_or_else(|| {
-                    SimulationError::behavior_engine_error(&format!(
+                    SimulationError::behavior_engine_error(format!(
                         "Cannot determine location for room {}",
                         last_activity.target_room
                     ))
@@ -560,27 +661,27 @@ impl BehaviorEngine {
         match activity_type {
             ActivityType::Meeting => {
                 // Meetings should be during business hours (9 AM - 5 PM)
-                hour >= 9 && hour < 17
+                (9..17).contains(&hour)
             }
             ActivityType::Lunch => {
                 // Lunch should be between 11 AM - 3 PM
-                hour >= 11 && hour < 15
+                (11..15).contains(&hour)
             }
             ActivityType::Bathroom => {
                 // Bathroom breaks can happen anytime during work hours
-                hour >= 7 && hour < 19
+                (7..19).contains(&hour)
             }
             ActivityType::Collaboration => {
                 // Collaboration should be during business hours
-                hour >= 9 && hour < 17
+                (9..17).contains(&hour)
             }
             ActivityType::Departure => {
                 // Departure should be in the evening (4 PM - 8 PM)
-                hour >= 16 && hour < 20
+                (16..20).contains(&hour)
             }
             _ => {
                 // Other activities are more flexible
-                hour >= 7 && hour < 19
+                (7..19).contains(&hour)
             }
         }
     }
@@ -2986,8 +3087,8 @@ mod tests {
         engine.clear_daily_location_tracking();
 
         assert_eq!(engine.user_daily_locations.len(), 0);
-        assert!(engine.user_daily_locations.get(&user1_id).is_none());
-        assert!(engine.user_daily_locations.get(&user2_id).is_none());
+        assert!(!engine.user_daily_locations.contains_key(&user1_id));
+        assert!(!engine.user_daily_locations.contains_key(&user2_id));
     }
 
     #[test]
@@ -3215,7 +3316,7 @@ mod tests {
 
         // Create schedule with recent activity in building1
         let base_time = Utc::now();
-        let schedule = vec![ScheduledActivity::new(
+        let schedule = [ScheduledActivity::new(
             ActivityType::Meeting,
             room1_id, // Activity in building1
             base_time,
@@ -3408,4 +3509,68 @@ mod tests {
         assert!(result.is_some());
 
     }
+    #[test]
+    fn test_localize_schedule_uses_location_time_zone() {
+        let location = Location::new("Tokyo".to_string(), (35.6762, 139.6503))
+            .with_time_zone(chrono_tz::Asia::Tokyo);
+        let date = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+        let schedule = vec![
+            ScheduledActivity::new(
+                ActivityType::Departure,
+                RoomId::new(),
+                date.and_hms_opt(17, 30, 0).unwrap().and_utc(),
+                Duration::minutes(10),
+            ),
+            ScheduledActivity::new(
+                ActivityType::Arrival,
+                RoomId::new(),
+                date.and_hms_opt(9, 0, 0).unwrap().and_utc(),
+                Duration::minutes(15),
+            ),
+        ];
+
+        let localized = BehaviorEngine::localize_schedule(schedule.clone(), Some(&location));
+        assert_eq!(localized[0].activity_type, ActivityType::Arrival);
+        assert_eq!(localized[0].start_time, date.and_hms_opt(0, 0, 0).unwrap().and_utc());
+        assert_eq!(localized[1].start_time, date.and_hms_opt(8, 30, 0).unwrap().and_utc());
+
+        // Unknown locations keep the schedule as generated
+        let unchanged = BehaviorEngine::localize_schedule(schedule.clone(), None);
+        assert_eq!(unchanged[0].start_time, schedule[0].start_time);
+    }
+    #[test]
+    fn test_half_day_keeps_arrival_or_skips_the_day() {
+        let mut engine = BehaviorEngine::new(SimulationConfig { seed: Some(3), ..Default::default() }, TimeManager::new());
+        let date = NaiveDate::from_ymd_opt(2024, 12, 24).unwrap();
+        let schedule = |arrival_hour: u32| {
+            vec![
+                ScheduledActivity::new(
+                    ActivityType::Arrival,
+                    RoomId::new(),
+                    date.and_hms_opt(arrival_hour, 0, 0).unwrap().and_utc(),
+                    Duration::minutes(15),
+                ),
+                ScheduledActivity::new(
+                    ActivityType::Meeting,
+                    RoomId::new(),
+                    date.and_hms_opt(15, 0, 0).unwrap().and_utc(),
+                    Duration::minutes(60),
+                ),
+                ScheduledActivity::new(
+                    ActivityType::Departure,
+                    RoomId::new(),
+                    date.and_hms_opt(17, 30, 0).unwrap().and_utc(),
+                    Duration::minutes(10),
+                ),
+            ]
+        };
+
+        let shortened = engine.shorten_for_half_day(schedule(9), date);
+        let activity_types: Vec<_> = shortened.iter().map(|activity| activity.activity_type).collect();
+        assert_eq!(activity_types, [ActivityType::Arrival, ActivityType::Departure]);
+        assert!(shortened[1].start_time.hour() < HALF_DAY_END_HOUR + 1);
+
+        // Arriving after everyone has left means not coming in at all
+        assert!(engine.shorten_for_half_day(schedule(14), date).is_empty());
+    }
 }
@@ -0,0 +1,142 @@
+//! Work calendar
+//!
+//! This module decides what kind of day a date is at a location: a regular work day, a
+//! half-day that ends at midday, a weekend day or a public holiday. Weekend days and the
+//! global holiday and half-day lists come from the simulation configuration, and each
+//! location can add its own holidays and half-days on top.
+
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+use std::ops::Range;
+
+use crate::facility::Location;
+use crate::types::WorkCalendarConfig;
+
+/// Local hour at which business hours start
+pub const BUSINESS_DAY_START_HOUR: u32 = 9;
+
+/// Local hour at which business hours end on a regular work day
+pub const BUSINESS_DAY_END_HOUR: u32 = 17;
+
+/// Local hour at which business hours end on a half-day
+pub const HALF_DAY_END_HOUR: u32 = 13;
+
+/// Kind of day in the work calendar
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum DayKind {
+    /// Regular work day
+    WorkDay,
+    /// Work day that ends at midday
+    HalfDay,
+    /// Weekend day
+    Weekend,
+    /// Public holiday
+    Holiday,
+}
+
+impl DayKind {
+    /// Check if users are expected at the office on this kind of day
+    pub fn is_work_day(self) -> bool {
+        matches!(self, DayKind::WorkDay | DayKind::HalfDay)
+    }
+
+    /// Local business hours on this kind of day, or None when the office is closed
+    pub fn business_hours(self) -> Option<Range<u32>> {
+        match self {
+            DayKind::WorkDay => Some(BUSINESS_DAY_START_HOUR..BUSINESS_DAY_END_HOUR),
+            DayKind::HalfDay => Some(BUSINESS_DAY_START_HOUR..HALF_DAY_END_HOUR),
+            DayKind::Weekend | DayKind::Holiday => None,
+        }
+    }
+}
+
+/// Work calendar combining the configured weekends, holidays and half-days with each
+/// location's own lists
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WorkCalendar {
+    config: WorkCalendarConfig,
+}
+
+impl WorkCalendar {
+    /// Create a work calendar from its configuration
+    pub fn new(config: WorkCalendarConfig) -> Self {
+        Self { config }
+    }
+
+    /// Get the calendar configuration
+    pub fn config(&self) -> &WorkCalendarConfig {
+        &self.config
+    }
+
+    /// Probability that a regular user comes in on a weekend or holiday
+    pub fn non_work_day_attendance(&self) -> f64 {
+        self.config.non_work_day_attendance
+    }
+
+    /// Classify a local date, taking the location's own holidays and half-days into account
+    ///
+    /// Holidays take precedence over weekends, and half-days falling on a weekend stay
+    /// weekend days.
+    pub fn day_kind(&self, date: NaiveDate, location: Option<&Location>) -> DayKind {
+        let listed = |global: &[NaiveDate], local: Option<&[NaiveDate]>| {
+            global.contains(&date) || local.is_some_and(|dates| dates.contains(&date))
+        };
+
+        if listed(&self.config.holidays, location.map(|l| l.holidays.as_slice())) {
+            DayKind::Holiday
+        } else if self.config.weekend_days.contains(&date.weekday()) {
+            DayKind::Weekend
+        } else if listed(&self.config.half_days, location.map(|l| l.half_days.as_slice())) {
+            DayKind::HalfDay
+        } else {
+            DayKind::WorkDay
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Weekday;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2024, 12, day).unwrap()
+    }
+
+    #[test]
+    fn test_default_calendar_has_weekends() {
+        let calendar = WorkCalendar::default();
+        assert_eq!(calendar.day_kind(date(20), None), DayKind::WorkDay); // Friday
+        assert_eq!(calendar.day_kind(date(21), None), DayKind::Weekend);
+        assert_eq!(calendar.day_kind(date(22), None), DayKind::Weekend);
+        assert_eq!(calendar.non_work_day_attendance(), 0.02);
+    }
+
+    #[test]
+    fn test_global_and_location_holidays() {
+        let calendar = WorkCalendar::new(WorkCalendarConfig {
+            weekend_days: vec![Weekday::Fri, Weekday::Sat],
+            holidays: vec![date(25)],
+            half_days: vec![date(24)],
+            ..Default::default()
+        });
+        let location = Location::new("Dublin".to_string(), (53.35, -6.26)).with_holidays(vec![date(26)], vec![date(31)]);
+
+        assert_eq!(calendar.day_kind(date(22), None), DayKind::WorkDay); // Sunday
+        assert_eq!(calendar.day_kind(date(27), None), DayKind::Weekend); // Friday
+        assert_eq!(calendar.day_kind(date(24), None), DayKind::HalfDay);
+        assert_eq!(calendar.day_kind(date(25), Some(&location)), DayKind::Holiday);
+        assert_eq!(calendar.day_kind(date(26), None), DayKind::WorkDay);
+        assert_eq!(calendar.day_kind(date(26), Some(&location)), DayKind::Holiday);
+        assert_eq!(calendar.day_kind(date(31), Some(&location)), DayKind::HalfDay);
+    }
+
+    #[test]
+    fn test_business_hours_by_day_kind() {
+        assert_eq!(DayKind::WorkDay.business_hours(), Some(9..17));
+        assert_eq!(DayKind::HalfDay.business_hours(), Some(9..13));
+        assert_eq!(DayKind::Weekend.business_hours(), None);
+        assert!(DayKind::HalfDay.is_work_day());
+        assert!(!DayKind::Holiday.is_work_day());
+    }
+}
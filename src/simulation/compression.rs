@@ -0,0 +1,198 @@
+//! Compressed output and input streams
+//!
+//! This module wraps writers and readers with optional gzip or Zstandard compression so
+//! event output and the user profile answer key can be compressed as they are written,
+//! and read back transparently by file extension.
+
+use flate2::read::MultiGzDecoder;
+use flate2::write::GzEncoder;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+
+use crate::types::Compression;
+
+/// Zstandard compression level used for output (the library default)
+const ZSTD_LEVEL: i32 = 3;
+
+/// Underlying encoder for a compressed writer
+enum Encoder<W: Write> {
+    Plain(W),
+    Gzip(GzEncoder<W>),
+    Zstd(zstd::Encoder<'static, W>),
+}
+
+/// Writer that compresses everything written to it
+///
+/// Compressed streams must be finalized with [`try_finish`](Self::try_finish) or
+/// [`finish`](Self::finish) once all data has been written; flushing alone does not
+/// write the stream trailer.
+pub struct CompressedWriter<W: Write> {
+    encoder: Encoder<W>,
+    compression: Compression,
+}
+
+impl<W: Write> CompressedWriter<W> {
+    /// Wrap a writer with the given compression
+    pub fn new(writer: W, compression: Compression) -> io::Result<Self> {
+        let encoder = match compression {
+            Compression::None => Encoder::Plain(writer),
+            Compression::Gzip => Encoder::Gzip(GzEncoder::new(writer, flate2::Compression::default())),
+            Compression::Zstd => Encoder::Zstd(zstd::Encoder::new(writer, ZSTD_LEVEL)?),
+        };
+        Ok(Self { encoder, compression })
+    }
+
+    /// Get the compression applied by this writer
+    pub fn compression(&self) -> Compression {
+        self.compression
+    }
+
+    /// Get a reference to the underlying writer
+    pub fn get_ref(&self) -> &W {
+        match &self.encoder {
+            Encoder::Plain(writer) => writer,
+            Encoder::Gzip(encoder) => encoder.get_ref(),
+            Encoder::Zstd(encoder) => encoder.get_ref(),
+        }
+    }
+
+    /// Get a mutable reference to the underlying writer
+    pub fn get_mut(&mut self) -> &mut W {
+        match &mut self.encoder {
+            Encoder::Plain(writer) => writer,
+            Encoder::Gzip(encoder) => encoder.get_mut(),
+            Encoder::Zstd(encoder) => encoder.get_mut(),
+        }
+    }
+
+    /// Write the stream trailer and flush the underlying writer
+    ///
+    /// No further data may be written to a compressed stream afterwards.
+    pub fn try_finish(&mut self) -> io::Result<()> {
+        match &mut self.encoder {
+            Encoder::Plain(_) => {}
+            Encoder::Gzip(encoder) => encoder.try_finish()?,
+            Encoder::Zstd(encoder) => encoder.do_finish()?,
+        }
+        self.get_mut().flush()
+    }
+
+    /// Finish the stream and return the underlying writer
+    pub fn finish(mut self) -> io::Result<W> {
+        self.try_finish()?;
+        Ok(match self.encoder {
+            Encoder::Plain(writer) => writer,
+            Encoder::Gzip(encoder) => encoder.finish()?,
+            Encoder::Zstd(encoder) => encoder.finish()?,
+        })
+    }
+}
+
+impl<W: Write> Write for CompressedWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        match &mut self.encoder {
+            Encoder::Plain(writer) => writer.write(buf),
+            Encoder::Gzip(encoder) => encoder.write(buf),
+            Encoder::Zstd(encoder) => encoder.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match &mut self.encoder {
+            Encoder::Plain(writer) => writer.flush(),
+            Encoder::Gzip(encoder) => encoder.flush(),
+            Encoder::Zstd(encoder) => encoder.flush(),
+        }
+    }
+}
+
+impl<W: Write> fmt::Debug for CompressedWriter<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CompressedWriter")
+            .field("compression", &self.compression)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Create (or truncate) an output file with the given compression
+pub fn create_output_file<P: AsRef<Path>>(
+    path: P,
+    compression: Compression,
+) -> io::Result<CompressedWriter<BufWriter<File>>> {
+    let file = File::create(path)?;
+    CompressedWriter::new(BufWriter::new(file), compression)
+}
+
+/// Open a file for buffered reading, decompressing it according to its extension
+pub fn open_input_file<P: AsRef<Path>>(path: P) -> io::Result<Box<dyn BufRead>> {
+    let path = path.as_ref();
+    let file = File::open(path)?;
+
+    Ok(match Compression::from_path(path) {
+        Compression::None => Box::new(BufReader::new(file)),
+        Compression::Gzip => Box::new(BufReader::new(MultiGzDecoder::new(file))),
+        Compression::Zstd => Box::new(BufReader::new(zstd::Decoder::new(file)?)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+
+    const SAMPLE: &str = "{\"user_id\":\"USER_1\"}\n{\"user_id\":\"USER_2\"}\n";
+
+    fn round_trip(compression: Compression, extension: &str) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(format!("sample.jsonl{}", extension));
+
+        let mut writer = create_output_file(&path, compression).unwrap();
+        writer.write_all(SAMPLE.as_bytes()).unwrap();
+        writer.finish().unwrap();
+
+        let mut contents = String::new();
+        open_input_file(&path).unwrap().read_to_string(&mut contents).unwrap();
+        assert_eq!(contents, SAMPLE);
+    }
+
+    #[test]
+    fn test_plain_round_trip() {
+        round_trip(Compression::None, "");
+    }
+
+    #[test]
+    fn test_gzip_round_trip() {
+        round_trip(Compression::Gzip, ".gz");
+    }
+
+    #[test]
+    fn test_zstd_round_trip() {
+        round_trip(Compression::Zstd, ".zst");
+    }
+
+    #[test]
+    fn test_compressed_output_is_not_plain_text() {
+        for (compression, magic) in [
+            (Compression::Gzip, vec![0x1f, 0x8b]),
+            (Compression::Zstd, vec![0x28, 0xb5, 0x2f, 0xfd]),
+        ] {
+            let mut writer = CompressedWriter::new(Vec::new(), compression).unwrap();
+            writer.write_all(SAMPLE.as_bytes()).unwrap();
+            let bytes = writer.finish().unwrap();
+            assert!(bytes.starts_with(&magic), "{} output should start with its magic bytes", compression);
+        }
+    }
+
+    #[test]
+    fn test_try_finish_flushes_trailer() {
+        let mut writer = CompressedWriter::new(Vec::new(), Compression::Gzip).unwrap();
+        writer.write_all(SAMPLE.as_bytes()).unwrap();
+        writer.try_finish().unwrap();
+
+        let mut contents = String::new();
+        MultiGzDecoder::new(writer.get_ref().as_slice()).read_to_string(&mut contents).unwrap();
+        assert_eq!(contents, SAMPLE);
+    }
+}
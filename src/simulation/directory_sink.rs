@@ -0,0 +1,507 @@
+//! Directory event output with per-day partitioning
+//!
+//! This module contains the `DirectoryEventSink`, which writes one file per simulated day
+//! (for example `events/2024-01-01.jsonl`) and optionally rolls over to additional part
+//! files when a size or event-count threshold is reached. Files can optionally be gzip or
+//! Zstandard compressed. When the sink finishes it writes a `manifest.json` describing each
+//! file's date range, event count, size and checksum.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use tracing::{debug, info};
+
+use crate::events::{AccessEvent, EventFormatter};
+use crate::simulation::{CompressedWriter, EventSink, SimulationError, SimulationResult};
+use crate::types::{Compression, OutputFormat};
+
+/// File name of the manifest written into the output directory
+pub const MANIFEST_FILE_NAME: &str = "manifest.json";
+
+/// Thresholds at which the current day's file is rolled over to a new part file
+///
+/// Sizes are measured on the uncompressed content.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RotationPolicy {
+    /// Maximum size of a single file in bytes
+    pub max_bytes: Option<u64>,
+    /// Maximum number of events in a single file
+    pub max_events: Option<usize>,
+}
+
+impl RotationPolicy {
+    /// Create a policy that never rotates (one file per day)
+    pub fn none() -> Self {
+        Self::default()
+    }
+
+    /// Check whether a file with the given size and event count must be rolled over
+    /// before `next_line_bytes` more bytes are written to it
+    ///
+    /// Files that contain no events are never rotated, so a single oversized event
+    /// still gets written.
+    pub fn should_rotate(&self, bytes: u64, events: usize, next_line_bytes: u64) -> bool {
+        if events == 0 {
+            return false;
+        }
+        let over_bytes = self.max_bytes.is_some_and(|max| bytes + next_line_bytes > max);
+        let over_events = self.max_events.is_some_and(|max| events >= max);
+        over_bytes || over_events
+    }
+}
+
+/// Manifest entry describing a single output file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ManifestFile {
+    /// File name relative to the output directory
+    pub path: String,
+    /// Simulated day the file belongs to
+    pub date: NaiveDate,
+    /// Part number within the day (starting at 1)
+    pub part: usize,
+    /// Timestamp of the first event in the file
+    pub first_timestamp: Option<DateTime<Utc>>,
+    /// Timestamp of the last event in the file
+    pub last_timestamp: Option<DateTime<Utc>>,
+    /// Number of events in the file
+    pub event_count: usize,
+    /// File size on disk in bytes
+    pub bytes: u64,
+    /// Hex-encoded SHA-256 checksum of the file contents as stored on disk
+    pub sha256: String,
+}
+
+/// Manifest describing all files written by a `DirectoryEventSink`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OutputManifest {
+    /// Format of the event files
+    pub output_format: OutputFormat,
+    /// Compression applied to the event files
+    #[serde(default)]
+    pub compression: Compression,
+    /// Total number of events across all files
+    pub total_events: usize,
+    /// Files in the order they were written
+    pub files: Vec<ManifestFile>,
+}
+
+impl OutputManifest {
+    /// Load a manifest from a JSON file
+    pub fn load<P: AsRef<Path>>(path: P) -> SimulationResult<Self> {
+        let content = fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&content)?)
+    }
+}
+
+/// Writer that tracks the size and checksum of everything written through it
+struct HashingWriter<W: Write> {
+    inner: W,
+    hasher: Sha256,
+    bytes: u64,
+}
+
+impl<W: Write> Write for HashingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.hasher.update(&buf[..written]);
+        self.bytes += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// File currently being written, with its running checksum
+struct OpenFile {
+    writer: CompressedWriter<HashingWriter<BufWriter<File>>>,
+    uncompressed_bytes: u64,
+    entry: ManifestFile,
+}
+
+impl OpenFile {
+    /// Write a line (without trailing newline), updating the uncompressed size
+    fn write_line(&mut self, line: &str) -> SimulationResult<()> {
+        self.writer.write_all(line.as_bytes())?;
+        self.writer.write_all(b"\n")?;
+        self.uncompressed_bytes += line.len() as u64 + 1;
+        Ok(())
+    }
+
+    /// Finish and flush the file and return its completed manifest entry
+    fn close(self) -> SimulationResult<ManifestFile> {
+        let hashing_writer = self.writer.finish()?;
+        let mut entry = self.entry;
+        entry.bytes = hashing_writer.bytes;
+        entry.sha256 = hashing_writer.hasher.finalize().iter().map(|b| format!("{:02x}", b)).collect();
+        Ok(entry)
+    }
+}
+
+impl fmt::Debug for OpenFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OpenFile").field("entry", &self.entry).finish_non_exhaustive()
+    }
+}
+
+/// Event sink that writes one file per simulated day into a directory
+#[derive(Debug)]
+pub struct DirectoryEventSink {
+    directory: PathBuf,
+    formatter: EventFormatter,
+    rotation: RotationPolicy,
+    current: Option<OpenFile>,
+    manifest: OutputManifest,
+}
+
+impl DirectoryEventSink {
+    /// Create the output directory (if needed) and return a sink that writes into it
+    pub fn create<P: AsRef<Path>>(
+        directory: P,
+        formatter: EventFormatter,
+        rotation: RotationPolicy,
+    ) -> SimulationResult<Self> {
+        let directory = directory.as_ref().to_path_buf();
+        fs::create_dir_all(&directory).map_err(|e| {
+            SimulationError::event_generation_error(format!(
+                "Failed to create output directory {}: {}",
+                directory.display(),
+                e
+            ))
+        })?;
+
+        let manifest = OutputManifest {
+            output_format: formatter.output_format(),
+            compression: Compression::None,
+            total_events: 0,
+            files: Vec::new(),
+        };
+
+        Ok(Self { directory, formatter, rotation, current: None, manifest })
+    }
+
+    /// Compress every output file with the given compression
+    ///
+    /// Must be called before any events are written.
+    pub fn with_compression(mut self, compression: Compression) -> Self {
+        self.manifest.compression = compression;
+        self
+    }
+
+    /// Get the output directory
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    /// Get the path of the manifest file
+    pub fn manifest_path(&self) -> PathBuf {
+        self.directory.join(MANIFEST_FILE_NAME)
+    }
+
+    /// Get the manifest entries for all files closed so far
+    pub fn manifest(&self) -> &OutputManifest {
+        &self.manifest
+    }
+
+    /// Build the file name for a part of a day's output
+    ///
+    /// The first part is named after the date alone; later parts get a `_partNNN` suffix.
+    /// Compressed files get the compression extension appended.
+    fn file_name(&self, date: NaiveDate, part: usize) -> String {
+        let mut extension = self.formatter.file_extension().to_string();
+        if let Some(compression_extension) = self.manifest.compression.extension() {
+            extension = format!("{}.{}", extension, compression_extension);
+        }
+        if part == 1 {
+            format!("{}.{}", date, extension)
+        } else {
+            format!("{}_part{:03}.{}", date, part, extension)
+        }
+    }
+
+    /// Open the next part file for a date, writing the header if the format has one
+    fn open_file(&mut self, date: NaiveDate) -> SimulationResult<()> {
+        self.close_file()?;
+
+        let part = self.manifest.files.iter().filter(|file| file.date == date).count() + 1;
+        let name = self.file_name(date, part);
+        let path = self.directory.join(&name);
+        let file = File::create(&path).map_err(|e| {
+            SimulationError::event_generation_error(format!(
+                "Failed to create event output file {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        debug!("Opened event output file {}", path.display());
+
+        let hashing_writer = HashingWriter { inner: BufWriter::new(file), hasher: Sha256::new(), bytes: 0 };
+        let mut open_file = OpenFile {
+            writer: CompressedWriter::new(hashing_writer, self.manifest.compression)?,
+            uncompressed_bytes: 0,
+            entry: ManifestFile {
+                path: name,
+                date,
+                part,
+                first_timestamp: None,
+                last_timestamp: None,
+                event_count: 0,
+                bytes: 0,
+                sha256: String::new(),
+            },
+        };
+        if let Some(header) = self.formatter.header() {
+            open_file.write_line(&header)?;
+        }
+
+        self.current = Some(open_file);
+        Ok(())
+    }
+
+    /// Close the current file, if any, and record it in the manifest
+    fn close_file(&mut self) -> SimulationResult<()> {
+        if let Some(open_file) = self.current.take() {
+            let entry = open_file.close()?;
+            self.manifest.total_events += entry.event_count;
+            self.manifest.files.push(entry);
+        }
+        Ok(())
+    }
+}
+
+impl EventSink for DirectoryEventSink {
+    fn begin_day(&mut self, date: NaiveDate) -> SimulationResult<()> {
+        self.open_file(date)
+    }
+
+    fn write_event(&mut self, event: &AccessEvent) -> SimulationResult<()> {
+        let line = self.formatter.format_event(event)?;
+
+        let date = match &self.current {
+            Some(open_file) => {
+                let entry = &open_file.entry;
+                let rotate = self.rotation.should_rotate(
+                    open_file.uncompressed_bytes,
+                    entry.event_count,
+                    line.len() as u64 + 1,
+                );
+                rotate.then_some(entry.date)
+            }
+            None => Some(event.timestamp.date_naive()),
+        };
+        if let Some(date) = date {
+            self.open_file(date)?;
+        }
+
+        let open_file = self.current.as_mut().expect("output file is open");
+        open_file.write_line(&line)?;
+        let entry = &mut open_file.entry;
+        entry.first_timestamp.get_or_insert(event.timestamp);
+        entry.last_timestamp = Some(event.timestamp);
+        entry.event_count += 1;
+        Ok(())
+    }
+
+    fn end_day(&mut self, _date: NaiveDate) -> SimulationResult<()> {
+        self.close_file()
+    }
+
+    fn finish(&mut self) -> SimulationResult<()> {
+        self.close_file()?;
+
+        let manifest_path = self.manifest_path();
+        fs::write(&manifest_path, serde_json::to_string_pretty(&self.manifest)?)?;
+        info!(
+            "Wrote {} events in {} files to {}",
+            self.manifest.total_events,
+            self.manifest.files.len(),
+            self.directory.display()
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::config::OutputFieldConfig;
+    use crate::types::{BuildingId, EventType, LocationId, RoomId, UserId};
+    use chrono::{Duration, TimeZone};
+
+    fn create_event(timestamp: DateTime<Utc>) -> AccessEvent {
+        AccessEvent::new(
+            timestamp,
+            UserId::new(),
+            RoomId::new(),
+            BuildingId::new(),
+            LocationId::new(),
+            true,
+            EventType::Success,
+        )
+    }
+
+    fn write_day(sink: &mut DirectoryEventSink, date: NaiveDate, count: usize) {
+        let start = Utc.from_utc_datetime(&date.and_hms_opt(8, 0, 0).unwrap());
+        sink.begin_day(date).unwrap();
+        for i in 0..count {
+            sink.write_event(&create_event(start + Duration::minutes(i as i64))).unwrap();
+        }
+        sink.end_day(date).unwrap();
+    }
+
+    fn json_formatter() -> EventFormatter {
+        EventFormatter::new(OutputFormat::Json, OutputFieldConfig::default())
+    }
+
+    #[test]
+    fn test_rotation_policy() {
+        let none = RotationPolicy::none();
+        assert!(!none.should_rotate(u64::MAX / 2, usize::MAX, 100));
+
+        let by_events = RotationPolicy { max_events: Some(2), ..Default::default() };
+        assert!(!by_events.should_rotate(0, 1, 10));
+        assert!(by_events.should_rotate(0, 2, 10));
+
+        let by_bytes = RotationPolicy { max_bytes: Some(100), ..Default::default() };
+        assert!(!by_bytes.should_rotate(90, 1, 10));
+        assert!(by_bytes.should_rotate(91, 1, 10));
+        assert!(!by_bytes.should_rotate(0, 0, 500), "empty files are never rotated");
+    }
+
+    #[test]
+    fn test_one_file_per_day() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut sink =
+            DirectoryEventSink::create(dir.path(), json_formatter(), RotationPolicy::none()).unwrap();
+        let day1 = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let day2 = NaiveDate::from_ymd_opt(2024, 1, 2).unwrap();
+
+        write_day(&mut sink, day1, 3);
+        write_day(&mut sink, day2, 2);
+        sink.finish().unwrap();
+
+        let day1_contents = fs::read_to_string(dir.path().join("2024-01-01.jsonl")).unwrap();
+        let day2_contents = fs::read_to_string(dir.path().join("2024-01-02.jsonl")).unwrap();
+        assert_eq!(day1_contents.lines().count(), 3);
+        assert_eq!(day2_contents.lines().count(), 2);
+
+        let manifest = OutputManifest::load(sink.manifest_path()).unwrap();
+        assert_eq!(manifest.total_events, 5);
+        assert_eq!(manifest.files.len(), 2);
+        assert_eq!(manifest.files[0].date, day1);
+        assert_eq!(manifest.files[0].event_count, 3);
+        assert_eq!(manifest.files[0].bytes, day1_contents.len() as u64);
+        assert_eq!(
+            manifest.files[0].first_timestamp,
+            Some(Utc.from_utc_datetime(&day1.and_hms_opt(8, 0, 0).unwrap()))
+        );
+        assert_eq!(
+            manifest.files[0].last_timestamp,
+            Some(Utc.from_utc_datetime(&day1.and_hms_opt(8, 2, 0).unwrap()))
+        );
+    }
+
+    #[test]
+    fn test_rotation_by_event_count() {
+        let dir = tempfile::tempdir().unwrap();
+        let rotation = RotationPolicy { max_events: Some(2), ..Default::default() };
+        let mut sink = DirectoryEventSink::create(dir.path(), json_formatter(), rotation).unwrap();
+        let date = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+
+        write_day(&mut sink, date, 5);
+        sink.finish().unwrap();
+
+        let names: Vec<&str> = sink.manifest().files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["2024-01-01.jsonl", "2024-01-01_part002.jsonl", "2024-01-01_part003.jsonl"]
+        );
+        let counts: Vec<usize> = sink.manifest().files.iter().map(|f| f.event_count).collect();
+        assert_eq!(counts, vec![2, 2, 1]);
+    }
+
+    #[test]
+    fn test_rotation_by_size_repeats_csv_header() {
+        let dir = tempfile::tempdir().unwrap();
+        let formatter = EventFormatter::new(OutputFormat::Csv, OutputFieldConfig::default());
+        let header = formatter.header().unwrap();
+        let rotation = RotationPolicy { max_bytes: Some(400), ..Default::default() };
+        let mut sink = DirectoryEventSink::create(dir.path(), formatter, rotation).unwrap();
+
+        write_day(&mut sink, NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(), 6);
+        sink.finish().unwrap();
+
+        let manifest = sink.manifest();
+        assert!(manifest.files.len() > 1, "Small size threshold should produce several parts");
+        assert_eq!(manifest.total_events, 6);
+        for file in &manifest.files {
+            let contents = fs::read_to_string(dir.path().join(&file.path)).unwrap();
+            assert!(file.bytes <= 400);
+            assert_eq!(contents.lines().next(), Some(header.as_str()));
+        }
+    }
+
+    #[test]
+    fn test_manifest_checksum_matches_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut sink =
+            DirectoryEventSink::create(dir.path(), json_formatter(), RotationPolicy::none()).unwrap();
+
+        write_day(&mut sink, NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(), 4);
+        sink.finish().unwrap();
+
+        let entry = &sink.manifest().files[0];
+        let contents = fs::read(dir.path().join(&entry.path)).unwrap();
+        let expected: String = Sha256::digest(&contents).iter().map(|b| format!("{:02x}", b)).collect();
+        assert_eq!(entry.sha256, expected);
+        assert_eq!(entry.sha256.len(), 64);
+    }
+
+    #[test]
+    fn test_compressed_files_checksum_bytes_on_disk() {
+        use std::io::Read;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mut sink = DirectoryEventSink::create(dir.path(), json_formatter(), RotationPolicy::none())
+            .unwrap()
+            .with_compression(Compression::Zstd);
+
+        write_day(&mut sink, NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(), 4);
+        sink.finish().unwrap();
+
+        let manifest = OutputManifest::load(sink.manifest_path()).unwrap();
+        assert_eq!(manifest.compression, Compression::Zstd);
+        let entry = &manifest.files[0];
+        assert_eq!(entry.path, "2024-01-01.jsonl.zst");
+
+        let path = dir.path().join(&entry.path);
+        let on_disk = fs::read(&path).unwrap();
+        assert_eq!(entry.bytes, on_disk.len() as u64);
+        let expected: String = Sha256::digest(&on_disk).iter().map(|b| format!("{:02x}", b)).collect();
+        assert_eq!(entry.sha256, expected);
+
+        let mut contents = String::new();
+        crate::simulation::open_input_file(&path).unwrap().read_to_string(&mut contents).unwrap();
+        assert_eq!(contents.lines().count(), 4);
+    }
+
+    #[test]
+    fn test_day_without_events_still_gets_a_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut sink =
+            DirectoryEventSink::create(dir.path(), json_formatter(), RotationPolicy::none()).unwrap();
+
+        write_day(&mut sink, NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(), 0);
+        sink.finish().unwrap();
+
+        let entry = &sink.manifest().files[0];
+        assert_eq!(entry.event_count, 0);
+        assert_eq!(entry.first_timestamp, None);
+        assert!(dir.path().join(&entry.path).exists());
+    }
+}
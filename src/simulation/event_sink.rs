@@ -0,0 +1,347 @@
+//! Event sinks for generated access events
+//!
+//! This module defines the `EventSink` trait that receives events from the
+//! `BatchEventGenerator`, along with the built-in sinks: a writer-backed sink for
+//! stdout and files (optionally compressed), and an in-memory sink for embedding the
+//! simulator in tests and other Rust programs.
+
+use chrono::NaiveDate;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use crate::events::{AccessEvent, EventFormatter};
+use crate::simulation::{CompressedWriter, SimulationError, SimulationResult};
+use crate::types::Compression;
+
+/// Destination for generated access events
+///
+/// The batch generator calls `begin_day` before the events of each simulated day,
+/// `write_event` once per event in chronological order, `end_day` after the day's
+/// events, and `finish` once after the last day. Events that extend beyond the
+/// simulated period are delivered as additional days before `finish`.
+pub trait EventSink: fmt::Debug {
+    /// Called before the events for a simulated day are written
+    fn begin_day(&mut self, _date: NaiveDate) -> SimulationResult<()> {
+        Ok(())
+    }
+
+    /// Write a single access event
+    fn write_event(&mut self, event: &AccessEvent) -> SimulationResult<()>;
+
+    /// Called after all events for a simulated day have been written
+    fn end_day(&mut self, _date: NaiveDate) -> SimulationResult<()> {
+        Ok(())
+    }
+
+    /// Called once after the last event has been written
+    fn finish(&mut self) -> SimulationResult<()> {
+        Ok(())
+    }
+}
+
+/// Event sink that writes formatted event lines to any `Write` implementation
+///
+/// The header line (for formats that have one) is written before the first event,
+/// or on `finish` if no events were written. Output is flushed at the end of each day,
+/// and compressed streams are finalized on `finish`.
+pub struct WriterEventSink<W: Write> {
+    writer: CompressedWriter<W>,
+    formatter: EventFormatter,
+    header_written: bool,
+    events_written: usize,
+}
+
+/// Event sink that writes formatted event lines to stdout
+pub type StdoutEventSink = WriterEventSink<BufWriter<io::Stdout>>;
+
+/// Event sink that writes formatted event lines to a file
+pub type FileEventSink = WriterEventSink<BufWriter<File>>;
+
+impl<W: Write> WriterEventSink<W> {
+    /// Create a new sink that writes uncompressed output to the given writer
+    pub fn new(writer: W, formatter: EventFormatter) -> Self {
+        let writer = CompressedWriter::new(writer, Compression::None)
+            .expect("uncompressed writers cannot fail to initialize");
+        Self::from_writer(writer, formatter)
+    }
+
+    /// Create a new sink that compresses its output before writing it to the given writer
+    pub fn with_compression(
+        writer: W,
+        formatter: EventFormatter,
+        compression: Compression,
+    ) -> SimulationResult<Self> {
+        Ok(Self::from_writer(CompressedWriter::new(writer, compression)?, formatter))
+    }
+
+    /// Create a new sink from an already wrapped writer
+    fn from_writer(writer: CompressedWriter<W>, formatter: EventFormatter) -> Self {
+        Self { writer, formatter, header_written: false, events_written: 0 }
+    }
+
+    /// Get the number of events written so far
+    pub fn events_written(&self) -> usize {
+        self.events_written
+    }
+
+    /// Get the formatter used to render events
+    pub fn formatter(&self) -> &EventFormatter {
+        &self.formatter
+    }
+
+    /// Consume the sink, finishing any compressed stream, and return the underlying writer
+    pub fn into_inner(self) -> SimulationResult<W> {
+        Ok(self.writer.finish()?)
+    }
+
+    /// Write the header line if the format has one and it has not been written yet
+    fn ensure_header(&mut self) -> SimulationResult<()> {
+        if !self.header_written {
+            self.header_written = true;
+            if let Some(header) = self.formatter.header() {
+                writeln!(self.writer, "{}", header)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl StdoutEventSink {
+    /// Create a sink that writes to stdout
+    pub fn stdout(formatter: EventFormatter) -> Self {
+        Self::new(BufWriter::new(io::stdout()), formatter)
+    }
+
+    /// Create a sink that writes compressed output to stdout
+    pub fn stdout_compressed(formatter: EventFormatter, compression: Compression) -> SimulationResult<Self> {
+        Self::with_compression(BufWriter::new(io::stdout()), formatter, compression)
+    }
+}
+
+impl FileEventSink {
+    /// Create (or truncate) the file at `path` and return a sink that writes to it
+    ///
+    /// Output is compressed when the path ends in `.gz` or `.zst`.
+    pub fn create<P: AsRef<Path>>(path: P, formatter: EventFormatter) -> SimulationResult<Self> {
+        let file = File::create(path.as_ref()).map_err(|e| {
+            SimulationError::event_generation_error(format!(
+                "Failed to create event output file {}: {}",
+                path.as_ref().display(),
+                e
+            ))
+        })?;
+        Self::with_compression(BufWriter::new(file), formatter, Compression::from_path(path))
+    }
+}
+
+impl<W: Write> EventSink for WriterEventSink<W> {
+    fn write_event(&mut self, event: &AccessEvent) -> SimulationResult<()> {
+        self.ensure_header()?;
+        let line = self.formatter.format_event(event)?;
+        writeln!(self.writer, "{}", line)?;
+        self.events_written += 1;
+        Ok(())
+    }
+
+    fn end_day(&mut self, _date: NaiveDate) -> SimulationResult<()> {
+        self.writer.flush()?;
+        Ok(())
+    }
+
+    fn finish(&mut self) -> SimulationResult<()> {
+        self.ensure_header()?;
+        self.writer.try_finish()?;
+        Ok(())
+    }
+}
+
+impl<W: Write> fmt::Debug for WriterEventSink<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WriterEventSink")
+            .field("writer", &self.writer)
+            .field("formatter", &self.formatter)
+            .field("header_written", &self.header_written)
+            .field("events_written", &self.events_written)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Event sink that collects events in memory
+///
+/// Useful for test harnesses and library users that want the generated events
+/// directly instead of capturing and re-parsing formatted output.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryEventSink {
+    events: Vec<AccessEvent>,
+    days: Vec<NaiveDate>,
+    finished: bool,
+}
+
+impl MemoryEventSink {
+    /// Create a new, empty in-memory sink
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the collected events in the order they were written
+    pub fn events(&self) -> &[AccessEvent] {
+        &self.events
+    }
+
+    /// Get the days that were started, in the order they were started
+    pub fn days(&self) -> &[NaiveDate] {
+        &self.days
+    }
+
+    /// Check whether `finish` has been called
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Consume the sink and return the collected events
+    pub fn into_events(self) -> Vec<AccessEvent> {
+        self.events
+    }
+}
+
+impl EventSink for MemoryEventSink {
+    fn begin_day(&mut self, date: NaiveDate) -> SimulationResult<()> {
+        self.days.push(date);
+        Ok(())
+    }
+
+    fn write_event(&mut self, event: &AccessEvent) -> SimulationResult<()> {
+        self.events.push(event.clone());
+        Ok(())
+    }
+
+    fn finish(&mut self) -> SimulationResult<()> {
+        self.finished = true;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::config::OutputFieldConfig;
+    use crate::types::{BuildingId, EventType, LocationId, OutputFormat, RoomId, UserId};
+    use chrono::{TimeZone, Utc};
+
+    fn create_test_event() -> AccessEvent {
+        AccessEvent::new(
+            Utc.with_ymd_and_hms(2024, 1, 15, 10, 30, 0).unwrap(),
+            UserId::new(),
+            RoomId::new(),
+            BuildingId::new(),
+            LocationId::new(),
+            true,
+            EventType::Success,
+        )
+    }
+
+    fn csv_formatter() -> EventFormatter {
+        EventFormatter::new(OutputFormat::Csv, OutputFieldConfig::default())
+    }
+
+    #[test]
+    fn test_writer_sink_writes_json_lines() {
+        let formatter = EventFormatter::new(OutputFormat::Json, OutputFieldConfig::default());
+        let mut sink = WriterEventSink::new(Vec::new(), formatter);
+        let date = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+
+        sink.begin_day(date).unwrap();
+        sink.write_event(&create_test_event()).unwrap();
+        sink.write_event(&create_test_event()).unwrap();
+        sink.end_day(date).unwrap();
+        sink.finish().unwrap();
+
+        assert_eq!(sink.events_written(), 2);
+        let output = String::from_utf8(sink.into_inner().unwrap()).unwrap();
+        assert_eq!(output.lines().count(), 2);
+        assert!(output.lines().all(|line| line.starts_with("{\"timestamp\":")));
+    }
+
+    #[test]
+    fn test_writer_sink_writes_csv_header_once() {
+        let mut sink = WriterEventSink::new(Vec::new(), csv_formatter());
+
+        sink.write_event(&create_test_event()).unwrap();
+        sink.write_event(&create_test_event()).unwrap();
+        sink.finish().unwrap();
+
+        let output = String::from_utf8(sink.into_inner().unwrap()).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], csv_formatter().header().unwrap());
+    }
+
+    #[test]
+    fn test_writer_sink_writes_csv_header_without_events() {
+        let mut sink = WriterEventSink::new(Vec::new(), csv_formatter());
+        sink.finish().unwrap();
+
+        let output = String::from_utf8(sink.into_inner().unwrap()).unwrap();
+        assert_eq!(output.trim_end(), csv_formatter().header().unwrap());
+    }
+
+    #[test]
+    fn test_file_sink_writes_to_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("events.csv");
+
+        let mut sink = FileEventSink::create(&path, csv_formatter()).unwrap();
+        sink.write_event(&create_test_event()).unwrap();
+        sink.finish().unwrap();
+        drop(sink);
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        assert_eq!(contents.lines().count(), 2);
+    }
+
+    #[test]
+    fn test_file_sink_compresses_by_extension() {
+        use std::io::Read;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("events.csv.gz");
+
+        let mut sink = FileEventSink::create(&path, csv_formatter()).unwrap();
+        sink.write_event(&create_test_event()).unwrap();
+        sink.finish().unwrap();
+        drop(sink);
+
+        let mut contents = String::new();
+        crate::simulation::open_input_file(&path).unwrap().read_to_string(&mut contents).unwrap();
+        assert_eq!(contents.lines().count(), 2);
+        assert!(std::fs::read(&path).unwrap().starts_with(&[0x1f, 0x8b]));
+    }
+
+    #[test]
+    fn test_file_sink_reports_create_failure() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("missing").join("events.jsonl");
+
+        assert!(FileEventSink::create(&path, csv_formatter()).is_err());
+    }
+
+    #[test]
+    fn test_memory_sink_collects_events_and_days() {
+        let mut sink = MemoryEventSink::new();
+        let date = NaiveDate::from_ymd_opt(2024, 1, 15).unwrap();
+        let event = create_test_event();
+
+        sink.begin_day(date).unwrap();
+        sink.write_event(&event).unwrap();
+        sink.end_day(date).unwrap();
+        assert!(!sink.is_finished());
+        sink.finish().unwrap();
+
+        assert!(sink.is_finished());
+        assert_eq!(sink.days(), &[date]);
+        assert_eq!(sink.events().len(), 1);
+        assert_eq!(sink.into_events()[0].user_id, event.user_id);
+    }
+}
@@ -0,0 +1,492 @@
+//! HTTP batch event sink
+//!
+//! This module POSTs batches of events to an HTTP endpoint, so an ingestion API can be
+//! driven directly from the simulator. Events are rendered with the configured
+//! `EventFormatter` (which must produce JSON) and sent either as newline-delimited JSON
+//! or as a JSON array.
+//!
+//! Batches that fail with a 5xx status or a connection error are retried with
+//! exponential backoff. Batches that still fail, or that the endpoint rejects with a
+//! 4xx status, are appended to a dead-letter file (one event per line) when one is
+//! configured; otherwise the run is aborted.
+
+use chrono::NaiveDate;
+use std::fmt;
+use std::fs::OpenOptions;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+use tracing::{info, warn};
+
+use crate::events::{AccessEvent, EventFormatter};
+use crate::simulation::{EventSink, SimulationError, SimulationResult};
+use crate::types::HttpBodyFormat;
+
+/// Default number of events per request
+pub const DEFAULT_HTTP_BATCH_SIZE: usize = 500;
+
+/// Timeout for a single HTTP request
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Retry behavior for batches that fail with a server or connection error
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Number of retries after the first attempt
+    pub max_retries: u32,
+    /// Delay before the first retry; doubled for each further retry
+    pub initial_backoff: Duration,
+    /// Upper bound for the delay between retries
+    pub max_backoff: Duration,
+}
+
+impl RetryPolicy {
+    /// Get the delay before the given retry (0 for the first retry)
+    pub fn backoff(&self, retry: u32) -> Duration {
+        self.initial_backoff
+            .saturating_mul(2u32.saturating_pow(retry))
+            .min(self.max_backoff)
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+        }
+    }
+}
+
+/// Delivery counters for an HTTP sink
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HttpSinkStats {
+    /// Batches accepted by the endpoint
+    pub batches_sent: usize,
+    /// Events in accepted batches
+    pub events_sent: usize,
+    /// Retries performed across all batches
+    pub retries: usize,
+    /// Batches written to the dead-letter file
+    pub batches_dead_lettered: usize,
+    /// Events in dead-lettered batches
+    pub events_dead_lettered: usize,
+}
+
+/// Event sink that POSTs batches of events to an HTTP endpoint
+///
+/// A batch is sent when it reaches the batch size, and any partial batch is sent at the
+/// end of each simulated day.
+pub struct HttpEventSink {
+    agent: ureq::Agent,
+    url: String,
+    formatter: EventFormatter,
+    body_format: HttpBodyFormat,
+    batch_size: usize,
+    header: Option<(String, String)>,
+    retry_policy: RetryPolicy,
+    dead_letter: Option<PathBuf>,
+    batch: Vec<String>,
+    stats: HttpSinkStats,
+}
+
+impl HttpEventSink {
+    /// Create a sink that posts events to `url`
+    ///
+    /// Fails when the formatter does not render events as JSON.
+    pub fn new(url: impl Into<String>, formatter: EventFormatter) -> SimulationResult<Self> {
+        if !formatter.output_format().is_json() {
+            return Err(SimulationError::configuration_error(format!(
+                "HTTP output requires a JSON-based output format, got {}",
+                formatter.output_format()
+            )));
+        }
+
+        Ok(Self {
+            agent: ureq::AgentBuilder::new().timeout(REQUEST_TIMEOUT).build(),
+            url: url.into(),
+            formatter,
+            body_format: HttpBodyFormat::default(),
+            batch_size: DEFAULT_HTTP_BATCH_SIZE,
+            header: None,
+            retry_policy: RetryPolicy::default(),
+            dead_letter: None,
+            batch: Vec::new(),
+            stats: HttpSinkStats::default(),
+        })
+    }
+
+    /// Set the maximum number of events per request (at least 1)
+    pub fn with_batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Set the request body layout
+    pub fn with_body_format(mut self, body_format: HttpBodyFormat) -> Self {
+        self.body_format = body_format;
+        self
+    }
+
+    /// Add a header to every request, e.g. `Authorization`
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.header = Some((name.into(), value.into()));
+        self
+    }
+
+    /// Set the retry behavior for failed batches
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Append undeliverable batches to this file instead of failing
+    pub fn with_dead_letter<P: AsRef<Path>>(mut self, path: P) -> Self {
+        self.dead_letter = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Get the delivery counters
+    pub fn stats(&self) -> HttpSinkStats {
+        self.stats
+    }
+
+    /// Send the buffered events, dead-lettering them if they cannot be delivered
+    fn flush_batch(&mut self) -> SimulationResult<()> {
+        if self.batch.is_empty() {
+            return Ok(());
+        }
+
+        let lines = std::mem::take(&mut self.batch);
+        let body = match self.body_format {
+            HttpBodyFormat::Ndjson => {
+                let mut body = lines.join("\n");
+                body.push('\n');
+                body
+            }
+            HttpBodyFormat::JsonArray => format!("[{}]", lines.join(",")),
+        };
+
+        match self.post_with_retry(&body) {
+            Ok(()) => {
+                self.stats.batches_sent += 1;
+                self.stats.events_sent += lines.len();
+                Ok(())
+            }
+            Err(reason) => self.dead_letter_batch(&lines, &reason),
+        }
+    }
+
+    /// POST a request body, retrying server and connection errors with backoff
+    fn post_with_retry(&mut self, body: &str) -> Result<(), String> {
+        let mut retry = 0;
+        loop {
+            let mut request =
+                self.agent.post(&self.url).set("Content-Type", self.body_format.content_type());
+            if let Some((name, value)) = &self.header {
+                request = request.set(name, value);
+            }
+
+            let error = match request.send_string(body) {
+                Ok(_) => return Ok(()),
+                Err(ureq::Error::Status(status, _)) if status >= 500 => {
+                    format!("server responded with HTTP {}", status)
+                }
+                Err(ureq::Error::Status(status, _)) => {
+                    return Err(format!("server rejected the batch with HTTP {}", status));
+                }
+                Err(ureq::Error::Transport(transport)) => format!("request failed: {}", transport),
+            };
+
+            if retry >= self.retry_policy.max_retries {
+                return Err(format!("{} (gave up after {} attempts)", error, retry + 1));
+            }
+            let delay = self.retry_policy.backoff(retry);
+            warn!("HTTP batch to {} failed: {}; retrying in {:?}", self.url, error, delay);
+            thread::sleep(delay);
+            retry += 1;
+            self.stats.retries += 1;
+        }
+    }
+
+    /// Append an undeliverable batch to the dead-letter file, or fail without one
+    fn dead_letter_batch(&mut self, lines: &[String], reason: &str) -> SimulationResult<()> {
+        let Some(path) = &self.dead_letter else {
+            return Err(SimulationError::event_generation_error(format!(
+                "Failed to deliver a batch of {} events to {}: {}",
+                lines.len(),
+                self.url,
+                reason
+            )));
+        };
+
+        let file = OpenOptions::new().create(true).append(true).open(path).map_err(|e| {
+            SimulationError::event_generation_error(format!(
+                "Failed to open dead-letter file {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        let mut writer = BufWriter::new(file);
+        for line in lines {
+            writeln!(writer, "{}", line)?;
+        }
+        writer.flush()?;
+
+        warn!(
+            "Wrote a batch of {} events to dead-letter file {}: {}",
+            lines.len(),
+            path.display(),
+            reason
+        );
+        self.stats.batches_dead_lettered += 1;
+        self.stats.events_dead_lettered += lines.len();
+        Ok(())
+    }
+}
+
+impl EventSink for HttpEventSink {
+    fn write_event(&mut self, event: &AccessEvent) -> SimulationResult<()> {
+        self.batch.push(self.formatter.format_event(event)?);
+        if self.batch.len() >= self.batch_size {
+            self.flush_batch()?;
+        }
+        Ok(())
+    }
+
+    fn end_day(&mut self, _date: NaiveDate) -> SimulationResult<()> {
+        self.flush_batch()
+    }
+
+    fn finish(&mut self) -> SimulationResult<()> {
+        self.flush_batch()?;
+        info!(
+            "HTTP output finished: {} events in {} batches sent, {} events in {} batches dead-lettered, {} retries",
+            self.stats.events_sent,
+            self.stats.batches_sent,
+            self.stats.events_dead_lettered,
+            self.stats.batches_dead_lettered,
+            self.stats.retries
+        );
+        Ok(())
+    }
+}
+
+impl fmt::Debug for HttpEventSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // The header value is omitted because it usually carries credentials
+        f.debug_struct("HttpEventSink")
+            .field("url", &self.url)
+            .field("formatter", &self.formatter)
+            .field("body_format", &self.body_format)
+            .field("batch_size", &self.batch_size)
+            .field("header", &self.header.as_ref().map(|(name, _)| name))
+            .field("retry_policy", &self.retry_policy)
+            .field("dead_letter", &self.dead_letter)
+            .field("buffered_events", &self.batch.len())
+            .field("stats", &self.stats)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::config::OutputFieldConfig;
+    use crate::types::{BuildingId, EventType, LocationId, OutputFormat, RoomId, UserId};
+    use chrono::{TimeZone, Utc};
+    use std::io::{BufRead, BufReader, Read};
+    use std::net::TcpListener;
+
+    /// A request received by the test server: (request line and headers, body)
+    type ReceivedRequest = (String, String);
+
+    fn create_test_event() -> AccessEvent {
+        AccessEvent::new(
+            Utc.with_ymd_and_hms(2024, 1, 15, 10, 30, 0).unwrap(),
+            UserId::new(),
+            RoomId::new(),
+            BuildingId::new(),
+            LocationId::new(),
+            true,
+            EventType::Success,
+        )
+    }
+
+    fn json_formatter() -> EventFormatter {
+        EventFormatter::new(OutputFormat::Json, OutputFieldConfig::default())
+    }
+
+    fn fast_retries(max_retries: u32) -> RetryPolicy {
+        RetryPolicy {
+            max_retries,
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(5),
+        }
+    }
+
+    /// Serve one request per status code, in order, and return the received requests
+    fn spawn_server(statuses: Vec<u16>) -> (String, thread::JoinHandle<Vec<ReceivedRequest>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/ingest", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let mut requests = Vec::new();
+            for status in statuses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+
+                let mut head = String::new();
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if line == "\r\n" || line.is_empty() {
+                        break;
+                    }
+                    head.push_str(&line);
+                }
+                let length = head
+                    .lines()
+                    .find_map(|line| {
+                        line.to_ascii_lowercase()
+                            .strip_prefix("content-length:")
+                            .map(|value| value.trim().parse::<usize>().unwrap())
+                    })
+                    .unwrap_or(0);
+                let mut body = vec![0; length];
+                reader.read_exact(&mut body).unwrap();
+
+                write!(
+                    reader.get_mut(),
+                    "HTTP/1.1 {} Test\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+                    status
+                )
+                .unwrap();
+                requests.push((head, String::from_utf8(body).unwrap()));
+            }
+            requests
+        });
+
+        (url, handle)
+    }
+
+    #[test]
+    fn test_batches_ndjson_with_auth_header() {
+        let (url, server) = spawn_server(vec![200, 200]);
+        let mut sink = HttpEventSink::new(url, json_formatter())
+            .unwrap()
+            .with_batch_size(2)
+            .with_header("Authorization", "Bearer secret");
+
+        let events = [create_test_event(), create_test_event(), create_test_event()];
+        for event in &events {
+            sink.write_event(event).unwrap();
+        }
+        sink.finish().unwrap();
+
+        let requests = server.join().unwrap();
+        assert_eq!(requests.len(), 2);
+        assert!(requests[0].0.starts_with("POST /ingest HTTP/1.1"));
+        assert!(requests[0].0.contains("Authorization: Bearer secret"));
+        assert!(requests[0].0.contains("Content-Type: application/x-ndjson"));
+        assert_eq!(requests[0].1.lines().count(), 2);
+        assert_eq!(requests[1].1.lines().count(), 1);
+        assert_eq!(
+            requests[1].1.trim_end(),
+            json_formatter().format_event(&events[2]).unwrap()
+        );
+
+        let stats = sink.stats();
+        assert_eq!((stats.batches_sent, stats.events_sent), (2, 3));
+        assert!(!format!("{:?}", sink).contains("secret"));
+    }
+
+    #[test]
+    fn test_json_array_body() {
+        let (url, server) = spawn_server(vec![202]);
+        let mut sink = HttpEventSink::new(url, json_formatter())
+            .unwrap()
+            .with_body_format(HttpBodyFormat::JsonArray);
+
+        sink.write_event(&create_test_event()).unwrap();
+        sink.write_event(&create_test_event()).unwrap();
+        sink.end_day(NaiveDate::from_ymd_opt(2024, 1, 15).unwrap()).unwrap();
+
+        let requests = server.join().unwrap();
+        assert!(requests[0].0.contains("Content-Type: application/json"));
+        let body: serde_json::Value = serde_json::from_str(&requests[0].1).unwrap();
+        assert_eq!(body.as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_retries_server_errors() {
+        let (url, server) = spawn_server(vec![503, 500, 200]);
+        let mut sink = HttpEventSink::new(url, json_formatter())
+            .unwrap()
+            .with_retry_policy(fast_retries(3));
+
+        sink.write_event(&create_test_event()).unwrap();
+        sink.finish().unwrap();
+
+        let requests = server.join().unwrap();
+        assert_eq!(requests.len(), 3);
+        assert!(requests.iter().all(|(_, body)| body == &requests[0].1));
+        assert_eq!(sink.stats().retries, 2);
+        assert_eq!(sink.stats().batches_sent, 1);
+    }
+
+    #[test]
+    fn test_failed_batches_go_to_dead_letter() {
+        let dir = tempfile::tempdir().unwrap();
+        let dead_letter = dir.path().join("failed.jsonl");
+
+        // The first batch exhausts its retries; the second is rejected without retrying
+        let (url, server) = spawn_server(vec![500, 500, 400]);
+        let mut sink = HttpEventSink::new(url, json_formatter())
+            .unwrap()
+            .with_batch_size(1)
+            .with_retry_policy(fast_retries(1))
+            .with_dead_letter(&dead_letter);
+
+        let events = [create_test_event(), create_test_event()];
+        for event in &events {
+            sink.write_event(event).unwrap();
+        }
+        sink.finish().unwrap();
+        assert_eq!(server.join().unwrap().len(), 3);
+
+        let contents = std::fs::read_to_string(&dead_letter).unwrap();
+        let expected: Vec<String> =
+            events.iter().map(|event| json_formatter().format_event(event).unwrap()).collect();
+        assert_eq!(contents.lines().collect::<Vec<_>>(), expected);
+        assert_eq!(sink.stats().batches_dead_lettered, 2);
+        assert_eq!(sink.stats().events_dead_lettered, 2);
+    }
+
+    #[test]
+    fn test_failure_without_dead_letter_is_an_error() {
+        let (url, server) = spawn_server(vec![422]);
+        let mut sink = HttpEventSink::new(url, json_formatter()).unwrap();
+
+        sink.write_event(&create_test_event()).unwrap();
+        let error = sink.finish().unwrap_err();
+        assert!(error.to_string().contains("HTTP 422"));
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn test_requires_json_formatter() {
+        let formatter = EventFormatter::new(OutputFormat::Csv, OutputFieldConfig::default());
+        assert!(HttpEventSink::new("http://localhost/ingest", formatter).is_err());
+    }
+
+    #[test]
+    fn test_backoff_doubles_up_to_limit() {
+        let policy = RetryPolicy::default();
+        assert_eq!(policy.backoff(0), Duration::from_millis(500));
+        assert_eq!(policy.backoff(1), Duration::from_secs(1));
+        assert_eq!(policy.backoff(3), Duration::from_secs(4));
+        assert_eq!(policy.backoff(10), Duration::from_secs(30));
+    }
+}
@@ -9,9 +9,18 @@
 //!
 //! - **SimulationOrchestrator**: Main controller that coordinates all simulation components
 //! - **TimeManager**: Handles time acceleration and temporal calculations
+//! - **WorkCalendar**: Weekends, public holidays and half-days at each location
 //! - **BehaviorEngine**: Generates realistic user activity patterns
 //! - **SimulationStatistics**: Collects and reports simulation metrics
 //! - **SimulationError**: Comprehensive error handling for simulation operations
+//! - **RngStream**: Per-subsystem random number streams derived from the simulation seed
+//! - **EventSink**: Destination for generated events, with stdout, file, and in-memory sinks
+//! - **DirectoryEventSink**: Writes one file per simulated day with rotation and a checksummed manifest
+//! - **CompressedWriter**: Optional gzip or Zstandard compression for output files and streams
+//! - **SyslogEventSink**: Sends events as RFC 5424 syslog messages over UDP or TCP
+//! - **HttpEventSink**: POSTs batches of events to an HTTP endpoint with retries and a dead-letter file
+//! - **SqliteEventSink**: Writes facilities, users, permissions and events to normalized SQLite tables
+//! - **ParquetEventSink**: Writes one columnar Parquet file per simulated day for dataframe tooling
 //!
 //! # Usage Example
 //!
@@ -31,24 +40,42 @@
 //!
 //! // Time management
 //! let time_manager = TimeManager::new();
-//! let is_business_hours = time_manager.is_business_hours(chrono::Utc::now());
+//! let is_business_hours = time_manager.is_business_hours(chrono::Utc::now(), chrono_tz::Europe::London);
 //! ```
 
 pub mod batch_generator;
 pub mod behavior_engine;
+pub mod calendar;
+pub mod compression;
+pub mod directory_sink;
 pub mod error;
+pub mod event_sink;
+pub mod http_sink;
+pub mod sqlite_sink;
 pub mod logging;
 pub mod orchestrator;
+pub mod parquet_sink;
+pub mod rng_streams;
 pub mod statistics;
+pub mod syslog_sink;
 pub mod time_manager;
 pub mod time_variance;
 
 // Re-export all public types for convenience
 pub use batch_generator::*;
 pub use behavior_engine::*;
+pub use calendar::*;
+pub use compression::*;
+pub use directory_sink::*;
 pub use error::*;
+pub use event_sink::*;
+pub use http_sink::*;
+pub use sqlite_sink::*;
 pub use logging::*;
 pub use orchestrator::*;
+pub use parquet_sink::*;
+pub use rng_streams::*;
 pub use statistics::*;
+pub use syslog_sink::*;
 pub use time_manager::*;
 pub use time_variance::*;
@@ -7,7 +7,7 @@ use crate::events::EventGenerator;
 use crate::facility::LocationRegistry;
 use crate::simulation::{
     BehaviorEngine, ErrorHandler, RuntimeStatistics, SimulationResult, SimulationStatistics,
-    TimeManager,
+    TimeManager, WorkCalendar,
 };
 use crate::types::SimulationConfig;
 use tracing::{debug, info, instrument, warn};
@@ -58,7 +58,7 @@ impl SimulationOrchestrator {
         };
 
         // Create time manager for batch processing
-        let time_manager = TimeManager::new();
+        let time_manager = TimeManager::new().with_calendar(WorkCalendar::new(config.calendar.clone()));
 
         // Initialize empty components that will be populated during setup
         let location_registry = LocationRegistry::new();
@@ -208,16 +208,10 @@ impl SimulationOrchestrator {
             }
 
             // Then, check for specific failure reasons that need special handling
-            if let Some(failure_reason) = &event.failure_reason {
-                match failure_reason {
-                    crate::types::FailureReason::BadgeReaderError => {
-                        self.statistics.increment_badge_reader_failure_events();
-                        continue; // Don't double-count as a regular failure
-                    }
-                    _ => {
-                        // Other failure reasons fall through to normal event type classification
-                    }
-                }
+            // Other failure reasons fall through to normal event type classification
+            if let Some(crate::types::FailureReason::BadgeReaderError) = &event.failure_reason {
+                self.statistics.increment_badge_reader_failure_events();
+                continue; // Don't double-count as a regular failure
             }
 
             // Standard event type classification
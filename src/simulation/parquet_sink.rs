@@ -0,0 +1,663 @@
+//! Apache Parquet output for large runs
+//!
+//! This module contains the `ParquetEventSink`, which writes one Parquet file per simulated
+//! day (for example `2024-01-01.parquet`) so multi-million-event datasets can be loaded
+//! straight into DuckDB, Polars or Spark without parsing JSON lines.
+//!
+//! The event schema mirrors the flat `FilteredAccessEvent` shape used by CSV output:
+//! `timestamp` is a UTC millisecond timestamp column, the four ids are dictionary-encoded
+//! strings, and the optional fields (`event_type`, `failure_reason` and the flattened
+//! `metadata_*` columns) are present only when the output field configuration enables
+//! them. Facility name columns are filled from the registry attached with
+//! [`ParquetEventSink::with_registry`], and the `badge_number` column from the directory
+//! attached with [`ParquetEventSink::with_identities`].
+//!
+//! [`write_user_profiles_parquet`] writes the user profile answer key in the same columnar
+//! form.
+
+use arrow_array::builder::{
+    BooleanBuilder, Float64Builder, Int64Builder, ListBuilder, StringBuilder, StringDictionaryBuilder,
+    TimestampMillisecondBuilder, UInt8Builder,
+};
+use arrow_array::types::{Int32Type, Int8Type};
+use arrow_array::{ArrayRef, RecordBatch};
+use arrow_schema::{DataType, Field, Schema, SchemaRef, TimeUnit};
+use chrono::NaiveDate;
+use parquet::arrow::ArrowWriter;
+use parquet::basic::{Compression as ParquetCompression, ZstdLevel};
+use parquet::file::properties::WriterProperties;
+use serde::Serialize;
+use std::fmt;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tracing::{debug, info};
+
+use crate::events::{AccessEvent, CsvEventFormatter, EventFacilityContext};
+use crate::facility::LocationRegistry;
+use crate::simulation::{EventSink, SimulationError, SimulationResult};
+use crate::types::config::OutputFieldConfig;
+use crate::user::{IdentityDirectory, UserProfile};
+
+/// File name of the user profile answer key written next to the event files
+pub const USER_PROFILES_PARQUET_FILE_NAME: &str = "user_profiles.parquet";
+
+/// Number of events buffered before they are handed to the Parquet writer as one record batch
+const RECORD_BATCH_SIZE: usize = 8192;
+
+/// Event sink that writes one Parquet file per simulated day into a directory
+pub struct ParquetEventSink {
+    directory: PathBuf,
+    field_config: OutputFieldConfig,
+    schema: SchemaRef,
+    registry: Option<Arc<LocationRegistry>>,
+    identities: Option<Arc<IdentityDirectory>>,
+    current: Option<(NaiveDate, ArrowWriter<File>)>,
+    buffer: Vec<AccessEvent>,
+    files_written: Vec<PathBuf>,
+    events_written: usize,
+}
+
+impl ParquetEventSink {
+    /// Create the output directory (if needed) and return a sink that writes into it
+    pub fn create<P: AsRef<Path>>(directory: P, field_config: OutputFieldConfig) -> SimulationResult<Self> {
+        let directory = directory.as_ref().to_path_buf();
+        fs::create_dir_all(&directory).map_err(|e| {
+            SimulationError::event_generation_error(format!(
+                "Failed to create Parquet output directory {}: {}",
+                directory.display(),
+                e
+            ))
+        })?;
+
+        let schema = event_schema(&field_config);
+        Ok(Self {
+            directory,
+            field_config,
+            schema,
+            registry: None,
+            identities: None,
+            current: None,
+            buffer: Vec::new(),
+            files_written: Vec::new(),
+            events_written: 0,
+        })
+    }
+
+    /// Attach a location registry used to fill in facility name columns
+    pub fn with_registry(mut self, registry: Arc<LocationRegistry>) -> Self {
+        self.registry = Some(registry);
+        self
+    }
+
+    /// Attach an identity directory used to fill in the badge number column
+    pub fn with_identities(mut self, identities: Arc<IdentityDirectory>) -> Self {
+        self.identities = Some(identities);
+        self
+    }
+
+    /// Get the output directory
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    /// Get the Arrow schema of the event files
+    pub fn schema(&self) -> &SchemaRef {
+        &self.schema
+    }
+
+    /// Get the paths of all files closed so far
+    pub fn files_written(&self) -> &[PathBuf] {
+        &self.files_written
+    }
+
+    /// Get the number of events written so far
+    pub fn events_written(&self) -> usize {
+        self.events_written
+    }
+
+    /// Open the file for a date, closing the current one
+    fn open_file(&mut self, date: NaiveDate) -> SimulationResult<()> {
+        self.close_file()?;
+
+        let path = self.directory.join(format!("{}.parquet", date));
+        let file = File::create(&path).map_err(|e| {
+            SimulationError::event_generation_error(format!(
+                "Failed to create Parquet file {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        let writer =
+            ArrowWriter::try_new(file, self.schema.clone(), Some(writer_properties())).map_err(parquet_error)?;
+        debug!("Opened Parquet output file {}", path.display());
+
+        self.current = Some((date, writer));
+        Ok(())
+    }
+
+    /// Write the buffered events to the current file as one record batch
+    fn flush_buffer(&mut self) -> SimulationResult<()> {
+        if self.buffer.is_empty() {
+            return Ok(());
+        }
+        let batch = event_record_batch(
+            &self.schema,
+            &self.field_config,
+            self.registry.as_deref(),
+            self.identities.as_deref(),
+            &self.buffer,
+        )?;
+        let (_, writer) = self.current.as_mut().expect("Parquet file is open");
+        writer.write(&batch).map_err(parquet_error)?;
+        self.events_written += self.buffer.len();
+        self.buffer.clear();
+        Ok(())
+    }
+
+    /// Flush and close the current file, if any
+    fn close_file(&mut self) -> SimulationResult<()> {
+        self.flush_buffer()?;
+        if let Some((date, writer)) = self.current.take() {
+            writer.close().map_err(parquet_error)?;
+            self.files_written.push(self.directory.join(format!("{}.parquet", date)));
+        }
+        Ok(())
+    }
+}
+
+impl EventSink for ParquetEventSink {
+    fn begin_day(&mut self, date: NaiveDate) -> SimulationResult<()> {
+        self.open_file(date)
+    }
+
+    fn write_event(&mut self, event: &AccessEvent) -> SimulationResult<()> {
+        if self.current.is_none() {
+            self.open_file(event.timestamp.date_naive())?;
+        }
+        self.buffer.push(event.clone());
+        if self.buffer.len() >= RECORD_BATCH_SIZE {
+            self.flush_buffer()?;
+        }
+        Ok(())
+    }
+
+    fn end_day(&mut self, _date: NaiveDate) -> SimulationResult<()> {
+        self.close_file()
+    }
+
+    fn finish(&mut self) -> SimulationResult<()> {
+        self.close_file()?;
+        info!(
+            "Wrote {} events in {} Parquet files to {}",
+            self.events_written,
+            self.files_written.len(),
+            self.directory.display()
+        );
+        Ok(())
+    }
+}
+
+impl fmt::Debug for ParquetEventSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ParquetEventSink")
+            .field("directory", &self.directory)
+            .field("field_config", &self.field_config)
+            .field("current_date", &self.current.as_ref().map(|(date, _)| *date))
+            .field("buffered_events", &self.buffer.len())
+            .field("events_written", &self.events_written)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Write user profiles (the answer key) to a single Parquet file
+///
+/// Each profile becomes one row with the primary assignments, the authorized rooms,
+/// buildings and locations as list columns, the behavior flags, the behavior profile
+/// flattened into `behavior_*` columns, and the synthetic identity (empty when absent).
+pub fn write_user_profiles_parquet<P: AsRef<Path>>(path: P, profiles: &[UserProfile]) -> SimulationResult<()> {
+    let path = path.as_ref();
+    let id_list = || DataType::List(Arc::new(Field::new("item", DataType::Utf8, true)));
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("user_id", DataType::Utf8, false),
+        Field::new("primary_location", dictionary_type(), false),
+        Field::new("primary_building", dictionary_type(), false),
+        Field::new("primary_workspace", DataType::Utf8, false),
+        Field::new("authorized_rooms", id_list(), false),
+        Field::new("authorized_buildings", id_list(), false),
+        Field::new("authorized_locations", id_list(), false),
+        Field::new("is_curious", DataType::Boolean, false),
+        Field::new("has_cloned_badge", DataType::Boolean, false),
+        Field::new("is_night_shift", DataType::Boolean, false),
+        Field::new("behavior_travel_frequency", DataType::Float64, false),
+        Field::new("behavior_curiosity_level", DataType::Float64, false),
+        Field::new("behavior_schedule_adherence", DataType::Float64, false),
+        Field::new("behavior_social_level", DataType::Float64, false),
+        Field::new("given_name", DataType::Utf8, true),
+        Field::new("family_name", DataType::Utf8, true),
+        Field::new("email", DataType::Utf8, true),
+        Field::new("employee_number", DataType::Utf8, true),
+        Field::new("badge_number", DataType::Utf8, true),
+    ]));
+
+    let mut user_id = StringBuilder::new();
+    let mut primary_location = StringDictionaryBuilder::<Int32Type>::new();
+    let mut primary_building = StringDictionaryBuilder::<Int32Type>::new();
+    let mut primary_workspace = StringBuilder::new();
+    let mut authorized_rooms = ListBuilder::new(StringBuilder::new());
+    let mut authorized_buildings = ListBuilder::new(StringBuilder::new());
+    let mut authorized_locations = ListBuilder::new(StringBuilder::new());
+    let mut is_curious = BooleanBuilder::new();
+    let mut has_cloned_badge = BooleanBuilder::new();
+    let mut is_night_shift = BooleanBuilder::new();
+    let mut behavior: [Float64Builder; 4] = Default::default();
+    let mut identity: [StringBuilder; 5] = Default::default();
+
+    for profile in profiles {
+        user_id.append_value(profile.user_id.to_string());
+        primary_location.append_value(profile.primary_location.to_string());
+        primary_building.append_value(profile.primary_building.to_string());
+        primary_workspace.append_value(profile.primary_workspace.to_string());
+        authorized_rooms.append_value(profile.authorized_rooms.iter().map(|id| Some(id.to_string())));
+        authorized_buildings.append_value(profile.authorized_buildings.iter().map(|id| Some(id.to_string())));
+        authorized_locations.append_value(profile.authorized_locations.iter().map(|id| Some(id.to_string())));
+        is_curious.append_value(profile.is_curious);
+        has_cloned_badge.append_value(profile.has_cloned_badge);
+        is_night_shift.append_value(profile.is_night_shift);
+
+        let behavior_profile = &profile.behavior_profile;
+        behavior[0].append_value(behavior_profile.travel_frequency);
+        behavior[1].append_value(behavior_profile.curiosity_level);
+        behavior[2].append_value(behavior_profile.schedule_adherence);
+        behavior[3].append_value(behavior_profile.social_level);
+
+        let user_identity = profile.identity.as_ref();
+        identity[0].append_option(user_identity.map(|i| i.given_name.as_str()));
+        identity[1].append_option(user_identity.map(|i| i.family_name.as_str()));
+        identity[2].append_option(user_identity.map(|i| i.email.as_str()));
+        identity[3].append_option(user_identity.map(|i| i.employee_number.as_str()));
+        identity[4].append_option(user_identity.map(|i| i.badge_number.to_string()));
+    }
+
+    let mut columns: Vec<ArrayRef> = vec![
+        Arc::new(user_id.finish()),
+        Arc::new(primary_location.finish()),
+        Arc::new(primary_building.finish()),
+        Arc::new(primary_workspace.finish()),
+        Arc::new(authorized_rooms.finish()),
+        Arc::new(authorized_buildings.finish()),
+        Arc::new(authorized_locations.finish()),
+        Arc::new(is_curious.finish()),
+        Arc::new(has_cloned_badge.finish()),
+        Arc::new(is_night_shift.finish()),
+    ];
+    columns.extend(behavior.iter_mut().map(|builder| Arc::new(builder.finish()) as ArrayRef));
+    columns.extend(identity.iter_mut().map(|builder| Arc::new(builder.finish()) as ArrayRef));
+    let batch = RecordBatch::try_new(schema.clone(), columns).map_err(parquet_error)?;
+
+    let file = File::create(path).map_err(|e| {
+        SimulationError::event_generation_error(format!(
+            "Failed to create Parquet file {}: {}",
+            path.display(),
+            e
+        ))
+    })?;
+    let mut writer = ArrowWriter::try_new(file, schema, Some(writer_properties())).map_err(parquet_error)?;
+    writer.write(&batch).map_err(parquet_error)?;
+    writer.close().map_err(parquet_error)?;
+    Ok(())
+}
+
+/// Build the Arrow schema for event files under the given field configuration
+///
+/// Column names and order match the CSV header for the same configuration.
+pub fn event_schema(field_config: &OutputFieldConfig) -> SchemaRef {
+    let fields: Vec<Field> = CsvEventFormatter::new(field_config.clone())
+        .column_names()
+        .into_iter()
+        .map(|name| {
+            let (data_type, nullable) = match name.as_str() {
+                "timestamp" => (DataType::Timestamp(TimeUnit::Millisecond, Some("UTC".into())), false),
+                "user_id" | "room_id" | "building_id" | "location_id" => (dictionary_type(), false),
+                "badge_number" => (dictionary_type(), true),
+                "success" => (DataType::Boolean, false),
+                "event_type" => (small_dictionary_type(), false),
+                "failure_reason" => (small_dictionary_type(), true),
+                "metadata_retry_attempt_number" => (DataType::UInt8, true),
+                "metadata_travel_time_violation_seconds" => (DataType::Int64, true),
+                "metadata_geographical_distance" => (DataType::Float64, true),
+                "local_offset" => (small_dictionary_type(), true),
+                "room_name" | "building_name" | "location_name" => (dictionary_type(), true),
+                "room_type" | "security_level" => (small_dictionary_type(), true),
+                _ => (DataType::Boolean, true),
+            };
+            Field::new(name, data_type, nullable)
+        })
+        .collect();
+    Arc::new(Schema::new(fields))
+}
+
+/// Build a record batch from events, with one array per schema column
+fn event_record_batch(
+    schema: &SchemaRef,
+    field_config: &OutputFieldConfig,
+    registry: Option<&LocationRegistry>,
+    identities: Option<&IdentityDirectory>,
+    events: &[AccessEvent],
+) -> SimulationResult<RecordBatch> {
+    let include_metadata = field_config.include_metadata || field_config.include_all;
+    let metadata = |event: &AccessEvent| event.metadata.clone().filter(|_| include_metadata);
+    let contexts: Vec<EventFacilityContext<'_>> = if field_config.include_facility_names {
+        events.iter().map(|event| EventFacilityContext::resolve(registry, event)).collect()
+    } else {
+        Vec::new()
+    };
+
+    let columns = schema
+        .fields()
+        .iter()
+        .map(|field| -> ArrayRef {
+            match field.name().as_str() {
+                "timestamp" => {
+                    let mut builder = TimestampMillisecondBuilder::with_capacity(events.len()).with_timezone("UTC");
+                    events.iter().for_each(|e| builder.append_value(e.timestamp.timestamp_millis()));
+                    Arc::new(builder.finish())
+                }
+                "user_id" => id_column(events.iter().map(|e| e.user_id.to_string())),
+                "badge_number" => {
+                    let mut builder = StringDictionaryBuilder::<Int32Type>::new();
+                    events.iter().for_each(|e| {
+                        builder.append_option(identities.and_then(|i| i.badge_number(e.user_id)).map(|b| b.to_string()))
+                    });
+                    Arc::new(builder.finish())
+                }
+                "room_id" => id_column(events.iter().map(|e| e.room_id.to_string())),
+                "building_id" => id_column(events.iter().map(|e| e.building_id.to_string())),
+                "location_id" => id_column(events.iter().map(|e| e.location_id.to_string())),
+                "success" => {
+                    let mut builder = BooleanBuilder::with_capacity(events.len());
+                    events.iter().for_each(|e| builder.append_value(e.success));
+                    Arc::new(builder.finish())
+                }
+                "event_type" => {
+                    let mut builder = StringDictionaryBuilder::<Int8Type>::new();
+                    events.iter().for_each(|e| builder.append_value(enum_name(&e.event_type)));
+                    Arc::new(builder.finish())
+                }
+                "failure_reason" => {
+                    let mut builder = StringDictionaryBuilder::<Int8Type>::new();
+                    events
+                        .iter()
+                        .for_each(|e| builder.append_option(e.failure_reason.map(|reason| enum_name(&reason))));
+                    Arc::new(builder.finish())
+                }
+                "metadata_retry_attempt_number" => {
+                    let mut builder = UInt8Builder::with_capacity(events.len());
+                    events.iter().for_each(|e| builder.append_option(metadata(e).and_then(|m| m.retry_attempt_number)));
+                    Arc::new(builder.finish())
+                }
+                "metadata_travel_time_violation_seconds" => {
+                    let mut builder = Int64Builder::with_capacity(events.len());
+                    events.iter().for_each(|e| {
+                        builder.append_option(
+                            metadata(e).and_then(|m| m.travel_time_violation).map(|d| d.num_seconds()),
+                        )
+                    });
+                    Arc::new(builder.finish())
+                }
+                "metadata_geographical_distance" => {
+                    let mut builder = Float64Builder::with_capacity(events.len());
+                    events.iter().for_each(|e| builder.append_option(metadata(e).and_then(|m| m.geographical_distance)));
+                    Arc::new(builder.finish())
+                }
+                "local_offset" => {
+                    let mut builder = StringDictionaryBuilder::<Int8Type>::new();
+                    events.iter().for_each(|e| builder.append_option(e.local_offset.map(|o| o.to_string())));
+                    Arc::new(builder.finish())
+                }
+                "room_name" => name_column(contexts.iter().map(|c| c.room_name())),
+                "building_name" => name_column(contexts.iter().map(|c| c.building_name())),
+                "location_name" => name_column(contexts.iter().map(|c| c.location_name())),
+                "room_type" => {
+                    let mut builder = StringDictionaryBuilder::<Int8Type>::new();
+                    contexts.iter().for_each(|c| builder.append_option(c.room.map(|room| enum_name(&room.room_type))));
+                    Arc::new(builder.finish())
+                }
+                "security_level" => {
+                    let mut builder = StringDictionaryBuilder::<Int8Type>::new();
+                    contexts
+                        .iter()
+                        .for_each(|c| builder.append_option(c.room.map(|room| enum_name(&room.security_level))));
+                    Arc::new(builder.finish())
+                }
+                flag => {
+                    let mut builder = BooleanBuilder::with_capacity(events.len());
+                    for event in events {
+                        builder.append_option(metadata(event).map(|m| match flag {
+                            "metadata_is_curious_attempt" => m.is_curious_attempt,
+                            "metadata_is_impossible_traveler" => m.is_impossible_traveler,
+                            "metadata_is_badge_reader_failure" => m.is_badge_reader_failure,
+                            _ => m.is_night_shift_event,
+                        }));
+                    }
+                    Arc::new(builder.finish())
+                }
+            }
+        })
+        .collect();
+
+    RecordBatch::try_new(schema.clone(), columns).map_err(parquet_error)
+}
+
+/// Build a dictionary-encoded string column from ids
+fn id_column(ids: impl Iterator<Item = String>) -> ArrayRef {
+    let mut builder = StringDictionaryBuilder::<Int32Type>::new();
+    ids.for_each(|id| builder.append_value(id));
+    Arc::new(builder.finish())
+}
+
+/// Build a dictionary-encoded string column from optional facility names
+fn name_column<'a>(names: impl Iterator<Item = Option<&'a str>>) -> ArrayRef {
+    let mut builder = StringDictionaryBuilder::<Int32Type>::new();
+    names.for_each(|name| builder.append_option(name));
+    Arc::new(builder.finish())
+}
+
+/// Arrow type used for id columns
+fn dictionary_type() -> DataType {
+    DataType::Dictionary(Box::new(DataType::Int32), Box::new(DataType::Utf8))
+}
+
+/// Arrow type used for enum columns with only a handful of distinct values
+fn small_dictionary_type() -> DataType {
+    DataType::Dictionary(Box::new(DataType::Int8), Box::new(DataType::Utf8))
+}
+
+/// Parquet writer settings shared by event and profile files
+fn writer_properties() -> WriterProperties {
+    WriterProperties::builder()
+        .set_compression(ParquetCompression::ZSTD(ZstdLevel::default()))
+        .set_dictionary_enabled(true)
+        .build()
+}
+
+/// Convert a Parquet or Arrow error into a simulation error
+fn parquet_error(error: impl fmt::Display) -> SimulationError {
+    SimulationError::event_generation_error(format!("Parquet export failed: {}", error))
+}
+
+/// Get the serialized name of an enum value (e.g. `Success`)
+fn enum_name<T: Serialize>(value: &T) -> String {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(name)) => name,
+        Ok(other) => other.to_string(),
+        Err(_) => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::EventMetadata;
+    use crate::types::{BuildingId, EventType, FailureReason, LocationId, RoomId, SimulationConfig, UserId};
+    use crate::user::UserGenerator;
+    use arrow_array::cast::AsArray;
+    use arrow_array::types::{Int64Type, TimestampMillisecondType};
+    use chrono::{DateTime, Duration, TimeZone, Utc};
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+    fn create_event(timestamp: DateTime<Utc>, user_id: UserId) -> AccessEvent {
+        AccessEvent::new(
+            timestamp,
+            user_id,
+            RoomId::new(),
+            BuildingId::new(),
+            LocationId::new(),
+            true,
+            EventType::Success,
+        )
+    }
+
+    fn read_batches(path: &Path) -> Vec<RecordBatch> {
+        let file = File::open(path).unwrap();
+        ParquetRecordBatchReaderBuilder::try_new(file).unwrap().build().unwrap().map(|b| b.unwrap()).collect()
+    }
+
+    #[test]
+    fn test_one_file_per_day_with_typed_columns() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut sink = ParquetEventSink::create(dir.path(), OutputFieldConfig::default()).unwrap();
+        let user_id = UserId::new();
+        let day1 = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let day2 = NaiveDate::from_ymd_opt(2024, 1, 2).unwrap();
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 8, 0, 0).unwrap();
+
+        sink.begin_day(day1).unwrap();
+        for i in 0..3 {
+            sink.write_event(&create_event(start + Duration::minutes(i), user_id)).unwrap();
+        }
+        sink.end_day(day1).unwrap();
+        sink.begin_day(day2).unwrap();
+        sink.write_event(&create_event(start + Duration::days(1), user_id)).unwrap();
+        sink.end_day(day2).unwrap();
+        sink.finish().unwrap();
+
+        assert_eq!(sink.events_written(), 4);
+        assert_eq!(
+            sink.files_written(),
+            &[dir.path().join("2024-01-01.parquet"), dir.path().join("2024-01-02.parquet")]
+        );
+
+        let batches = read_batches(&dir.path().join("2024-01-01.parquet"));
+        let batch = &batches[0];
+        assert_eq!(batch.num_rows(), 3);
+        let schema = batch.schema();
+        let columns: Vec<&str> = schema.fields().iter().map(|f| f.name().as_str()).collect();
+        assert_eq!(columns, vec!["timestamp", "user_id", "room_id", "building_id", "location_id", "success"]);
+
+        let timestamps = batch.column(0).as_primitive::<TimestampMillisecondType>();
+        assert_eq!(timestamps.value(0), start.timestamp_millis());
+        let user_ids = batch.column(1).as_dictionary::<Int32Type>();
+        assert_eq!(user_ids.values().len(), 1, "Repeated ids share one dictionary entry");
+        assert_eq!(user_ids.values().as_string::<i32>().value(0), user_id.to_string());
+    }
+
+    #[test]
+    fn test_optional_columns_follow_field_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let field_config = OutputFieldConfig { include_all: true, ..Default::default() };
+        let mut sink = ParquetEventSink::create(dir.path(), field_config.clone()).unwrap();
+
+        let mut event = create_event(Utc.with_ymd_and_hms(2024, 1, 1, 9, 0, 0).unwrap(), UserId::new());
+        event.success = false;
+        event.event_type = EventType::Suspicious;
+        event.failure_reason = Some(FailureReason::ImpossibleTraveler);
+        event.metadata = Some(EventMetadata::impossible_traveler(Duration::hours(2), 812.5));
+        sink.write_event(&event).unwrap();
+        sink.write_event(&create_event(event.timestamp, event.user_id)).unwrap();
+        sink.finish().unwrap();
+
+        let batch = &read_batches(&dir.path().join("2024-01-01.parquet"))[0];
+        let schema = batch.schema();
+        let expected: Vec<String> = CsvEventFormatter::new(field_config).column_names();
+        let columns: Vec<String> = schema.fields().iter().map(|f| f.name().clone()).collect();
+        assert_eq!(columns, expected);
+
+        let column = |name: &str| batch.column(schema.index_of(name).unwrap()).clone();
+        let reasons = column("failure_reason");
+        assert_eq!(reasons.as_dictionary::<Int8Type>().values().as_string::<i32>().value(0), "ImpossibleTraveler");
+        assert!(reasons.is_null(1));
+        assert!(column("metadata_is_impossible_traveler").as_boolean().value(0));
+        let violations = column("metadata_travel_time_violation_seconds");
+        assert_eq!(violations.as_primitive::<Int64Type>().value(0), 7200);
+        assert!(column("metadata_geographical_distance").is_null(1));
+    }
+
+    #[test]
+    fn test_facility_name_columns() {
+        let config = SimulationConfig { location_count: 1, ..Default::default() };
+        let registry = Arc::new(crate::facility::FacilityGenerator::with_seed(8).generate_facilities(&config).unwrap());
+        let location = &registry.locations[0];
+        let room = &location.buildings[0].rooms[0];
+
+        let dir = tempfile::tempdir().unwrap();
+        let field_config = OutputFieldConfig { include_facility_names: true, ..Default::default() };
+        let mut sink = ParquetEventSink::create(dir.path(), field_config).unwrap().with_registry(registry.clone());
+        let mut event = create_event(Utc.with_ymd_and_hms(2024, 1, 1, 9, 0, 0).unwrap(), UserId::new());
+        event.room_id = room.id;
+        event.building_id = location.buildings[0].id;
+        event.location_id = location.id;
+        sink.write_event(&event).unwrap();
+        sink.write_event(&create_event(event.timestamp, event.user_id)).unwrap();
+        sink.finish().unwrap();
+
+        let batch = &read_batches(&dir.path().join("2024-01-01.parquet"))[0];
+        let schema = batch.schema();
+        let column = |name: &str| batch.column(schema.index_of(name).unwrap()).clone();
+        let room_names = column("room_name");
+        assert_eq!(room_names.as_dictionary::<Int32Type>().values().as_string::<i32>().value(0), room.name);
+        assert!(room_names.is_null(1));
+        let room_types = column("room_type");
+        assert_eq!(room_types.as_dictionary::<Int8Type>().values().as_string::<i32>().value(0), enum_name(&room.room_type));
+        assert!(column("location_name").is_null(1));
+    }
+
+    #[test]
+    fn test_batches_larger_than_buffer() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut sink = ParquetEventSink::create(dir.path(), OutputFieldConfig::default()).unwrap();
+        let start = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let count = RECORD_BATCH_SIZE + 10;
+
+        for i in 0..count {
+            sink.write_event(&create_event(start + Duration::seconds(i as i64), UserId::new())).unwrap();
+        }
+        sink.finish().unwrap();
+
+        let rows: usize = read_batches(&dir.path().join("2024-01-01.parquet")).iter().map(|b| b.num_rows()).sum();
+        assert_eq!(rows, count);
+        assert_eq!(sink.events_written(), count);
+    }
+
+    #[test]
+    fn test_user_profiles_parquet() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(USER_PROFILES_PARQUET_FILE_NAME);
+        let config = SimulationConfig { user_count: 10, location_count: 1, seed: Some(5), ..Default::default() };
+        let registry = crate::facility::FacilityGenerator::with_seed(5).generate_facilities(&config).unwrap();
+        let users = UserGenerator::with_seed(5).generate_users(&config, &registry).unwrap();
+        let profiles: Vec<UserProfile> = users.iter().map(|u| UserProfile::from_user(u, &config)).collect();
+
+        write_user_profiles_parquet(&path, &profiles).unwrap();
+
+        let batch = &read_batches(&path)[0];
+        assert_eq!(batch.num_rows(), profiles.len());
+        let schema = batch.schema();
+        let rooms = batch.column(schema.index_of("authorized_rooms").unwrap()).as_list::<i32>();
+        assert_eq!(rooms.value(0).len(), profiles[0].authorized_rooms.len());
+        let curious = batch.column(schema.index_of("is_curious").unwrap()).as_boolean();
+        assert_eq!(curious.value(0), profiles[0].is_curious);
+        let badges = batch.column(schema.index_of("badge_number").unwrap()).as_string::<i32>();
+        assert_eq!(badges.value(0), profiles[0].identity.as_ref().unwrap().badge_number.to_string());
+    }
+}
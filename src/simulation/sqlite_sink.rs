@@ -0,0 +1,564 @@
+//! SQLite export of facilities, users, permissions and events
+//!
+//! This module writes a normalized SQLite database so detection can be taught and
+//! prototyped in SQL, joining events against room types, security levels and user
+//! permissions. The facility and user tables are written when the sink is created; events
+//! are inserted as they are generated, one transaction per simulated day.
+//!
+//! Foreign keys link every table and are enforced while loading. Answer-key columns
+//! follow the output field configuration: `event_type`, `failure_reason` and the
+//! `metadata_*` event columns, as well as the user behavior flags, are NULL unless the
+//! corresponding fields are enabled. The users table also carries each user's synthetic
+//! identity, so badge numbers can be joined back to the badge holder.
+
+use chrono::{NaiveDate, SecondsFormat};
+use rusqlite::{params, Connection};
+use serde::Serialize;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::events::AccessEvent;
+use crate::facility::LocationRegistry;
+use crate::permissions::PermissionLevel;
+use crate::simulation::{EventSink, SimulationError, SimulationResult};
+use crate::types::config::OutputFieldConfig;
+use crate::user::User;
+
+/// Table definitions and indexes for the export
+const SCHEMA: &str = "
+CREATE TABLE locations (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    latitude REAL NOT NULL,
+    longitude REAL NOT NULL
+);
+
+CREATE TABLE buildings (
+    id TEXT PRIMARY KEY,
+    location_id TEXT NOT NULL REFERENCES locations(id),
+    name TEXT NOT NULL,
+    lobby_room_id TEXT REFERENCES rooms(id) DEFERRABLE INITIALLY DEFERRED
+);
+
+CREATE TABLE rooms (
+    id TEXT PRIMARY KEY,
+    building_id TEXT NOT NULL REFERENCES buildings(id),
+    name TEXT NOT NULL,
+    room_type TEXT NOT NULL,
+    security_level TEXT NOT NULL
+);
+
+CREATE TABLE room_required_access (
+    room_id TEXT NOT NULL REFERENCES rooms(id),
+    required_room_id TEXT NOT NULL REFERENCES rooms(id),
+    PRIMARY KEY (room_id, required_room_id)
+);
+
+CREATE TABLE users (
+    id TEXT PRIMARY KEY,
+    primary_location_id TEXT NOT NULL REFERENCES locations(id),
+    primary_building_id TEXT NOT NULL REFERENCES buildings(id),
+    primary_workspace_id TEXT NOT NULL REFERENCES rooms(id),
+    assigned_night_building_id TEXT REFERENCES buildings(id),
+    is_curious INTEGER,
+    has_cloned_badge INTEGER,
+    is_night_shift INTEGER,
+    given_name TEXT,
+    family_name TEXT,
+    email TEXT,
+    employee_number TEXT,
+    badge_number TEXT UNIQUE
+);
+
+CREATE TABLE permissions (
+    user_id TEXT NOT NULL REFERENCES users(id),
+    scope TEXT NOT NULL CHECK (scope IN ('room', 'building', 'location')),
+    room_id TEXT REFERENCES rooms(id),
+    building_id TEXT REFERENCES buildings(id),
+    location_id TEXT REFERENCES locations(id)
+);
+
+CREATE TABLE events (
+    id INTEGER PRIMARY KEY,
+    timestamp TEXT NOT NULL,
+    user_id TEXT NOT NULL REFERENCES users(id),
+    room_id TEXT NOT NULL REFERENCES rooms(id),
+    building_id TEXT NOT NULL REFERENCES buildings(id),
+    location_id TEXT NOT NULL REFERENCES locations(id),
+    success INTEGER NOT NULL,
+    event_type TEXT,
+    failure_reason TEXT,
+    metadata_is_curious_attempt INTEGER,
+    metadata_is_impossible_traveler INTEGER,
+    metadata_is_badge_reader_failure INTEGER,
+    metadata_is_night_shift_event INTEGER,
+    metadata_retry_attempt_number INTEGER,
+    metadata_travel_time_violation_seconds INTEGER,
+    metadata_geographical_distance REAL
+);
+
+CREATE INDEX idx_buildings_location ON buildings(location_id);
+CREATE INDEX idx_rooms_building ON rooms(building_id);
+CREATE INDEX idx_rooms_type ON rooms(room_type);
+CREATE INDEX idx_rooms_security_level ON rooms(security_level);
+CREATE INDEX idx_users_primary_building ON users(primary_building_id);
+CREATE INDEX idx_permissions_user ON permissions(user_id);
+CREATE INDEX idx_events_timestamp ON events(timestamp);
+CREATE INDEX idx_events_user_timestamp ON events(user_id, timestamp);
+CREATE INDEX idx_events_room ON events(room_id);
+CREATE INDEX idx_events_building ON events(building_id);
+CREATE INDEX idx_events_location ON events(location_id);
+CREATE INDEX idx_events_success ON events(success);
+";
+
+/// Statement used to insert each event
+const INSERT_EVENT: &str = "
+INSERT INTO events (
+    timestamp, user_id, room_id, building_id, location_id, success, event_type, failure_reason,
+    metadata_is_curious_attempt, metadata_is_impossible_traveler, metadata_is_badge_reader_failure,
+    metadata_is_night_shift_event, metadata_retry_attempt_number,
+    metadata_travel_time_violation_seconds, metadata_geographical_distance
+) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)";
+
+/// Event sink that writes a normalized SQLite database
+pub struct SqliteEventSink {
+    connection: Connection,
+    path: PathBuf,
+    field_config: OutputFieldConfig,
+    in_transaction: bool,
+    events_written: usize,
+}
+
+impl SqliteEventSink {
+    /// Create (or replace) the database at `path` and load the facility and user tables
+    pub fn create<P: AsRef<Path>>(
+        path: P,
+        registry: &LocationRegistry,
+        users: &[User],
+        field_config: OutputFieldConfig,
+    ) -> SimulationResult<Self> {
+        let path = path.as_ref().to_path_buf();
+        remove_database_files(&path)?;
+
+        let connection = Connection::open(&path).map_err(sqlite_error)?;
+        connection
+            .execute_batch("PRAGMA foreign_keys = ON; PRAGMA journal_mode = WAL; PRAGMA synchronous = NORMAL;")
+            .map_err(sqlite_error)?;
+        connection.execute_batch(SCHEMA).map_err(sqlite_error)?;
+
+        let mut sink = Self { connection, path, field_config, in_transaction: false, events_written: 0 };
+        sink.load_facilities(registry)?;
+        sink.load_users(users)?;
+        Ok(sink)
+    }
+
+    /// Get the database path
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Get the number of events written so far
+    pub fn events_written(&self) -> usize {
+        self.events_written
+    }
+
+    /// Insert locations, buildings, rooms and room access requirements
+    fn load_facilities(&mut self, registry: &LocationRegistry) -> SimulationResult<()> {
+        let transaction = self.connection.transaction().map_err(sqlite_error)?;
+        {
+            let mut insert_location = transaction
+                .prepare("INSERT INTO locations (id, name, latitude, longitude) VALUES (?1, ?2, ?3, ?4)")
+                .map_err(sqlite_error)?;
+            let mut insert_building = transaction
+                .prepare("INSERT INTO buildings (id, location_id, name, lobby_room_id) VALUES (?1, ?2, ?3, ?4)")
+                .map_err(sqlite_error)?;
+            let mut insert_room = transaction
+                .prepare(
+                    "INSERT INTO rooms (id, building_id, name, room_type, security_level) VALUES (?1, ?2, ?3, ?4, ?5)",
+                )
+                .map_err(sqlite_error)?;
+            let mut insert_required_access = transaction
+                .prepare("INSERT OR IGNORE INTO room_required_access (room_id, required_room_id) VALUES (?1, ?2)")
+                .map_err(sqlite_error)?;
+
+            for location in &registry.locations {
+                let (latitude, longitude) = location.coordinates;
+                insert_location
+                    .execute(params![location.id.to_string(), location.name, latitude, longitude])
+                    .map_err(sqlite_error)?;
+
+                for building in &location.buildings {
+                    insert_building
+                        .execute(params![
+                            building.id.to_string(),
+                            location.id.to_string(),
+                            building.name,
+                            building.lobby_room_id.map(|id| id.to_string()),
+                        ])
+                        .map_err(sqlite_error)?;
+
+                    for room in &building.rooms {
+                        insert_room
+                            .execute(params![
+                                room.id.to_string(),
+                                building.id.to_string(),
+                                room.name,
+                                enum_name(&room.room_type),
+                                enum_name(&room.security_level),
+                            ])
+                            .map_err(sqlite_error)?;
+                    }
+                }
+            }
+
+            // Access requirements may point at rooms inserted later, so they go in last
+            for room in registry.locations.iter().flat_map(|l| &l.buildings).flat_map(|b| &b.rooms) {
+                for required in &room.required_intermediate_access {
+                    insert_required_access
+                        .execute(params![room.id.to_string(), required.to_string()])
+                        .map_err(sqlite_error)?;
+                }
+            }
+        }
+        transaction.commit().map_err(sqlite_error)
+    }
+
+    /// Insert users and their permissions
+    fn load_users(&mut self, users: &[User]) -> SimulationResult<()> {
+        let include_labels = self.field_config.include_metadata || self.field_config.include_all;
+        let transaction = self.connection.transaction().map_err(sqlite_error)?;
+        {
+            let mut insert_user = transaction
+                .prepare(
+                    "INSERT INTO users (id, primary_location_id, primary_building_id, primary_workspace_id,
+                        assigned_night_building_id, is_curious, has_cloned_badge, is_night_shift,
+                        given_name, family_name, email, employee_number, badge_number)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+                )
+                .map_err(sqlite_error)?;
+            let mut insert_permission = transaction
+                .prepare(
+                    "INSERT INTO permissions (user_id, scope, room_id, building_id, location_id)
+                     VALUES (?1, ?2, ?3, ?4, ?5)",
+                )
+                .map_err(sqlite_error)?;
+
+            for user in users {
+                let identity = user.identity.as_ref();
+                insert_user
+                    .execute(params![
+                        user.id.to_string(),
+                        user.primary_location.to_string(),
+                        user.primary_building.to_string(),
+                        user.primary_workspace.to_string(),
+                        user.assigned_night_building.map(|id| id.to_string()),
+                        include_labels.then_some(user.is_curious),
+                        include_labels.then_some(user.has_cloned_badge),
+                        include_labels.then_some(user.is_night_shift),
+                        identity.map(|i| i.given_name.as_str()),
+                        identity.map(|i| i.family_name.as_str()),
+                        identity.map(|i| i.email.as_str()),
+                        identity.map(|i| i.employee_number.as_str()),
+                        identity.map(|i| i.badge_number.to_string()),
+                    ])
+                    .map_err(sqlite_error)?;
+
+                for permission in &user.permissions.permissions {
+                    let (scope, room, building, location) = match permission {
+                        PermissionLevel::Room(id) => ("room", Some(id.to_string()), None, None),
+                        PermissionLevel::Building(id) => ("building", None, Some(id.to_string()), None),
+                        PermissionLevel::Location(id) => ("location", None, None, Some(id.to_string())),
+                    };
+                    insert_permission
+                        .execute(params![user.id.to_string(), scope, room, building, location])
+                        .map_err(sqlite_error)?;
+                }
+            }
+        }
+        transaction.commit().map_err(sqlite_error)
+    }
+
+    /// Start a transaction for a batch of events if one is not already open
+    fn begin_transaction(&mut self) -> SimulationResult<()> {
+        if !self.in_transaction {
+            self.connection.execute_batch("BEGIN").map_err(sqlite_error)?;
+            self.in_transaction = true;
+        }
+        Ok(())
+    }
+
+    /// Commit the open transaction, if any
+    fn commit_transaction(&mut self) -> SimulationResult<()> {
+        if self.in_transaction {
+            self.connection.execute_batch("COMMIT").map_err(sqlite_error)?;
+            self.in_transaction = false;
+        }
+        Ok(())
+    }
+}
+
+impl EventSink for SqliteEventSink {
+    fn begin_day(&mut self, _date: NaiveDate) -> SimulationResult<()> {
+        self.begin_transaction()
+    }
+
+    fn write_event(&mut self, event: &AccessEvent) -> SimulationResult<()> {
+        self.begin_transaction()?;
+
+        let fields = &self.field_config;
+        let include_event_type = fields.include_event_type || fields.include_all;
+        let include_failure_reason = fields.include_failure_reason || fields.include_all;
+        let metadata = event
+            .metadata
+            .as_ref()
+            .filter(|_| fields.include_metadata || fields.include_all);
+
+        let mut insert = self.connection.prepare_cached(INSERT_EVENT).map_err(sqlite_error)?;
+        insert
+            .execute(params![
+                event.timestamp.to_rfc3339_opts(SecondsFormat::Millis, true),
+                event.user_id.to_string(),
+                event.room_id.to_string(),
+                event.building_id.to_string(),
+                event.location_id.to_string(),
+                event.success,
+                include_event_type.then(|| enum_name(&event.event_type)),
+                event.failure_reason.filter(|_| include_failure_reason).map(|reason| enum_name(&reason)),
+                metadata.map(|m| m.is_curious_attempt),
+                metadata.map(|m| m.is_impossible_traveler),
+                metadata.map(|m| m.is_badge_reader_failure),
+                metadata.map(|m| m.is_night_shift_event),
+                metadata.and_then(|m| m.retry_attempt_number),
+                metadata.and_then(|m| m.travel_time_violation).map(|d| d.num_seconds()),
+                metadata.and_then(|m| m.geographical_distance),
+            ])
+            .map_err(sqlite_error)?;
+
+        self.events_written += 1;
+        Ok(())
+    }
+
+    fn end_day(&mut self, _date: NaiveDate) -> SimulationResult<()> {
+        self.commit_transaction()
+    }
+
+    fn finish(&mut self) -> SimulationResult<()> {
+        self.commit_transaction()?;
+        self.connection.execute_batch("PRAGMA optimize;").map_err(sqlite_error)
+    }
+}
+
+impl fmt::Debug for SqliteEventSink {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SqliteEventSink")
+            .field("path", &self.path)
+            .field("field_config", &self.field_config)
+            .field("in_transaction", &self.in_transaction)
+            .field("events_written", &self.events_written)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Convert a SQLite error into a simulation error
+fn sqlite_error(error: rusqlite::Error) -> SimulationError {
+    SimulationError::event_generation_error(format!("SQLite export failed: {}", error))
+}
+
+/// Remove a database along with the `-wal` and `-shm` files SQLite keeps next to it
+///
+/// A write-ahead log left by an interrupted run could otherwise be replayed into the new
+/// database.
+fn remove_database_files(path: &Path) -> SimulationResult<()> {
+    for suffix in ["", "-wal", "-shm"] {
+        let mut file_name = path.as_os_str().to_os_string();
+        file_name.push(suffix);
+        let file = PathBuf::from(file_name);
+        if file.exists() {
+            fs::remove_file(&file).map_err(|e| {
+                SimulationError::event_generation_error(format!(
+                    "Failed to replace SQLite database {}: {}",
+                    file.display(),
+                    e
+                ))
+            })?;
+        }
+    }
+    Ok(())
+}
+
+/// Get the serialized name of an enum value (e.g. `MeetingRoom`)
+fn enum_name<T: Serialize>(value: &T) -> String {
+    match serde_json::to_value(value) {
+        Ok(serde_json::Value::String(name)) => name,
+        Ok(other) => other.to_string(),
+        Err(_) => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::EventMetadata;
+    use crate::facility::FacilityGenerator;
+    use crate::types::{EventType, FailureReason, SimulationConfig};
+    use crate::user::UserGenerator;
+    use chrono::{TimeZone, Utc};
+
+    fn create_test_world() -> (LocationRegistry, Vec<User>) {
+        let config = SimulationConfig {
+            user_count: 20,
+            location_count: 2,
+            seed: Some(17),
+            ..Default::default()
+        };
+        let registry = FacilityGenerator::with_seed(17).generate_facilities(&config).unwrap();
+        let users = UserGenerator::with_seed(17).generate_users(&config, &registry).unwrap();
+        (registry, users)
+    }
+
+    fn create_event(users: &[User], success: bool) -> AccessEvent {
+        let user = &users[0];
+        AccessEvent::new(
+            Utc.with_ymd_and_hms(2024, 1, 15, 10, 30, 0).unwrap(),
+            user.id,
+            user.primary_workspace,
+            user.primary_building,
+            user.primary_location,
+            success,
+            if success { EventType::Success } else { EventType::Suspicious },
+        )
+    }
+
+    fn count(connection: &Connection, table: &str) -> usize {
+        connection
+            .query_row(&format!("SELECT COUNT(*) FROM {}", table), [], |row| row.get(0))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_exports_facilities_users_and_events() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("simulation.db");
+        let (registry, users) = create_test_world();
+
+        let mut sink = SqliteEventSink::create(&path, &registry, &users, OutputFieldConfig::default()).unwrap();
+        sink.begin_day(NaiveDate::from_ymd_opt(2024, 1, 15).unwrap()).unwrap();
+        sink.write_event(&create_event(&users, true)).unwrap();
+        sink.write_event(&create_event(&users, false)).unwrap();
+        sink.end_day(NaiveDate::from_ymd_opt(2024, 1, 15).unwrap()).unwrap();
+        sink.finish().unwrap();
+        assert_eq!(sink.events_written(), 2);
+        drop(sink);
+
+        let connection = Connection::open(&path).unwrap();
+        assert_eq!(count(&connection, "locations"), registry.location_count());
+        assert_eq!(count(&connection, "buildings"), registry.total_building_count());
+        assert_eq!(count(&connection, "rooms"), registry.total_room_count());
+        assert_eq!(count(&connection, "users"), users.len());
+        assert_eq!(
+            count(&connection, "permissions"),
+            users.iter().map(|u| u.permissions.permissions.len()).sum::<usize>()
+        );
+        assert_eq!(count(&connection, "events"), 2);
+
+        // Events join against room type and security level
+        let (room_type, security_level): (String, String) = connection
+            .query_row(
+                "SELECT r.room_type, r.security_level FROM events e JOIN rooms r ON r.id = e.room_id LIMIT 1",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .unwrap();
+        let workspace = registry.get_room(users[0].primary_workspace).unwrap();
+        assert_eq!(room_type, enum_name(&workspace.room_type));
+        assert_eq!(security_level, enum_name(&workspace.security_level));
+
+        // Answer-key columns are left empty by default
+        let labels: (Option<String>, Option<bool>) = connection
+            .query_row("SELECT e.event_type, u.is_curious FROM events e JOIN users u ON u.id = e.user_id", [], |row| {
+                Ok((row.get(0)?, row.get(1)?))
+            })
+            .unwrap();
+        assert_eq!(labels, (None, None));
+
+        // Badge numbers resolve to the badge holder
+        let identity = users[0].identity.as_ref().unwrap();
+        let email: String = connection
+            .query_row(
+                "SELECT email FROM users WHERE badge_number = ?1",
+                [identity.badge_number.to_string()],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(email, identity.email);
+
+        let violations: usize = connection
+            .query_row("SELECT COUNT(*) FROM pragma_foreign_key_check", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(violations, 0);
+    }
+
+    #[test]
+    fn test_answer_key_columns_follow_field_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("simulation.db");
+        let (registry, users) = create_test_world();
+        let field_config = OutputFieldConfig { include_all: true, ..Default::default() };
+
+        let mut event = create_event(&users, false);
+        event.failure_reason = Some(FailureReason::ImpossibleTraveler);
+        event.metadata = Some(EventMetadata::impossible_traveler(chrono::Duration::hours(2), 812.5));
+
+        let mut sink = SqliteEventSink::create(&path, &registry, &users, field_config).unwrap();
+        sink.write_event(&event).unwrap();
+        sink.finish().unwrap();
+        drop(sink);
+
+        let connection = Connection::open(&path).unwrap();
+        let row: (String, String, bool, i64, f64) = connection
+            .query_row(
+                "SELECT event_type, failure_reason, metadata_is_impossible_traveler,
+                        metadata_travel_time_violation_seconds, metadata_geographical_distance FROM events",
+                [],
+                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?, row.get(4)?)),
+            )
+            .unwrap();
+        assert_eq!(row, ("Suspicious".to_string(), "ImpossibleTraveler".to_string(), true, 7200, 812.5));
+
+        let is_curious: Option<bool> = connection
+            .query_row("SELECT is_curious FROM users WHERE id = ?1", [users[0].id.to_string()], |row| row.get(0))
+            .unwrap();
+        assert_eq!(is_curious, Some(users[0].is_curious));
+    }
+
+    #[test]
+    fn test_create_removes_stale_write_ahead_log() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("simulation.db");
+        let stale_files = ["simulation.db", "simulation.db-wal", "simulation.db-shm"].map(|name| dir.path().join(name));
+        for file in &stale_files {
+            fs::write(file, b"left by an interrupted run").unwrap();
+        }
+
+        remove_database_files(&path).unwrap();
+        assert!(stale_files.iter().all(|file| !file.exists()));
+
+        let (registry, users) = create_test_world();
+        let sink = SqliteEventSink::create(&path, &registry, &users, OutputFieldConfig::default()).unwrap();
+        assert_eq!(count(&sink.connection, "users"), users.len());
+    }
+
+    #[test]
+    fn test_unknown_user_violates_foreign_key() {
+        let dir = tempfile::tempdir().unwrap();
+        let (registry, users) = create_test_world();
+        let mut sink =
+            SqliteEventSink::create(dir.path().join("simulation.db"), &registry, &users, OutputFieldConfig::default())
+                .unwrap();
+
+        let mut event = create_event(&users, true);
+        event.user_id = crate::types::UserId::new();
+        assert!(sink.write_event(&event).is_err());
+    }
+}
@@ -175,7 +175,7 @@ impl ConsolidatedStatistics {
     /// Generate a detailed breakdown of all event types with counts and percentages (backward compatibility)
     pub fn detailed_breakdown(&self) -> String {
         let mut breakdown = String::new();
-        breakdown.push_str(&format!("=== Event Type Breakdown ===\n"));
+        breakdown.push_str("=== Event Type Breakdown ===\n");
         breakdown.push_str(&format!("Total Events Generated: {}\n\n", self.total_events));
 
         breakdown.push_str("Standard Event Types:\n");
@@ -639,7 +639,7 @@ impl ConsolidatedStatistics {
         // One-line summary for easy parsing (Requirement 3.3)
         output.push_str("💡 Summary: ");
         output.push_str(&self.generate_compact_summary());
-        output.push_str("\n");
+        output.push('\n');
 
         output
     }
@@ -692,7 +692,7 @@ impl fmt::Display for ConsolidatedStatistics {
 }
 
 /// Detailed statistics about event types generated during simulation
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct EventTypeStatistics {
     /// Total number of events generated
     pub total_events: usize,
@@ -854,23 +854,6 @@ impl EventTypeStatistics {
     }
 }
 
-impl Default for EventTypeStatistics {
-    fn default() -> Self {
-        Self {
-            total_events: 0,
-            success_events: 0,
-            failure_events: 0,
-            invalid_badge_events: 0,
-            outside_hours_events: 0,
-            suspicious_events: 0,
-            curious_events: 0,
-            impossible_traveler_events: 0,
-            badge_reader_failure_events: 0,
-            night_shift_events: 0,
-        }
-    }
-}
-
 impl EventTypeStatistics {
     /// Generate a summary of event type breakdowns with counts and percentages
     pub fn summary(&self) -> String {
@@ -888,7 +871,7 @@ impl EventTypeStatistics {
     /// Generate a detailed breakdown of all event types with counts and percentages
     pub fn detailed_breakdown(&self) -> String {
         let mut breakdown = String::new();
-        breakdown.push_str(&format!("=== Event Type Breakdown ===\n"));
+        breakdown.push_str("=== Event Type Breakdown ===\n");
         breakdown.push_str(&format!("Total Events Generated: {}\n\n", self.total_events));
 
         breakdown.push_str("Standard Event Types:\n");
@@ -0,0 +1,396 @@
+//! Syslog network event sink
+//!
+//! This module sends access events as RFC 5424 syslog messages over UDP or TCP, for
+//! exercising a collector pipeline (a local rsyslog or a test listener) end to end.
+//!
+//! Each message carries the event time as its timestamp, the badge reader's `RoomId` as
+//! its hostname and the event type as its MSGID. The event itself is rendered with the
+//! configured `EventFormatter` as the message body, so CEF or LEEF can be shipped over
+//! syslog. The event identifiers are carried as structured data elements, along with the
+//! failure reason and the `EventMetadata` flags when the output field configuration
+//! includes them. TCP messages use octet-counting framing (RFC 6587).
+//!
+//! Messages can optionally be paced by simulated time: with a speed-up factor of 60, the
+//! gap between two events one simulated minute apart is one real second.
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use std::fmt::Write as _;
+use std::io::{BufWriter, Write};
+use std::net::{TcpStream, ToSocketAddrs, UdpSocket};
+use std::thread;
+use std::time::Instant;
+
+use crate::events::{AccessEvent, EventFormatter, EventMetadata};
+use crate::simulation::{EventSink, SimulationError, SimulationResult};
+use crate::types::config::OutputFieldConfig;
+use crate::types::{EventType, SyslogProtocol};
+
+/// Syslog facility for security/authorization messages
+const FACILITY_AUTH: u8 = 4;
+
+/// Application name reported in each message
+const APP_NAME: &str = "badge-access";
+
+/// Enterprise number used in structured data IDs (reserved for documentation, RFC 5612)
+const ENTERPRISE_NUMBER: u32 = 32473;
+
+/// Connection to the syslog collector
+#[derive(Debug)]
+enum Transport {
+    Udp(UdpSocket),
+    Tcp(BufWriter<TcpStream>),
+}
+
+/// Paces messages by the simulated time between events
+#[derive(Debug)]
+struct Pacer {
+    speed: f64,
+    start: Option<(DateTime<Utc>, Instant)>,
+}
+
+impl Pacer {
+    /// Sleep until the real time that corresponds to the event's simulated time
+    fn wait_for(&mut self, timestamp: DateTime<Utc>) {
+        let (first_timestamp, started) = *self.start.get_or_insert((timestamp, Instant::now()));
+        let simulated = (timestamp - first_timestamp).to_std().unwrap_or_default();
+        let target = started + simulated.div_f64(self.speed);
+        let now = Instant::now();
+        if target > now {
+            thread::sleep(target - now);
+        }
+    }
+}
+
+/// Event sink that sends each event as an RFC 5424 syslog message
+#[derive(Debug)]
+pub struct SyslogEventSink {
+    transport: Transport,
+    formatter: EventFormatter,
+    pacer: Option<Pacer>,
+    events_sent: usize,
+}
+
+impl SyslogEventSink {
+    /// Connect to a syslog collector at `target` (host:port) over the given transport
+    pub fn connect(
+        target: &str,
+        protocol: SyslogProtocol,
+        formatter: EventFormatter,
+    ) -> SimulationResult<Self> {
+        let connect_error = |e: std::io::Error| {
+            SimulationError::event_generation_error(format!(
+                "Failed to connect to syslog collector {} over {}: {}",
+                target, protocol, e
+            ))
+        };
+
+        let transport = match protocol {
+            SyslogProtocol::Udp => {
+                let address = target
+                    .to_socket_addrs()
+                    .map_err(connect_error)?
+                    .next()
+                    .ok_or_else(|| {
+                        SimulationError::event_generation_error(format!(
+                            "Syslog collector {} did not resolve to an address",
+                            target
+                        ))
+                    })?;
+                let bind_address = if address.is_ipv4() { "0.0.0.0:0" } else { "[::]:0" };
+                let socket = UdpSocket::bind(bind_address).map_err(connect_error)?;
+                socket.connect(address).map_err(connect_error)?;
+                Transport::Udp(socket)
+            }
+            SyslogProtocol::Tcp => {
+                Transport::Tcp(BufWriter::new(TcpStream::connect(target).map_err(connect_error)?))
+            }
+        };
+
+        Ok(Self { transport, formatter, pacer: None, events_sent: 0 })
+    }
+
+    /// Pace messages by simulated time, sped up by `speed` (simulated seconds per real second)
+    pub fn with_pacing(mut self, speed: f64) -> Self {
+        self.pacer = Some(Pacer { speed, start: None });
+        self
+    }
+
+    /// Get the number of messages sent so far
+    pub fn events_sent(&self) -> usize {
+        self.events_sent
+    }
+
+    /// Render an event as an RFC 5424 syslog message (without transport framing)
+    pub fn format_message(&self, event: &AccessEvent) -> SimulationResult<String> {
+        let priority = FACILITY_AUTH * 8 + syslog_severity(event.event_type);
+        let timestamp = event.timestamp.to_rfc3339_opts(SecondsFormat::Millis, true);
+        let body = self.formatter.format_event(event)?;
+
+        Ok(format!(
+            "<{}>1 {} {} {} - {} {} {}",
+            priority,
+            timestamp,
+            event.room_id,
+            APP_NAME,
+            message_id(event.event_type),
+            structured_data(event, self.formatter.field_config()),
+            body
+        ))
+    }
+}
+
+impl EventSink for SyslogEventSink {
+    fn write_event(&mut self, event: &AccessEvent) -> SimulationResult<()> {
+        if let Some(pacer) = &mut self.pacer {
+            pacer.wait_for(event.timestamp);
+        }
+
+        let message = self.format_message(event)?;
+        match &mut self.transport {
+            Transport::Udp(socket) => {
+                socket.send(message.as_bytes())?;
+            }
+            Transport::Tcp(stream) => {
+                write!(stream, "{} {}", message.len(), message)?;
+                if self.pacer.is_some() {
+                    stream.flush()?;
+                }
+            }
+        }
+        self.events_sent += 1;
+        Ok(())
+    }
+
+    fn end_day(&mut self, _date: chrono::NaiveDate) -> SimulationResult<()> {
+        if let Transport::Tcp(stream) = &mut self.transport {
+            stream.flush()?;
+        }
+        Ok(())
+    }
+
+    fn finish(&mut self) -> SimulationResult<()> {
+        if let Transport::Tcp(stream) = &mut self.transport {
+            stream.flush()?;
+        }
+        Ok(())
+    }
+}
+
+/// Map an event type to a syslog severity
+fn syslog_severity(event_type: EventType) -> u8 {
+    match event_type {
+        EventType::Success => 6,
+        EventType::Failure => 5,
+        EventType::InvalidBadge | EventType::OutsideHours => 4,
+        EventType::Suspicious => 2,
+    }
+}
+
+/// Map an event type to a syslog MSGID
+fn message_id(event_type: EventType) -> &'static str {
+    match event_type {
+        EventType::Success => "SUCCESS",
+        EventType::Failure => "FAILURE",
+        EventType::InvalidBadge => "INVALID_BADGE",
+        EventType::OutsideHours => "OUTSIDE_HOURS",
+        EventType::Suspicious => "SUSPICIOUS",
+    }
+}
+
+/// Build the structured data elements for an event
+///
+/// The `access` element carries the event identifiers and outcome; the `meta` element
+/// carries the `EventMetadata` flags. The failure reason and the `meta` element are answer
+/// key labels, so like the message body they follow the field configuration.
+fn structured_data(event: &AccessEvent, field_config: &OutputFieldConfig) -> String {
+    let mut access = vec![
+        ("user", event.user_id.to_string()),
+        ("room", event.room_id.to_string()),
+        ("building", event.building_id.to_string()),
+        ("location", event.location_id.to_string()),
+        ("success", event.success.to_string()),
+    ];
+    if let Some(reason) = event.failure_reason.filter(|_| field_config.include_failure_reason || field_config.include_all) {
+        access.push(("failureReason", reason.to_string()));
+    }
+
+    let mut data = sd_element("access", &access);
+    if let Some(metadata) = event.metadata.as_ref().filter(|_| field_config.include_metadata || field_config.include_all) {
+        data.push_str(&sd_element("meta", &metadata_params(metadata)));
+    }
+    data
+}
+
+/// Structured data parameters for event metadata
+fn metadata_params(metadata: &EventMetadata) -> Vec<(&'static str, String)> {
+    let mut params = vec![
+        ("curiousAttempt", metadata.is_curious_attempt.to_string()),
+        ("impossibleTraveler", metadata.is_impossible_traveler.to_string()),
+        ("badgeReaderFailure", metadata.is_badge_reader_failure.to_string()),
+        ("nightShift", metadata.is_night_shift_event.to_string()),
+    ];
+    if let Some(retry) = metadata.retry_attempt_number {
+        params.push(("retryAttempt", retry.to_string()));
+    }
+    if let Some(violation) = metadata.travel_time_violation {
+        params.push(("travelTimeViolationSeconds", violation.num_seconds().to_string()));
+    }
+    if let Some(distance) = metadata.geographical_distance {
+        params.push(("geographicalDistanceKm", distance.to_string()));
+    }
+    params
+}
+
+/// Render a structured data element with escaped parameter values
+fn sd_element(name: &str, params: &[(&str, String)]) -> String {
+    let mut element = format!("[{}@{}", name, ENTERPRISE_NUMBER);
+    for (key, value) in params {
+        let _ = write!(element, " {}=\"{}\"", key, escape_param_value(value));
+    }
+    element.push(']');
+    element
+}
+
+/// Escape `"`, `\` and `]` in a structured data parameter value
+fn escape_param_value(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '"' | '\\' | ']') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{BuildingId, FailureReason, LocationId, OutputFormat, RoomId, UserId};
+    use chrono::TimeZone;
+    use std::io::Read;
+    use std::net::TcpListener;
+    use std::time::Duration;
+
+    fn create_test_event() -> AccessEvent {
+        AccessEvent::new(
+            Utc.with_ymd_and_hms(2024, 1, 15, 10, 30, 0).unwrap(),
+            UserId::new(),
+            RoomId::new(),
+            BuildingId::new(),
+            LocationId::new(),
+            true,
+            EventType::Success,
+        )
+    }
+
+    fn json_formatter() -> EventFormatter {
+        EventFormatter::new(OutputFormat::Json, OutputFieldConfig::default())
+    }
+
+    #[test]
+    fn test_udp_message_format() {
+        let listener = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let target = listener.local_addr().unwrap().to_string();
+        let mut sink = SyslogEventSink::connect(&target, SyslogProtocol::Udp, json_formatter()).unwrap();
+
+        let event = create_test_event();
+        sink.write_event(&event).unwrap();
+        sink.finish().unwrap();
+        assert_eq!(sink.events_sent(), 1);
+
+        let mut buffer = [0u8; 4096];
+        let length = listener.recv(&mut buffer).unwrap();
+        let message = std::str::from_utf8(&buffer[..length]).unwrap();
+
+        let expected_prefix = format!(
+            "<38>1 2024-01-15T10:30:00.000Z {} badge-access - SUCCESS [access@32473 user=\"{}\"",
+            event.room_id, event.user_id
+        );
+        assert!(message.starts_with(&expected_prefix), "unexpected message: {}", message);
+        assert!(!message.contains("[meta@"));
+        assert!(message.ends_with(&json_formatter().format_event(&event).unwrap()));
+    }
+
+    #[test]
+    fn test_metadata_flags_in_structured_data() {
+        let listener = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let target = listener.local_addr().unwrap().to_string();
+        let field_config = OutputFieldConfig { include_failure_reason: true, include_metadata: true, ..Default::default() };
+        let sink = SyslogEventSink::connect(&target, SyslogProtocol::Udp, EventFormatter::new(OutputFormat::Json, field_config))
+            .unwrap();
+
+        let mut event = create_test_event();
+        event.success = false;
+        event.event_type = EventType::Suspicious;
+        event.failure_reason = Some(FailureReason::ImpossibleTraveler);
+        event.metadata = Some(EventMetadata::impossible_traveler(chrono::Duration::hours(3), 4120.5));
+
+        let message = sink.format_message(&event).unwrap();
+        assert!(message.starts_with("<34>1 "));
+        assert!(message.contains(" SUSPICIOUS [access@32473 "));
+        assert!(message.contains("failureReason=\"Impossible Traveler\""));
+        assert!(message.contains(
+            "[meta@32473 curiousAttempt=\"false\" impossibleTraveler=\"true\" badgeReaderFailure=\"false\" nightShift=\"false\" travelTimeViolationSeconds=\"10800\" geographicalDistanceKm=\"4120.5\"]"
+        ));
+
+        // Without the field toggles the labels stay out, as they do in the message body
+        let default_sink = SyslogEventSink::connect(&target, SyslogProtocol::Udp, json_formatter()).unwrap();
+        let message = default_sink.format_message(&event).unwrap();
+        assert!(!message.contains("failureReason="));
+        assert!(!message.contains("[meta@"));
+    }
+
+    #[test]
+    fn test_tcp_octet_counting() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let target = listener.local_addr().unwrap().to_string();
+        let reader = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut received = String::new();
+            stream.read_to_string(&mut received).unwrap();
+            received
+        });
+
+        let mut sink = SyslogEventSink::connect(&target, SyslogProtocol::Tcp, json_formatter()).unwrap();
+        let events = [create_test_event(), create_test_event()];
+        for event in &events {
+            sink.write_event(event).unwrap();
+        }
+        sink.finish().unwrap();
+        let expected: String = events
+            .iter()
+            .map(|event| {
+                let message = sink.format_message(event).unwrap();
+                format!("{} {}", message.len(), message)
+            })
+            .collect();
+        drop(sink);
+
+        assert_eq!(reader.join().unwrap(), expected);
+    }
+
+    #[test]
+    fn test_pacing_follows_simulated_time() {
+        let listener = UdpSocket::bind("127.0.0.1:0").unwrap();
+        let target = listener.local_addr().unwrap().to_string();
+        // 1000x speed-up: 100 simulated seconds take 100ms
+        let mut sink = SyslogEventSink::connect(&target, SyslogProtocol::Udp, json_formatter())
+            .unwrap()
+            .with_pacing(1000.0);
+
+        let first = create_test_event();
+        let mut second = create_test_event();
+        second.timestamp = first.timestamp + chrono::Duration::seconds(100);
+
+        let started = Instant::now();
+        sink.write_event(&first).unwrap();
+        sink.write_event(&second).unwrap();
+        assert!(started.elapsed() >= Duration::from_millis(100));
+    }
+
+    #[test]
+    fn test_escape_param_value() {
+        assert_eq!(escape_param_value(r#"a"b\c]d"#), r#"a\"b\\c\]d"#);
+    }
+}
@@ -3,8 +3,11 @@
 //! This module contains time management and time acceleration logic.
 
 // Error handling types available if needed
+use crate::facility::Location;
+use crate::simulation::calendar::WorkCalendar;
 use crate::types::{BuildingId, LocationId, RoomId};
 use chrono::{DateTime, Duration, Timelike, Utc};
+use chrono_tz::Tz;
 use rand::Rng;
 use tracing::{debug, info, instrument, warn};
 
@@ -15,13 +18,21 @@ pub struct TimeManager {
     acceleration_factor: f64,
     /// When the simulation started in simulated time
     simulation_start: DateTime<Utc>,
+    /// Work calendar deciding which days have business hours
+    calendar: WorkCalendar,
 }
 
 impl TimeManager {
     /// Create a new TimeManager for batch processing
     pub fn new() -> Self {
         info!("Initializing time manager for batch processing");
-        Self { acceleration_factor: 1.0, simulation_start: Utc::now() }
+        Self { acceleration_factor: 1.0, simulation_start: Utc::now(), calendar: WorkCalendar::default() }
+    }
+
+    /// Use the given work calendar for business hours checks
+    pub fn with_calendar(mut self, calendar: WorkCalendar) -> Self {
+        self.calendar = calendar;
+        self
     }
 
     /// Get the current simulated time
@@ -46,7 +57,7 @@ impl TimeManager {
         let real_duration_to_subtract = Duration::milliseconds(
             (duration.num_milliseconds() as f64 / self.acceleration_factor) as i64,
         );
-        self.simulation_start = self.simulation_start - real_duration_to_subtract;
+        self.simulation_start -= real_duration_to_subtract;
 
         debug!(
             "Advanced simulation time by {} (real time adjustment: {})",
@@ -54,16 +65,28 @@ impl TimeManager {
         );
     }
 
-    /// Check if the given time is during business hours (9 AM - 5 PM, every day)
+    /// Check if the given time is during local business hours in the given time zone
+    ///
+    /// Business hours are 9 AM - 5 PM on work days and 9 AM - 1 PM on half-days; weekends
+    /// and holidays from the work calendar have none.
     #[instrument(skip(self))]
-    pub fn is_business_hours(&self, timestamp: DateTime<Utc>) -> bool {
-        let hour = timestamp.hour();
-
-        // Business hours: 9 AM to 5 PM (17:00) - every day is a work day
-        (9..17).contains(&hour)
+    pub fn is_business_hours(&self, timestamp: DateTime<Utc>, time_zone: Tz) -> bool {
+        let local = timestamp.with_timezone(&time_zone);
+        self.calendar
+            .day_kind(local.date_naive(), None)
+            .business_hours()
+            .is_some_and(|hours| hours.contains(&local.hour()))
     }
 
-
+    /// Check if the given time is during business hours at a location, including the
+    /// location's own holidays and half-days
+    pub fn is_location_business_hours(&self, timestamp: DateTime<Utc>, location: &Location) -> bool {
+        let local = location.local_time(timestamp);
+        self.calendar
+            .day_kind(local.date_naive(), Some(location))
+            .business_hours()
+            .is_some_and(|hours| hours.contains(&local.hour()))
+    }
 
     /// Calculate realistic travel time between two rooms
     ///
@@ -72,6 +95,7 @@ impl TimeManager {
     /// - Same building: 30 seconds to 3 minutes
     /// - Same location, different building: 2-10 minutes
     /// - Different locations: 4-12 hours (including travel time)
+    #[allow(clippy::too_many_arguments)]
     pub fn calculate_travel_time<R: Rng>(
         &self,
         from_room: Option<RoomId>,
@@ -138,18 +162,47 @@ mod tests {
     fn test_business_hours_detection() {
         let tm = TimeManager::default();
 
-        // Test business hours (9 AM - 5 PM, every day)
+        // Test business hours (9 AM - 5 PM on work days)
         let monday_10am = Utc.with_ymd_and_hms(2024, 1, 8, 10, 0, 0).unwrap(); // Monday
         let friday_2pm = Utc.with_ymd_and_hms(2024, 1, 12, 14, 0, 0).unwrap(); // Friday
         let saturday_10am = Utc.with_ymd_and_hms(2024, 1, 13, 10, 0, 0).unwrap(); // Saturday
         let monday_8am = Utc.with_ymd_and_hms(2024, 1, 8, 8, 0, 0).unwrap(); // Before business hours
         let monday_6pm = Utc.with_ymd_and_hms(2024, 1, 8, 18, 0, 0).unwrap(); // After business hours
 
-        assert!(tm.is_business_hours(monday_10am));
-        assert!(tm.is_business_hours(friday_2pm));
-        assert!(tm.is_business_hours(saturday_10am)); // Saturday is now a work day
-        assert!(!tm.is_business_hours(monday_8am));
-        assert!(!tm.is_business_hours(monday_6pm));
+        assert!(tm.is_business_hours(monday_10am, Tz::UTC));
+        assert!(tm.is_business_hours(friday_2pm, Tz::UTC));
+        assert!(!tm.is_business_hours(saturday_10am, Tz::UTC)); // Weekends have no business hours
+        assert!(!tm.is_business_hours(monday_8am, Tz::UTC));
+        assert!(!tm.is_business_hours(monday_6pm, Tz::UTC));
+
+        // Hours are local: 10 AM UTC is 7 PM in Tokyo and 2 AM in Seattle
+        assert!(!tm.is_business_hours(monday_10am, chrono_tz::Asia::Tokyo));
+        assert!(!tm.is_business_hours(monday_10am, chrono_tz::America::Los_Angeles));
+        assert!(tm.is_business_hours(monday_6pm, chrono_tz::America::Los_Angeles));
+    }
+
+    #[test]
+    fn test_location_business_hours_follow_calendar() {
+        use crate::types::WorkCalendarConfig;
+        use chrono::NaiveDate;
+
+        let new_years_eve = NaiveDate::from_ymd_opt(2024, 12, 31).unwrap();
+        let tm = TimeManager::new().with_calendar(WorkCalendar::new(WorkCalendarConfig {
+            weekend_days: Vec::new(),
+            ..Default::default()
+        }));
+        let location = Location::new("London".to_string(), (51.5074, -0.1278))
+            .with_time_zone(chrono_tz::Europe::London)
+            .with_holidays(vec![NaiveDate::from_ymd_opt(2024, 12, 26).unwrap()], vec![new_years_eve]);
+
+        let saturday_10am = Utc.with_ymd_and_hms(2024, 12, 28, 10, 0, 0).unwrap();
+        assert!(tm.is_location_business_hours(saturday_10am, &location));
+        let boxing_day_10am = Utc.with_ymd_and_hms(2024, 12, 26, 10, 0, 0).unwrap();
+        assert!(!tm.is_location_business_hours(boxing_day_10am, &location));
+        assert!(tm.is_business_hours(boxing_day_10am, Tz::UTC));
+        let half_day_2pm = Utc.with_ymd_and_hms(2024, 12, 31, 14, 0, 0).unwrap();
+        assert!(!tm.is_location_business_hours(half_day_2pm, &location));
+        assert!(tm.is_location_business_hours(half_day_2pm - chrono::Duration::hours(2), &location));
     }
 
     #[test]
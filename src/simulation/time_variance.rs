@@ -5,13 +5,13 @@
 //! scheduled times.
 
 use chrono::{DateTime, Duration, Utc};
-use rand::rngs::ThreadRng;
-use rand::{thread_rng, Rng};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 
 use crate::events::access_event::AccessEvent;
 
 /// Default variance window in seconds (300.0 seconds = 5 minutes forward-only)
-const DEFAULT_VARIANCE_WINDOW_SECONDS: f64 = 300.0;
+pub const DEFAULT_VARIANCE_WINDOW_SECONDS: f64 = 300.0;
 
 /// Maximum timestamp gap in milliseconds for random timestamp separation (500ms)
 const MAX_TIMESTAMP_GAP_MS: i64 = 500;
@@ -24,13 +24,24 @@ const MAX_TIMESTAMP_GAP_MS: i64 = 500;
 #[derive(Debug)]
 pub struct TimeVariance {
     variance_window_seconds: f64,
-    rng: ThreadRng,
+    rng: StdRng,
 }
 
 impl TimeVariance {
     /// Create a new TimeVariance instance with default settings
     pub fn new() -> Self {
-        Self { variance_window_seconds: DEFAULT_VARIANCE_WINDOW_SECONDS, rng: thread_rng() }
+        Self {
+            variance_window_seconds: DEFAULT_VARIANCE_WINDOW_SECONDS,
+            rng: StdRng::from_entropy(),
+        }
+    }
+
+    /// Create a new TimeVariance instance with a specific seed for reproducible results
+    pub fn with_seed(seed: u64) -> Self {
+        Self {
+            variance_window_seconds: DEFAULT_VARIANCE_WINDOW_SECONDS,
+            rng: StdRng::seed_from_u64(seed),
+        }
     }
 
     /// Apply forward-only variance to a scheduled time
@@ -136,6 +147,20 @@ mod tests {
         assert!(some_count > 0, "Expected some events to be kept");
     }
 
+    #[test]
+    fn test_seeded_variance_is_reproducible() {
+        let mut variance_a = TimeVariance::with_seed(42);
+        let mut variance_b = TimeVariance::with_seed(42);
+        let scheduled_time = Utc.with_ymd_and_hms(2024, 1, 15, 10, 30, 0).unwrap();
+
+        for _ in 0..50 {
+            assert_eq!(
+                variance_a.apply_variance(scheduled_time),
+                variance_b.apply_variance(scheduled_time)
+            );
+        }
+    }
+
     #[test]
     fn test_default_implementation() {
         let variance = TimeVariance::default();
@@ -0,0 +1,654 @@
+// Streaming detection
+//
+// Reads badge events from files or stdin, as JSON lines or CSV (optionally compressed),
+// one event at a time in the order they were written, and runs the detection rules on
+// each event as it arrives. Alerts are written as JSON lines the moment a rule fires.
+// Only a small sliding state is kept per user (the last event, a denied attempt waiting
+// for a possible retry, the denials of the last day and a few recent off-hours badge-ins),
+// and users who stay idle longer
+// than the rules look back are evicted, so memory follows the number of active users
+// rather than the size of the input.
+
+use std::collections::{HashMap, VecDeque};
+use std::io::{self, BufRead, Write};
+
+use amzn_career_pathway_activity_rust::events::{parse_csv_row, AccessEvent, EventGenerator};
+use amzn_career_pathway_activity_rust::facility::LocationRegistry;
+use amzn_career_pathway_activity_rust::simulation::{open_input_file, TimeManager, WorkCalendar};
+use amzn_career_pathway_activity_rust::types::{
+    BuildingId, EventType, LocationId, RoomId, SecurityLevel, SimulationConfig, UserId,
+};
+use chrono::{DateTime, Duration, Timelike, Utc};
+use serde::Serialize;
+use serde_json::Value;
+use tracing::warn;
+use uuid::Uuid;
+
+use crate::analysis::{
+    cloned_badge_anomaly, find_impossible_travel, security_weight, AnomalyEvidence, UserAnomaly,
+    READER_RETRY_WINDOW_SECONDS,
+};
+
+/// Input name that reads events from stdin
+pub const STDIN_INPUT: &str = "-";
+
+/// Denials older than this no longer count towards a user's unauthorized attempts
+const DENIAL_WINDOW_HOURS: i64 = 24;
+
+/// Users idle for this long are evicted; no rule looks back further
+const IDLE_EVICTION_HOURS: i64 = 24;
+
+/// Minutes of event time between sweeps for idle users and unanswered denials
+const EVICTION_INTERVAL_MINUTES: i64 = 15;
+
+/// Most denials kept per user within the window
+const MAX_RECENT_DENIALS: usize = 256;
+
+/// Weighted denials within the window from which a user is reported as curious
+const UNAUTHORIZED_WEIGHT_THRESHOLD: f64 = 2.0;
+
+/// Distinct rooms a user must be refused at within the window to be reported as curious
+const MIN_CURIOUS_ROOMS: usize = 2;
+
+/// Successful off-hours badge-ins within the denial window that mark a user as night staff
+const NIGHT_STAFF_OFF_HOURS_EVENTS: usize = 6;
+
+/// Invalid lines reported individually before only being counted
+const MAX_REPORTED_INVALID_LINES: usize = 10;
+
+/// Sliding state of one user
+#[derive(Debug)]
+struct UserState {
+    /// Last event, for the location and time of the next impossible travel check
+    last_event: AccessEvent,
+    /// Denied attempt waiting to see whether the user retries it successfully
+    pending_denial: Option<AccessEvent>,
+    /// Unauthorized attempts within the denial window, oldest first
+    recent_denials: VecDeque<AccessEvent>,
+    /// When the user was last reported as curious
+    curious_alerted_at: Option<DateTime<Utc>>,
+    /// Latest successful badge-ins outside business hours, oldest first
+    off_hours_events: VecDeque<DateTime<Utc>>,
+}
+
+impl UserState {
+    fn new(event: AccessEvent) -> Self {
+        Self {
+            last_event: event,
+            pending_denial: None,
+            recent_denials: VecDeque::new(),
+            curious_alerted_at: None,
+            off_hours_events: VecDeque::new(),
+        }
+    }
+
+    /// Track off-hours badge-ins, and hold a denial unless it is a night staff member's off-hours refusal
+    fn note_event(&mut self, event: AccessEvent, business_hours: bool) {
+        if event.success {
+            if !business_hours {
+                self.off_hours_events.push_back(event.timestamp);
+                if self.off_hours_events.len() > NIGHT_STAFF_OFF_HOURS_EVENTS {
+                    self.off_hours_events.pop_front();
+                }
+            }
+        } else if business_hours || !self.is_night_staff(event.timestamp) {
+            self.pending_denial = Some(event);
+        }
+    }
+
+    /// Whether the user has been working outside business hours within the denial window
+    fn is_night_staff(&self, now: DateTime<Utc>) -> bool {
+        self.off_hours_events.len() >= NIGHT_STAFF_OFF_HOURS_EVENTS
+            && self
+                .off_hours_events
+                .front()
+                .is_some_and(|oldest| now - *oldest <= Duration::hours(DENIAL_WINDOW_HOURS))
+    }
+}
+
+/// Counters of a streaming detection run
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct StreamStatistics {
+    pub events: u64,
+    /// Events whose room or location is missing from the facility layout
+    pub unresolved_events: u64,
+    pub invalid_lines: u64,
+    pub alerts: u64,
+    pub evicted_users: u64,
+    pub tracked_users: usize,
+    pub peak_tracked_users: usize,
+}
+
+/// Detector that applies the rules to each event as it arrives
+///
+/// Impossible travel is checked against the user's last event with the simulator's
+/// travel-time model. A denied attempt is held until the user's next event or the next
+/// sweep: a success at the same room within the retry window marks it as a reader retry,
+/// otherwise it counts as unauthorized. Night staff, recognised by their successful
+/// badge-ins outside business hours within the last day, are routinely refused at
+/// time-restricted doors, so their off-hours denials are ignored. Unauthorized attempts
+/// within the last day are weighted by room security level, the n-th attempt at a room
+/// adding 1/n of its weight, and a user refused at several rooms whose total reaches the
+/// threshold is reported once per window.
+#[derive(Debug)]
+pub struct StreamingDetector {
+    travel_model: EventGenerator,
+    location_registry: LocationRegistry,
+    calendar: WorkCalendar,
+    users: HashMap<String, UserState>,
+    next_sweep: Option<DateTime<Utc>>,
+    statistics: StreamStatistics,
+}
+
+impl StreamingDetector {
+    pub fn new(config: &SimulationConfig, location_registry: LocationRegistry) -> Self {
+        Self {
+            travel_model: EventGenerator::new(config.clone(), location_registry.clone(), TimeManager::new()),
+            location_registry,
+            calendar: WorkCalendar::new(config.calendar.clone()),
+            users: HashMap::new(),
+            next_sweep: None,
+            statistics: StreamStatistics::default(),
+        }
+    }
+
+    pub fn statistics(&self) -> &StreamStatistics {
+        &self.statistics
+    }
+
+    /// Run the rules on the next event of the user identified by `user_key`, returning the alerts it raised
+    pub fn observe(&mut self, user_key: &str, event: AccessEvent) -> Vec<UserAnomaly> {
+        // Ground-truth labels never reach the rules
+        let event = AccessEvent { metadata: None, failure_reason: None, ..event };
+        self.statistics.events += 1;
+        if self.location_registry.get_location(event.location_id).is_none()
+            || self.location_registry.get_room(event.room_id).is_none()
+        {
+            self.statistics.unresolved_events += 1;
+        }
+
+        let mut alerts = Vec::new();
+        if self.next_sweep.is_none_or(|next_sweep| event.timestamp >= next_sweep) {
+            alerts.extend(self.sweep(event.timestamp));
+            self.next_sweep = Some(event.timestamp + Duration::minutes(EVICTION_INTERVAL_MINUTES));
+        }
+
+        let business_hours = self.is_business_hours(&event);
+        match self.users.get_mut(user_key) {
+            Some(state) => {
+                let previous = std::mem::replace(&mut state.last_event, event.clone());
+                if let Some(finding) =
+                    find_impossible_travel(&self.travel_model, &self.location_registry, previous, event.clone())
+                {
+                    alerts.push(cloned_badge_anomaly(user_key.to_string(), finding, Vec::new()));
+                }
+
+                if let Some(denial) = state.pending_denial.take() {
+                    let is_retry = event.success
+                        && event.room_id == denial.room_id
+                        && event.timestamp - denial.timestamp <= Duration::seconds(READER_RETRY_WINDOW_SECONDS);
+                    if !is_retry {
+                        alerts.extend(record_denial(&self.location_registry, user_key, state, denial));
+                    }
+                }
+                state.note_event(event, business_hours);
+            }
+            None => {
+                let mut state = UserState::new(event.clone());
+                state.note_event(event, business_hours);
+                self.users.insert(user_key.to_string(), state);
+                self.statistics.tracked_users = self.users.len();
+                self.statistics.peak_tracked_users = self.statistics.peak_tracked_users.max(self.users.len());
+            }
+        }
+
+        self.statistics.alerts += alerts.len() as u64;
+        alerts
+    }
+
+    /// Settle the denials still waiting for a retry at the end of the input
+    pub fn finish(&mut self) -> Vec<UserAnomaly> {
+        let mut alerts = Vec::new();
+        for (user_key, state) in self.users.iter_mut() {
+            if let Some(denial) = state.pending_denial.take() {
+                alerts.extend(record_denial(&self.location_registry, user_key, state, denial));
+            }
+        }
+        self.statistics.alerts += alerts.len() as u64;
+        alerts
+    }
+
+    /// Settle denials whose retry window has passed and evict idle users
+    fn sweep(&mut self, now: DateTime<Utc>) -> Vec<UserAnomaly> {
+        let mut alerts = Vec::new();
+        let retry_window = Duration::seconds(READER_RETRY_WINDOW_SECONDS);
+        for (user_key, state) in self.users.iter_mut() {
+            if let Some(denial) = state.pending_denial.take_if(|denial| now - denial.timestamp > retry_window) {
+                alerts.extend(record_denial(&self.location_registry, user_key, state, denial));
+            }
+        }
+
+        let before = self.users.len();
+        let idle_timeout = Duration::hours(IDLE_EVICTION_HOURS);
+        self.users.retain(|_, state| now - state.last_event.timestamp <= idle_timeout);
+        if self.users.capacity() > 2 * self.users.len().max(1024) {
+            self.users.shrink_to_fit();
+        }
+        self.statistics.evicted_users += (before - self.users.len()) as u64;
+        self.statistics.tracked_users = self.users.len();
+        alerts
+    }
+
+    /// Check if an event falls within business hours of a work day at its location
+    fn is_business_hours(&self, event: &AccessEvent) -> bool {
+        let location = self.location_registry.get_location(event.location_id);
+        let local_time = match location {
+            Some(location) => location.local_time(event.timestamp).naive_local(),
+            None => event.local_timestamp().naive_local(),
+        };
+        self.calendar
+            .day_kind(local_time.date(), location)
+            .business_hours()
+            .is_some_and(|hours| hours.contains(&local_time.hour()))
+    }
+}
+
+/// Add an unauthorized attempt to the user's window, returning an alert when it takes them over the threshold
+fn record_denial(
+    location_registry: &LocationRegistry,
+    user_key: &str,
+    state: &mut UserState,
+    denial: AccessEvent,
+) -> Option<UserAnomaly> {
+    let window = Duration::hours(DENIAL_WINDOW_HOURS);
+    let detected_at = denial.timestamp;
+    state.recent_denials.push_back(denial);
+    while state.recent_denials.len() > MAX_RECENT_DENIALS
+        || state.recent_denials.front().is_some_and(|oldest| detected_at - oldest.timestamp > window)
+    {
+        state.recent_denials.pop_front();
+    }
+
+    // The n-th attempt at a room adds its security weight divided by n
+    let mut attempts_per_room: HashMap<RoomId, usize> = HashMap::new();
+    let mut weighted_attempts = 0.0;
+    for attempt in &state.recent_denials {
+        let security_level =
+            location_registry.get_room(attempt.room_id).map_or(SecurityLevel::Standard, |room| room.security_level);
+        let attempts = attempts_per_room.entry(attempt.room_id).or_default();
+        *attempts += 1;
+        weighted_attempts += security_weight(security_level) / *attempts as f64;
+    }
+    let rooms = attempts_per_room.len();
+
+    let recently_alerted = state.curious_alerted_at.is_some_and(|alerted_at| detected_at - alerted_at <= window);
+    if weighted_attempts < UNAUTHORIZED_WEIGHT_THRESHOLD || rooms < MIN_CURIOUS_ROOMS || recently_alerted {
+        return None;
+    }
+    state.curious_alerted_at = Some(detected_at);
+
+    let severity = match weighted_attempts {
+        weighted if weighted >= 6.0 => "high",
+        weighted if weighted >= 4.0 => "medium",
+        _ => "low",
+    };
+    let attempts: Vec<AccessEvent> = state.recent_denials.iter().cloned().collect();
+    Some(UserAnomaly {
+        user_id: user_key.to_string(),
+        anomaly_type: "curious_access".to_string(),
+        details: format!(
+            "{} unauthorized attempt(s) at {} room(s) in the last {} hours, weighing {:.1}",
+            attempts.len(),
+            rooms,
+            DENIAL_WINDOW_HOURS,
+            weighted_attempts
+        ),
+        issue: "Repeated attempts to access unauthorized rooms".to_string(),
+        severity: severity.to_string(),
+        timestamp: detected_at.to_rfc3339(),
+        evidence: Some(AnomalyEvidence::RecentDenials {
+            window_hours: DENIAL_WINDOW_HOURS,
+            weighted_attempts,
+            attempts,
+        }),
+    })
+}
+
+/// Layout of an event input, detected from its first line
+#[derive(Debug)]
+enum InputFormat {
+    JsonLines,
+    /// CSV with the column index of each header name
+    Csv(HashMap<String, usize>),
+}
+
+/// An event with the user id or badge number it was keyed by
+#[derive(Debug)]
+pub struct EventRecord {
+    pub user_key: String,
+    pub event: AccessEvent,
+}
+
+impl EventRecord {
+    /// Build a record from named fields, as found in JSON and CSV events
+    ///
+    /// Events keyed by badge number get a user id derived from the badge number, so the
+    /// same badge always maps to the same user.
+    fn from_fields(field: impl Fn(&str) -> Option<String>) -> Result<Self, String> {
+        let required = |name: &str| field(name).filter(|value| !value.is_empty()).ok_or(format!("missing {}", name));
+        let user_key = field("user_id")
+            .or_else(|| field("badge_number"))
+            .filter(|key| !key.is_empty())
+            .ok_or("missing user_id or badge_number")?;
+        let user_id = user_key.parse().unwrap_or_else(|_| UserId(Uuid::new_v5(&Uuid::NAMESPACE_OID, user_key.as_bytes())));
+
+        let timestamp = DateTime::parse_from_rfc3339(&required("timestamp")?)
+            .map_err(|e| format!("invalid timestamp: {}", e))?
+            .with_timezone(&Utc);
+        let room_id: RoomId = required("room_id")?.parse().map_err(|e| format!("invalid room_id: {}", e))?;
+        let building_id: BuildingId =
+            required("building_id")?.parse().map_err(|e| format!("invalid building_id: {}", e))?;
+        let location_id: LocationId =
+            required("location_id")?.parse().map_err(|e| format!("invalid location_id: {}", e))?;
+        let success = match required("success")?.as_str() {
+            "true" => true,
+            "false" => false,
+            other => return Err(format!("invalid success flag '{}'", other)),
+        };
+        let event_type = if success { EventType::Success } else { EventType::Failure };
+
+        Ok(Self {
+            user_key,
+            event: AccessEvent::new(timestamp, user_id, room_id, building_id, location_id, success, event_type),
+        })
+    }
+
+    fn from_json(line: &str) -> Result<Self, String> {
+        let value: Value = serde_json::from_str(line).map_err(|e| format!("invalid JSON: {}", e))?;
+        Self::from_fields(|name| match value.get(name)? {
+            Value::Null => None,
+            Value::String(text) => Some(text.clone()),
+            other => Some(other.to_string()),
+        })
+    }
+
+    fn from_csv(columns: &HashMap<String, usize>, line: &str) -> Result<Self, String> {
+        let values = parse_csv_row(line)?;
+        Self::from_fields(|name| columns.get(name).and_then(|index| values.get(*index)).cloned())
+    }
+}
+
+/// Run the detector over each input in turn, writing alerts as JSON lines as they fire
+///
+/// Inputs are files (compressed by extension) or `-` for stdin, each in JSON lines or CSV
+/// with a header row. Lines that are not valid events are counted and skipped; read and
+/// write errors stop the run.
+pub fn detect_streams<W: Write>(
+    detector: &mut StreamingDetector,
+    inputs: &[String],
+    alerts: &mut W,
+) -> Result<(), String> {
+    for input in inputs {
+        let reader: Box<dyn BufRead> = if input == STDIN_INPUT {
+            Box::new(io::stdin().lock())
+        } else {
+            open_input_file(input).map_err(|e| format!("Failed to open events '{}': {}", input, e))?
+        };
+        detect_stream(detector, input, reader, alerts)?;
+    }
+
+    for alert in detector.finish() {
+        write_alert(alerts, &alert)?;
+    }
+    Ok(())
+}
+
+fn detect_stream<R: BufRead, W: Write>(
+    detector: &mut StreamingDetector,
+    input: &str,
+    reader: R,
+    alerts: &mut W,
+) -> Result<(), String> {
+    let mut format = None;
+    for (index, line) in reader.lines().enumerate() {
+        let line = line.map_err(|e| format!("Failed to read events '{}': {}", input, e))?;
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let record = match &format {
+            Some(InputFormat::JsonLines) => EventRecord::from_json(line),
+            Some(InputFormat::Csv(columns)) => EventRecord::from_csv(columns, line),
+            None if line.starts_with('{') => {
+                format = Some(InputFormat::JsonLines);
+                EventRecord::from_json(line)
+            }
+            None => {
+                let header = parse_csv_row(line)
+                    .map_err(|e| format!("Invalid CSV header in '{}': {}", input, e))?;
+                format = Some(InputFormat::Csv(header.into_iter().enumerate().map(|(i, name)| (name, i)).collect()));
+                continue;
+            }
+        };
+
+        match record {
+            Ok(record) => {
+                for alert in detector.observe(&record.user_key, record.event) {
+                    write_alert(alerts, &alert)?;
+                }
+            }
+            Err(e) => {
+                detector.statistics.invalid_lines += 1;
+                if detector.statistics.invalid_lines <= MAX_REPORTED_INVALID_LINES as u64 {
+                    warn!("Skipping line {} of '{}': {}", index + 1, input, e);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Write one alert as a JSON line and flush it, so downstream consumers see it at once
+fn write_alert<W: Write>(alerts: &mut W, alert: &UserAnomaly) -> Result<(), String> {
+    serde_json::to_writer(&mut *alerts, alert)
+        .map_err(|e| e.to_string())
+        .and_then(|_| writeln!(alerts).and_then(|_| alerts.flush()).map_err(|e| e.to_string()))
+        .map_err(|e| format!("Failed to write alert: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use amzn_career_pathway_activity_rust::facility::{FacilityDefinition, Room};
+    use chrono::TimeZone;
+
+    const OFFICES: &str = r#"
+locations:
+  - name: Seattle
+    latitude: 47.6062
+    longitude: -122.3321
+    time_zone: America/Los_Angeles
+    buildings:
+      - name: Tower
+        rooms:
+          - { name: Lobby, room_type: Lobby, security_level: Public }
+          - { name: Lab, room_type: Laboratory, security_level: HighSecurity }
+          - { name: Vault, room_type: Storage, security_level: Restricted }
+  - name: Tokyo
+    latitude: 35.6762
+    longitude: 139.6503
+    time_zone: Asia/Tokyo
+    buildings:
+      - name: Annex
+        rooms:
+          - { name: Entrance, room_type: Lobby, security_level: Public }
+"#;
+
+    fn detector() -> (StreamingDetector, HashMap<String, Room>) {
+        let registry = FacilityDefinition::from_yaml_str(OFFICES).unwrap().into_registry().unwrap();
+        let rooms = registry.get_all_rooms().into_iter().map(|room| (room.name.clone(), room.clone())).collect();
+        (StreamingDetector::new(&SimulationConfig::default(), registry), rooms)
+    }
+
+    fn badge(detector: &StreamingDetector, user_id: UserId, room: &Room, timestamp: DateTime<Utc>, success: bool) -> AccessEvent {
+        let location_id = detector.location_registry.get_building(room.building_id).unwrap().location_id;
+        let event_type = if success { EventType::Success } else { EventType::Failure };
+        AccessEvent::new(timestamp, user_id, room.id, room.building_id, location_id, success, event_type)
+    }
+
+    // Monday 2024-01-08 at 10:00 in Seattle
+    fn monday_morning() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 1, 8, 18, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_impossible_travel_alerts_at_once() {
+        let (mut detector, rooms) = detector();
+        let user = UserId::new();
+        let key = user.to_string();
+        let start = monday_morning();
+
+        let event = badge(&detector, user, &rooms["Lobby"], start, true);
+        assert!(detector.observe(&key, event).is_empty());
+        let event = badge(&detector, user, &rooms["Entrance"], start + Duration::hours(2), true);
+        let alerts = detector.observe(&key, event);
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].anomaly_type, "cloned_badge");
+        assert_eq!(alerts[0].user_id, key);
+        assert_eq!(alerts[0].timestamp, (start + Duration::hours(2)).to_rfc3339());
+    }
+
+    #[test]
+    fn test_denials_at_several_rooms_raise_one_curious_alert() {
+        let (mut detector, rooms) = detector();
+        let start = monday_morning();
+
+        // A reader retry and a patrolling guard's night-time refusals do not count
+        let guard = UserId::new();
+        for day in 0..3 {
+            let night = start + Duration::days(day) + Duration::hours(8);
+            for round in 0..NIGHT_STAFF_OFF_HOURS_EVENTS as i64 {
+                let event = badge(&detector, guard, &rooms["Lobby"], night + Duration::minutes(10 * round), true);
+                assert!(detector.observe("guard", event).is_empty());
+            }
+            for (i, room) in ["Lab", "Vault"].into_iter().enumerate() {
+                let event = badge(&detector, guard, &rooms[room], night + Duration::hours(i as i64 + 1), false);
+                assert!(detector.observe("guard", event).is_empty());
+            }
+        }
+        let event = badge(&detector, guard, &rooms["Vault"], start, false);
+        assert!(detector.observe("guard", event).is_empty());
+        let event = badge(&detector, guard, &rooms["Vault"], start + Duration::seconds(20), true);
+        assert!(detector.observe("guard", event).is_empty());
+
+        // One refused room is not enough, however restricted
+        let curious = UserId::new();
+        let event = badge(&detector, curious, &rooms["Vault"], start, false);
+        assert!(detector.observe("curious", event).is_empty());
+        let event = badge(&detector, curious, &rooms["Lobby"], start + Duration::minutes(15), true);
+        assert!(detector.observe("curious", event).is_empty());
+        let event = badge(&detector, curious, &rooms["Lab"], start + Duration::minutes(30), false);
+        assert!(detector.observe("curious", event).is_empty());
+        let event = badge(&detector, curious, &rooms["Lobby"], start + Duration::hours(1), true);
+        let alerts = detector.observe("curious", event);
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].anomaly_type, "curious_access");
+        assert_eq!(alerts[0].timestamp, (start + Duration::minutes(30)).to_rfc3339());
+
+        // Further denials within the window raise no second alert
+        let event = badge(&detector, curious, &rooms["Vault"], start + Duration::hours(2), false);
+        assert!(detector.observe("curious", event).is_empty());
+        assert!(detector.finish().is_empty());
+        assert_eq!(detector.statistics().alerts, 1);
+    }
+
+    #[test]
+    fn test_off_hours_probing_is_reported() {
+        let (mut detector, rooms) = detector();
+        let user = UserId::new();
+        // Saturday night in Seattle, from someone who only ever badged in during the day
+        let night = monday_morning() + Duration::days(5) + Duration::hours(12);
+
+        let event = badge(&detector, user, &rooms["Lobby"], monday_morning(), true);
+        assert!(detector.observe("prober", event).is_empty());
+        let event = badge(&detector, user, &rooms["Lobby"], night, true);
+        assert!(detector.observe("prober", event).is_empty());
+        let event = badge(&detector, user, &rooms["Vault"], night + Duration::minutes(5), false);
+        assert!(detector.observe("prober", event).is_empty());
+        let event = badge(&detector, user, &rooms["Lab"], night + Duration::minutes(10), false);
+        assert!(detector.observe("prober", event).is_empty());
+
+        let alerts = detector.finish();
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].timestamp, (night + Duration::minutes(10)).to_rfc3339());
+    }
+
+    #[test]
+    fn test_idle_users_are_evicted() {
+        let (mut detector, rooms) = detector();
+        let start = monday_morning();
+
+        let event = badge(&detector, UserId::new(), &rooms["Lobby"], start, true);
+        detector.observe("early", event);
+        let event = badge(&detector, UserId::new(), &rooms["Lobby"], start + Duration::hours(1), true);
+        detector.observe("later", event);
+        assert_eq!(detector.statistics().tracked_users, 2);
+
+        let event = badge(&detector, UserId::new(), &rooms["Lobby"], start + Duration::hours(24) + Duration::minutes(30), true);
+        detector.observe("next_day", event);
+        assert_eq!(detector.statistics().evicted_users, 1);
+        assert_eq!(detector.statistics().tracked_users, 2);
+        assert_eq!(detector.statistics().peak_tracked_users, 2);
+    }
+
+    #[test]
+    fn test_reads_json_and_csv_events() {
+        let (mut detector, rooms) = detector();
+        let start = monday_morning();
+        let (lobby, entrance) = (&rooms["Lobby"], &rooms["Entrance"]);
+        let seattle = detector.location_registry.get_building(lobby.building_id).unwrap().location_id;
+        let tokyo = detector.location_registry.get_building(entrance.building_id).unwrap().location_id;
+
+        let json = format!(
+            "{{\"timestamp\":\"{}\",\"badge_number\":\"100042\",\"room_id\":\"{}\",\"building_id\":\"{}\",\"location_id\":\"{}\",\"success\":true}}\nnot an event\n",
+            start.to_rfc3339(),
+            lobby.id,
+            lobby.building_id,
+            seattle
+        );
+        let csv = format!(
+            "timestamp,badge_number,room_id,building_id,location_id,success\n{},100042,{},{},{},true\n",
+            (start + Duration::hours(1)).to_rfc3339(),
+            entrance.id,
+            entrance.building_id,
+            tokyo
+        );
+
+        let mut alerts = Vec::new();
+        detect_stream(&mut detector, "events.jsonl", io::Cursor::new(json), &mut alerts).unwrap();
+        detect_stream(&mut detector, "events.csv", io::Cursor::new(csv), &mut alerts).unwrap();
+
+        let alerts: Vec<Value> =
+            String::from_utf8(alerts).unwrap().lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0]["user_id"], "100042");
+        assert_eq!(alerts[0]["anomaly_type"], "cloned_badge");
+        assert_eq!(detector.statistics().events, 2);
+        assert_eq!(detector.statistics().unresolved_events, 0);
+        assert_eq!(detector.statistics().invalid_lines, 1);
+    }
+
+    #[test]
+    fn test_counts_events_outside_the_facility_layout() {
+        let (mut detector, rooms) = detector();
+        let user = UserId::new();
+        let event = badge(&detector, user, &rooms["Lobby"], monday_morning(), true);
+        detector.observe("known", event.clone());
+
+        let other_layout = AccessEvent { room_id: RoomId::new(), location_id: LocationId::new(), ..event };
+        detector.observe("unknown", other_layout);
+        assert_eq!(detector.statistics().events, 2);
+        assert_eq!(detector.statistics().unresolved_events, 1);
+    }
+}
@@ -3,7 +3,8 @@
 //! This module contains the simulation configuration structure and validation logic
 //! used to control the behavior and parameters of the simulation system.
 
-use super::OutputFormat;
+use super::{Compression, HttpBodyFormat, OutputFormat, SyslogProtocol};
+use chrono::{NaiveDate, Utc, Weekday};
 use clap::Parser;
 use serde::{Deserialize, Serialize};
 use std::fs;
@@ -25,7 +26,7 @@ pub mod night_shift {
 }
 
 /// Configuration for which fields to include in event output
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
 pub struct OutputFieldConfig {
     /// Include failure_reason field in output (default: false)
     pub include_failure_reason: bool,
@@ -35,19 +36,56 @@ pub struct OutputFieldConfig {
     pub include_metadata: bool,
     /// Include all fields (overrides individual settings, default: false)
     pub include_all: bool,
+    /// Include room, building and location names, room type and security level (default: false)
+    ///
+    /// Not enabled by `include_all`, since room types are the answer to the room-type
+    /// inference challenge.
+    #[serde(default)]
+    pub include_facility_names: bool,
+    /// Include the UTC offset of the event's location at the event time (default: false)
+    #[serde(default)]
+    pub include_local_offset: bool,
+    /// Identify the badge holder by badge number instead of user id (default: false)
+    #[serde(default)]
+    pub key_by_badge_number: bool,
+}
+
+/// Work calendar deciding which days users are expected at the office
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct WorkCalendarConfig {
+    /// Days of the week that are not work days (default: Saturday and Sunday)
+    #[serde(default = "default_weekend_days")]
+    pub weekend_days: Vec<Weekday>,
+    /// Public holidays observed at every location, in addition to each location's own list
+    #[serde(default)]
+    pub holidays: Vec<NaiveDate>,
+    /// Days when every location closes at midday, in addition to each location's own list
+    #[serde(default)]
+    pub half_days: Vec<NaiveDate>,
+    /// Probability that a regular user comes in on a weekend or holiday (default: 0.02)
+    #[serde(default = "default_non_work_day_attendance")]
+    pub non_work_day_attendance: f64,
 }
 
-impl Default for OutputFieldConfig {
+impl Default for WorkCalendarConfig {
     fn default() -> Self {
         Self {
-            include_failure_reason: false,
-            include_event_type: false,
-            include_metadata: false,
-            include_all: false,
+            weekend_days: default_weekend_days(),
+            holidays: Vec::new(),
+            half_days: Vec::new(),
+            non_work_day_attendance: default_non_work_day_attendance(),
         }
     }
 }
 
+fn default_weekend_days() -> Vec<Weekday> {
+    vec![Weekday::Sat, Weekday::Sun]
+}
+
+fn default_non_work_day_attendance() -> f64 {
+    0.02
+}
+
 /// Command line arguments structure
 #[derive(Debug, Clone, Parser)]
 #[command(
@@ -160,11 +198,145 @@ pub struct CliArgs {
     /// Output format for generated events
     #[arg(
         long,
-        help = "Output format (json or csv)",
-        long_help = "Output format for generated events. Supported formats: json, csv. Default: json"
+        help = "Output format (json, csv, ocsf, ecs, cef or leef)",
+        long_help = "Output format for generated events. Supported formats: json, csv, ocsf (OCSF Authentication events as JSON lines), ecs (Elastic Common Schema documents as JSON lines), cef (ArcSight CEF lines), leef (QRadar LEEF lines). Default: json"
     )]
     pub output_format: Option<String>,
 
+    /// Directory to write per-day event files into
+    #[arg(
+        long,
+        help = "Write events to per-day files in this directory instead of stdout",
+        long_help = "Write events to one file per simulated day (e.g. 2024-01-01.jsonl) in this directory instead of stdout. A manifest.json with each file's date range, event count and SHA-256 checksum is written when the run finishes."
+    )]
+    pub output_dir: Option<String>,
+
+    /// Maximum size of a single event file in bytes
+    #[arg(
+        long,
+        help = "Roll over to a new file when a day's file would exceed this many bytes",
+        long_help = "Roll over to a new part file (e.g. 2024-01-01_part002.jsonl) when the current day's file would exceed this many bytes. Requires --output-dir."
+    )]
+    pub rotate_max_bytes: Option<u64>,
+
+    /// Maximum number of events in a single event file
+    #[arg(
+        long,
+        help = "Roll over to a new file when a day's file reaches this many events",
+        long_help = "Roll over to a new part file when the current day's file reaches this many events. Requires --output-dir."
+    )]
+    pub rotate_max_events: Option<usize>,
+
+    /// Compression for event and user profile output
+    #[arg(
+        long,
+        help = "Compress event and user profile output (gzip or zstd)",
+        long_help = "Compress event output and the user profiles file as they are written. Supported: gzip, zstd, none. Files whose name already ends in .gz or .zst are compressed accordingly without this flag; otherwise the extension is appended."
+    )]
+    pub compress: Option<String>,
+
+    /// Syslog collector to send events to (host:port)
+    #[arg(
+        long,
+        value_name = "HOST:PORT",
+        help = "Send events as RFC 5424 syslog messages to this collector instead of stdout",
+        long_help = "Send each event as an RFC 5424 syslog message to the collector at HOST:PORT instead of writing to stdout. The message body uses --output-format (e.g. cef or leef). The failure reason and the EventMetadata flags are carried as structured data when --include-failure-reason and --include-metadata (or --include-all-fields) are set."
+    )]
+    pub syslog: Option<String>,
+
+    /// Transport for syslog messages
+    #[arg(
+        long,
+        help = "Syslog transport (udp or tcp)",
+        long_help = "Transport for syslog messages: udp (one datagram per message) or tcp (octet-counted framing per RFC 6587). Requires --syslog. Default: udp"
+    )]
+    pub syslog_protocol: Option<String>,
+
+    /// Pace syslog messages by simulated time at this speed-up factor
+    #[arg(
+        long,
+        help = "Pace syslog messages by simulated time, sped up by this factor",
+        long_help = "Send syslog messages paced by the simulated time between events, sped up by this factor (e.g. 60 replays one simulated minute per real second). Requires --syslog. Messages are sent as fast as possible when not set."
+    )]
+    pub syslog_speed: Option<f64>,
+
+    /// HTTP endpoint to POST event batches to
+    #[arg(
+        long,
+        value_name = "URL",
+        help = "POST batches of events to this HTTP endpoint instead of writing to stdout",
+        long_help = "POST batches of events to this HTTP(S) endpoint instead of writing to stdout. Requires a JSON-based --output-format (json, ocsf or ecs)."
+    )]
+    pub http_url: Option<String>,
+
+    /// Number of events per HTTP request
+    #[arg(
+        long,
+        help = "Number of events per HTTP request (default: 500)",
+        long_help = "Maximum number of events per HTTP request. Partial batches are sent at the end of each simulated day. Requires --http-url. Default: 500"
+    )]
+    pub http_batch_size: Option<usize>,
+
+    /// Request body layout for HTTP batches
+    #[arg(
+        long,
+        help = "HTTP request body format (ndjson or json)",
+        long_help = "Request body layout for HTTP batches: ndjson (one event per line) or json (a JSON array of events). Requires --http-url. Default: ndjson"
+    )]
+    pub http_format: Option<String>,
+
+    /// Header added to every HTTP request (e.g. for authentication)
+    #[arg(
+        long,
+        value_name = "NAME: VALUE",
+        help = "Header to send with every HTTP request, e.g. \"Authorization: Bearer TOKEN\"",
+        long_help = "Header to send with every HTTP request, given as \"Name: value\", typically for authentication (e.g. \"Authorization: Bearer TOKEN\"). Requires --http-url."
+    )]
+    pub http_auth_header: Option<String>,
+
+    /// Number of retries for HTTP batches that fail with a server error
+    #[arg(
+        long,
+        help = "Retries for batches that fail with 5xx or connection errors (default: 3)",
+        long_help = "Number of times a batch is retried, with exponential backoff starting at 500ms, when the endpoint responds with a 5xx status or cannot be reached. Requires --http-url. Default: 3"
+    )]
+    pub http_max_retries: Option<u32>,
+
+    /// File that receives batches that could not be delivered
+    #[arg(
+        long,
+        value_name = "FILE",
+        help = "Append undeliverable batches to this file instead of aborting",
+        long_help = "Append the events of batches that could not be delivered (after retries, or rejected with a 4xx status) to this file, one JSON event per line, instead of aborting the run. Requires --http-url."
+    )]
+    pub http_dead_letter: Option<String>,
+
+    /// SQLite database that receives facilities, users, permissions and events
+    #[arg(
+        long,
+        value_name = "PATH",
+        help = "Write facilities, users, permissions and events to a SQLite database",
+        long_help = "Write normalized tables (locations, buildings, rooms, users, permissions and events) to a SQLite database at PATH, replacing any existing file. Tables are linked by foreign keys and indexed on the usual query columns. Answer-key columns follow the --include-* field flags (--include-event-type, --include-failure-reason, --include-metadata, --include-all-fields). Cannot be combined with --output-dir, --syslog or --http-url."
+    )]
+    pub sqlite: Option<String>,
+
+    /// Directory to write per-day Parquet event files into
+    #[arg(
+        long,
+        value_name = "DIR",
+        help = "Write events to per-day Parquet files in this directory",
+        long_help = "Write events to one Apache Parquet file per simulated day (e.g. 2024-01-01.parquet) in DIR, for loading into DuckDB, Polars or Spark. Ids are dictionary-encoded, timestamps are typed UTC columns, and optional columns follow the --include-* field flags. Files are Zstandard-compressed internally, so --compress does not apply to them. Cannot be combined with --output-dir, --syslog, --http-url or --sqlite."
+    )]
+    pub parquet_dir: Option<String>,
+
+    /// Also write the user profiles answer key as Parquet
+    #[arg(
+        long,
+        help = "Also write the user profiles answer key to user_profiles.parquet in the Parquet directory",
+        long_help = "Also write the user profiles answer key to user_profiles.parquet in the --parquet-dir directory, with authorized rooms, buildings and locations as list columns. The JSONL answer key is still written. Requires --parquet-dir."
+    )]
+    pub parquet_user_profiles: bool,
+
     /// Random seed for reproducible results
     #[arg(long, help = "Random seed for reproducible results")]
     pub seed: Option<u64>,
@@ -173,6 +345,33 @@ pub struct CliArgs {
     #[arg(long, help = "Output path for user profiles JSONL file")]
     pub user_profiles_output: Option<String>,
 
+    /// Output path for the facility topology (locations, buildings and rooms)
+    #[arg(
+        long,
+        value_name = "FILE",
+        help = "Write the generated facility layout to this file (JSON, or CSV for .csv)",
+        long_help = "Write every generated location (with coordinates), building (with its lobby) and room (with room type, security level and required intermediate rooms) to FILE. Files ending in .csv get one row per room; anything else gets a nested JSON document. --compress applies as for the user profiles file."
+    )]
+    pub facility_output: Option<String>,
+
+    /// Facility definition file to load instead of generating facilities
+    #[arg(
+        long,
+        value_name = "FILE",
+        help = "Load the facility layout from a JSON or YAML definition file",
+        long_help = "Load locations, buildings and rooms (with room types, security levels and required checkpoint rooms) from FILE instead of generating them at random. Files ending in .yaml or .yml are read as YAML, anything else as JSON. --location-count and the building and room count ranges are ignored."
+    )]
+    pub facility_definition: Option<String>,
+
+    /// User roster file to load instead of generating users
+    #[arg(
+        long,
+        value_name = "FILE",
+        help = "Load users and their permissions from a roster CSV file",
+        long_help = "Build users from FILE instead of generating them at random. The CSV header names the columns: user_id, location, building and workspace are required; role, permissions (a ;-separated list of room:, building: and location: grants), is_curious, has_cloned_badge and is_night_shift are optional. Empty curious and cloned badge cells are filled to reach --curious-user-percentage and --cloned-badge-percentage. --user-count is ignored."
+    )]
+    pub user_roster: Option<String>,
+
     /// Enable verbose logging
     #[arg(short, long, help = "Enable verbose logging")]
     pub verbose: bool,
@@ -189,6 +388,44 @@ pub struct CliArgs {
     #[arg(long, help = "Print default configuration in JSON format and exit")]
     pub print_config: bool,
 
+    /// Anomaly report to score against the user profiles answer key
+    #[arg(
+        long,
+        value_name = "REPORT",
+        help = "Score an anomaly report against the user profiles answer key and exit",
+        long_help = "Compare the anomalies in REPORT (an anomaly_report.json file, or one anomaly per line) with the user profiles answer key at --user-profiles-output (user_profiles.json by default), print per-class confusion matrices, precision, recall and F1 as JSON, and exit without running a simulation."
+    )]
+    pub evaluate: Option<String>,
+
+    /// Event file with metadata labels for measuring time-to-detect
+    #[arg(
+        long,
+        value_name = "FILE",
+        requires = "evaluate",
+        help = "Event file written with --include-metadata, used to measure time-to-detect (needs --evaluate)",
+        long_help = "Read the events written with --include-metadata (JSON lines or CSV, optionally compressed) and measure how long after each user's first labeled anomalous event the report flagged them. Events without metadata labels are skipped, so time-to-detect is not measured for files written without --include-metadata."
+    )]
+    pub evaluate_events: Option<String>,
+
+    /// Room type inference output file
+    #[arg(
+        long,
+        value_name = "FILE",
+        help = "Infer room types and security levels from access behavior and write the scored predictions to FILE",
+        long_help = "Cluster rooms by how they are used (hourly profile, dwell time, share of users who get in, night and weekend use, failure rate), predict each room's type and security level, and write the predictions with their accuracy against the facility layout to FILE as JSON."
+    )]
+    pub infer_room_types: Option<String>,
+
+    /// Event inputs for streaming detection
+    #[arg(
+        long,
+        value_name = "INPUT",
+        num_args = 1..,
+        help = "Run the detectors over JSON or CSV events read from files or stdin (-) and write alerts as JSON lines, instead of running a simulation",
+        long_help = "Read events (JSON lines or CSV with a header, optionally compressed) from the given files in order, or from stdin when the input is -, and run the detection rules on each event as it arrives. Alerts are written to stdout as JSON lines as soon as a rule fires. Per-user state is limited to a sliding window and idle users are evicted, so memory stays bounded on long inputs. The facility layout comes from --facility-definition or is generated from the same settings, so one of --facility-definition or --seed is required (use the same --seed and scale options as the simulation). The run fails when any event references a room or location missing from the layout."
+    )]
+    pub detect: Vec<String>,
+
     /// Include failure_reason field in output
     #[arg(
         long,
@@ -221,6 +458,30 @@ pub struct CliArgs {
     )]
     pub include_all_fields: bool,
 
+    /// Include the local UTC offset in output
+    #[arg(
+        long,
+        help = "Include the UTC offset of the event's location in output",
+        long_help = "Add local_offset (e.g. +09:00) to each event: the UTC offset in effect at the event's location at the time of the event, including daylight saving time. Timestamps stay in UTC; adding the offset gives the local wall-clock time. Also enabled by --include-all-fields."
+    )]
+    pub include_local_offset: bool,
+
+    /// Include facility names alongside ids in output
+    #[arg(
+        long,
+        help = "Include room, building and location names in output",
+        long_help = "Add room_name, room_type, security_level, building_name and location_name to each event, looked up from the generated facilities. Meant for demos and dashboard development: room types give away the room-type inference challenge, so --include-all-fields does not enable this."
+    )]
+    pub include_facility_names: bool,
+
+    /// Identify badge holders by badge number in output
+    #[arg(
+        long,
+        help = "Identify badge holders by badge number instead of user id",
+        long_help = "Replace the user_id field with badge_number (facility code and card number, e.g. 042-13087), as a physical access control system would log it. The user profiles answer key maps each badge number back to its user. Supported for json and csv output and --parquet-dir."
+    )]
+    pub key_by_badge_number: bool,
+
     /// Number of days to simulate
     #[arg(
         long,
@@ -229,6 +490,62 @@ pub struct CliArgs {
         long_help = "Number of days to simulate. Must be greater than 0. Default: 1"
     )]
     pub days: usize,
+
+    /// First day to simulate (YYYY-MM-DD)
+    #[arg(
+        long,
+        help = "First day to simulate (YYYY-MM-DD)",
+        long_help = "First simulated day in YYYY-MM-DD format. Defaults to today, or to 2024-01-01 when --seed is given so seeded runs are reproducible."
+    )]
+    pub start_date: Option<NaiveDate>,
+
+    /// Days of the week that are not work days
+    #[arg(
+        long,
+        value_delimiter = ',',
+        value_name = "DAYS",
+        conflicts_with = "no_weekends",
+        help = "Comma-separated weekend days (e.g. Fri,Sat)",
+        long_help = "Days of the week that are not work days, comma-separated (e.g. Fri,Sat). Regular users rarely come in on weekends, while night-shift security keeps patrolling. Default: Sat,Sun"
+    )]
+    pub weekend_days: Option<Vec<Weekday>>,
+
+    /// Treat every day of the week as a work day
+    #[arg(
+        long,
+        help = "Treat every day of the week as a work day",
+        long_help = "Simulate a seven-day work week with no weekend days. Holidays and half-days still apply."
+    )]
+    pub no_weekends: bool,
+
+    /// Public holidays observed at every location
+    #[arg(
+        long,
+        value_delimiter = ',',
+        value_name = "DATES",
+        help = "Comma-separated holidays for all locations (YYYY-MM-DD)",
+        long_help = "Public holidays observed at every location, comma-separated in YYYY-MM-DD format. Locations loaded from a facility definition can list their own holidays as well."
+    )]
+    pub holidays: Option<Vec<NaiveDate>>,
+
+    /// Days when every location closes at midday
+    #[arg(
+        long,
+        value_delimiter = ',',
+        value_name = "DATES",
+        help = "Comma-separated half-days for all locations (YYYY-MM-DD)",
+        long_help = "Days when every location closes at midday (business hours end at 1 PM local time and users leave early), comma-separated in YYYY-MM-DD format."
+    )]
+    pub half_days: Option<Vec<NaiveDate>>,
+
+    /// Probability that a regular user comes in on a non-work day
+    #[arg(
+        long,
+        value_name = "PROB",
+        help = "Probability a regular user comes in on a weekend or holiday (0.0-1.0)",
+        long_help = "Probability that a regular (non-night-shift) user comes in for a short visit on a weekend or holiday. Must be between 0.0 and 1.0. Default: 0.02"
+    )]
+    pub non_work_day_attendance: Option<f64>,
 }
 
 /// Configuration file structure (allows partial configuration)
@@ -272,17 +589,80 @@ pub struct ConfigFile {
     /// Output format for generated events
     pub output_format: Option<String>,
 
+    /// Directory to write per-day event files into
+    pub output_dir: Option<String>,
+
+    /// Maximum size of a single event file in bytes
+    pub rotate_max_bytes: Option<u64>,
+
+    /// Maximum number of events in a single event file
+    pub rotate_max_events: Option<usize>,
+
+    /// Compression for event and user profile output
+    pub compress: Option<String>,
+
+    /// Syslog collector to send events to (host:port)
+    pub syslog: Option<String>,
+
+    /// Transport for syslog messages (udp or tcp)
+    pub syslog_protocol: Option<String>,
+
+    /// Speed-up factor for pacing syslog messages by simulated time
+    pub syslog_speed: Option<f64>,
+
+    /// HTTP endpoint to POST event batches to
+    pub http_url: Option<String>,
+
+    /// Number of events per HTTP request
+    pub http_batch_size: Option<usize>,
+
+    /// Request body layout for HTTP batches (ndjson or json)
+    pub http_format: Option<String>,
+
+    /// Header added to every HTTP request ("Name: value")
+    pub http_auth_header: Option<String>,
+
+    /// Number of retries for HTTP batches that fail with a server error
+    pub http_max_retries: Option<u32>,
+
+    /// File that receives batches that could not be delivered
+    pub http_dead_letter: Option<String>,
+
+    /// SQLite database that receives facilities, users, permissions and events
+    pub sqlite: Option<String>,
+
+    /// Directory to write per-day Parquet event files into
+    pub parquet_dir: Option<String>,
+
+    /// Also write the user profiles answer key as Parquet
+    pub parquet_user_profiles: Option<bool>,
+
     /// Random seed for reproducible results
     pub seed: Option<u64>,
 
     /// Output path for user profiles (answer key)
     pub user_profiles_output: Option<String>,
 
+    /// Output path for the facility topology (locations, buildings and rooms)
+    pub facility_output: Option<String>,
+
+    /// Facility definition file to load instead of generating facilities
+    pub facility_definition: Option<String>,
+
+    /// User roster CSV file to load instead of generating users
+    pub user_roster: Option<String>,
+
     /// Configuration for which fields to include in event output
     pub output_fields: Option<OutputFieldConfig>,
 
     /// Number of days to simulate
     pub days: Option<usize>,
+
+    /// First day to simulate
+    pub start_date: Option<NaiveDate>,
+
+    /// Work calendar with weekends, holidays and half-days
+    pub calendar: Option<WorkCalendarConfig>,
 }
 
 /// Configuration for the badge access simulation
@@ -326,17 +706,81 @@ pub struct SimulationConfig {
     /// Output format for generated events
     pub output_format: String,
 
+    /// Directory to write per-day event files into (stdout when not set)
+    pub output_dir: Option<String>,
+
+    /// Maximum size of a single event file in bytes
+    pub rotate_max_bytes: Option<u64>,
+
+    /// Maximum number of events in a single event file
+    pub rotate_max_events: Option<usize>,
+
+    /// Compression for event and user profile output (gzip, zstd, or none)
+    pub compress: Option<String>,
+
+    /// Syslog collector to send events to (host:port) instead of stdout
+    pub syslog: Option<String>,
+
+    /// Transport for syslog messages (udp or tcp, default udp)
+    pub syslog_protocol: Option<String>,
+
+    /// Speed-up factor for pacing syslog messages by simulated time (unpaced when not set)
+    pub syslog_speed: Option<f64>,
+
+    /// HTTP endpoint to POST event batches to instead of stdout
+    pub http_url: Option<String>,
+
+    /// Number of events per HTTP request (default 500)
+    pub http_batch_size: Option<usize>,
+
+    /// Request body layout for HTTP batches (ndjson or json, default ndjson)
+    pub http_format: Option<String>,
+
+    /// Header added to every HTTP request ("Name: value")
+    pub http_auth_header: Option<String>,
+
+    /// Number of retries for HTTP batches that fail with a server error (default 3)
+    pub http_max_retries: Option<u32>,
+
+    /// File that receives batches that could not be delivered (the run aborts when not set)
+    pub http_dead_letter: Option<String>,
+
+    /// SQLite database that receives facilities, users, permissions and events (replaced if it exists)
+    pub sqlite: Option<String>,
+
+    /// Directory to write per-day Parquet event files into
+    pub parquet_dir: Option<String>,
+
+    /// Also write the user profiles answer key to `user_profiles.parquet` in the Parquet directory
+    pub parquet_user_profiles: bool,
+
     /// Random seed for reproducible results
     pub seed: Option<u64>,
 
     /// Output path for user profiles (answer key)
     pub user_profiles_output: Option<String>,
 
+    /// Output path for the facility topology (not written when not set)
+    pub facility_output: Option<String>,
+
+    /// Facility definition file (JSON or YAML) loaded instead of generating facilities
+    pub facility_definition: Option<String>,
+
+    /// User roster CSV file loaded instead of generating users
+    pub user_roster: Option<String>,
+
     /// Configuration for which fields to include in event output
     pub output_fields: OutputFieldConfig,
 
     /// Number of days to simulate
     pub days: usize,
+
+    /// First day to simulate (defaults to today, or a fixed date for seeded runs)
+    pub start_date: Option<NaiveDate>,
+
+    /// Work calendar with weekends, holidays and half-days
+    #[serde(default)]
+    pub calendar: WorkCalendarConfig,
 }
 
 /// Configuration loading and validation errors
@@ -402,6 +846,74 @@ pub enum ConfigValidationError {
         sum: f64,
     },
 
+    /// Output format is not supported
+    #[error("Unsupported output format: {0} (supported: json, csv, ocsf, ecs, cef, leef)")]
+    InvalidOutputFormat(String),
+
+    /// Compression is not supported
+    #[error("Unsupported compression: {0} (supported: gzip, zstd, none)")]
+    InvalidCompression(String),
+
+    /// File rotation threshold is zero
+    #[error("Rotation threshold {0} must be greater than 0")]
+    InvalidRotationThreshold(String),
+
+    /// File rotation was requested without directory output
+    #[error("File rotation requires an output directory")]
+    RotationWithoutOutputDir,
+
+    /// More than one event destination was configured
+    #[error("Only one event destination can be used at a time, got: {0}")]
+    ConflictingEventDestinations(String),
+
+    /// Syslog target is not a host:port pair
+    #[error("Invalid syslog target: {0} (expected host:port)")]
+    InvalidSyslogTarget(String),
+
+    /// Syslog protocol is not supported
+    #[error("Unsupported syslog protocol: {0} (supported: udp, tcp)")]
+    InvalidSyslogProtocol(String),
+
+    /// Syslog pacing speed is not a positive number
+    #[error("Invalid syslog speed: {0} (must be greater than 0)")]
+    InvalidSyslogSpeed(f64),
+
+    /// Syslog options were given without a syslog target
+    #[error("Syslog options require a syslog target")]
+    SyslogOptionWithoutTarget,
+
+    /// HTTP endpoint is not an http(s) URL
+    #[error("Invalid HTTP URL: {0} (must start with http:// or https://)")]
+    InvalidHttpUrl(String),
+
+    /// HTTP batch size is zero
+    #[error("HTTP batch size must be greater than 0")]
+    InvalidHttpBatchSize,
+
+    /// HTTP body format is not supported
+    #[error("Unsupported HTTP body format: {0} (supported: ndjson, json)")]
+    InvalidHttpFormat(String),
+
+    /// HTTP header is not a "Name: value" pair
+    #[error("Invalid HTTP header: {0} (expected \"Name: value\")")]
+    InvalidHttpHeader(String),
+
+    /// HTTP output was requested with an output format that is not JSON
+    #[error("HTTP output requires a JSON-based output format (json, ocsf or ecs), got: {0}")]
+    HttpRequiresJsonFormat(String),
+
+    /// HTTP options were given without an HTTP URL
+    #[error("HTTP options require an HTTP URL")]
+    HttpOptionWithoutUrl,
+
+    /// Parquet user profiles were requested without a Parquet directory
+    #[error("Parquet user profiles require a Parquet output directory")]
+    ParquetOptionWithoutDir,
+
+    /// Badge number keying was requested for an output that does not support it
+    #[error("Keying events by badge number requires json or csv output or a Parquet directory, got: {0}")]
+    BadgeKeyUnsupportedOutput(String),
+
 
 }
 
@@ -420,10 +932,31 @@ impl Default for SimulationConfig {
             same_location_travel: 0.29,
             different_location_travel: 0.01,
             output_format: "json".to_string(),
+            output_dir: None,
+            rotate_max_bytes: None,
+            rotate_max_events: None,
+            compress: None,
+            syslog: None,
+            syslog_protocol: None,
+            syslog_speed: None,
+            http_url: None,
+            http_batch_size: None,
+            http_format: None,
+            http_auth_header: None,
+            http_max_retries: None,
+            http_dead_letter: None,
+            sqlite: None,
+            parquet_dir: None,
+            parquet_user_profiles: false,
             seed: None,
             user_profiles_output: None,
+            facility_output: None,
+            facility_definition: None,
+            user_roster: None,
             output_fields: OutputFieldConfig::default(),
             days: 1,
+            start_date: None,
+            calendar: WorkCalendarConfig::default(),
         }
     }
 }
@@ -510,12 +1043,35 @@ impl SimulationConfig {
                 .different_location_travel
                 .unwrap_or(defaults.different_location_travel),
             output_format: config_file.output_format.unwrap_or(defaults.output_format),
+            output_dir: config_file.output_dir.or(defaults.output_dir),
+            rotate_max_bytes: config_file.rotate_max_bytes.or(defaults.rotate_max_bytes),
+            rotate_max_events: config_file.rotate_max_events.or(defaults.rotate_max_events),
+            compress: config_file.compress.or(defaults.compress),
+            syslog: config_file.syslog.or(defaults.syslog),
+            syslog_protocol: config_file.syslog_protocol.or(defaults.syslog_protocol),
+            syslog_speed: config_file.syslog_speed.or(defaults.syslog_speed),
+            http_url: config_file.http_url.or(defaults.http_url),
+            http_batch_size: config_file.http_batch_size.or(defaults.http_batch_size),
+            http_format: config_file.http_format.or(defaults.http_format),
+            http_auth_header: config_file.http_auth_header.or(defaults.http_auth_header),
+            http_max_retries: config_file.http_max_retries.or(defaults.http_max_retries),
+            http_dead_letter: config_file.http_dead_letter.or(defaults.http_dead_letter),
+            sqlite: config_file.sqlite.or(defaults.sqlite),
+            parquet_dir: config_file.parquet_dir.or(defaults.parquet_dir),
+            parquet_user_profiles: config_file
+                .parquet_user_profiles
+                .unwrap_or(defaults.parquet_user_profiles),
             seed: config_file.seed.or(defaults.seed),
             user_profiles_output: config_file
                 .user_profiles_output
                 .or(defaults.user_profiles_output),
+            facility_output: config_file.facility_output.or(defaults.facility_output),
+            facility_definition: config_file.facility_definition.or(defaults.facility_definition),
+            user_roster: config_file.user_roster.or(defaults.user_roster),
             output_fields: config_file.output_fields.unwrap_or(defaults.output_fields),
             days: config_file.days.unwrap_or(defaults.days),
+            start_date: config_file.start_date.or(defaults.start_date),
+            calendar: config_file.calendar.unwrap_or(defaults.calendar),
         }
     }
 
@@ -557,6 +1113,54 @@ impl SimulationConfig {
         if let Some(value) = args.output_format {
             config.output_format = value;
         }
+        if let Some(value) = args.output_dir {
+            config.output_dir = Some(value);
+        }
+        if let Some(value) = args.rotate_max_bytes {
+            config.rotate_max_bytes = Some(value);
+        }
+        if let Some(value) = args.rotate_max_events {
+            config.rotate_max_events = Some(value);
+        }
+        if let Some(value) = args.compress {
+            config.compress = Some(value);
+        }
+        if let Some(value) = args.syslog {
+            config.syslog = Some(value);
+        }
+        if let Some(value) = args.syslog_protocol {
+            config.syslog_protocol = Some(value);
+        }
+        if let Some(value) = args.syslog_speed {
+            config.syslog_speed = Some(value);
+        }
+        if let Some(value) = args.http_url {
+            config.http_url = Some(value);
+        }
+        if let Some(value) = args.http_batch_size {
+            config.http_batch_size = Some(value);
+        }
+        if let Some(value) = args.http_format {
+            config.http_format = Some(value);
+        }
+        if let Some(value) = args.http_auth_header {
+            config.http_auth_header = Some(value);
+        }
+        if let Some(value) = args.http_max_retries {
+            config.http_max_retries = Some(value);
+        }
+        if let Some(value) = args.http_dead_letter {
+            config.http_dead_letter = Some(value);
+        }
+        if let Some(value) = args.sqlite {
+            config.sqlite = Some(value);
+        }
+        if let Some(value) = args.parquet_dir {
+            config.parquet_dir = Some(value);
+        }
+        if args.parquet_user_profiles {
+            config.parquet_user_profiles = true;
+        }
 
         if let Some(value) = args.seed {
             config.seed = Some(value);
@@ -564,17 +1168,58 @@ impl SimulationConfig {
         if let Some(value) = args.user_profiles_output {
             config.user_profiles_output = Some(value);
         }
+        if let Some(value) = args.facility_output {
+            config.facility_output = Some(value);
+        }
+        if let Some(value) = args.facility_definition {
+            config.facility_definition = Some(value);
+        }
+        if let Some(value) = args.user_roster {
+            config.user_roster = Some(value);
+        }
+        if let Some(value) = args.start_date {
+            config.start_date = Some(value);
+        }
+        if let Some(value) = args.weekend_days {
+            config.calendar.weekend_days = value;
+        }
+        if args.no_weekends {
+            config.calendar.weekend_days.clear();
+        }
+        if let Some(value) = args.holidays {
+            config.calendar.holidays = value;
+        }
+        if let Some(value) = args.half_days {
+            config.calendar.half_days = value;
+        }
+        if let Some(value) = args.non_work_day_attendance {
+            config.calendar.non_work_day_attendance = value;
+        }
 
         // Handle output field configuration from CLI arguments
         // CLI arguments override config file settings
-        if args.include_failure_reason || args.include_event_type || args.include_metadata || args.include_all_fields {
+        if args.include_failure_reason
+            || args.include_event_type
+            || args.include_metadata
+            || args.include_local_offset
+            || args.include_all_fields
+        {
             config.output_fields = OutputFieldConfig {
                 include_failure_reason: args.include_failure_reason || args.include_all_fields,
                 include_event_type: args.include_event_type || args.include_all_fields,
                 include_metadata: args.include_metadata || args.include_all_fields,
                 include_all: args.include_all_fields,
+                include_facility_names: config.output_fields.include_facility_names,
+                include_local_offset: args.include_local_offset || args.include_all_fields,
+                key_by_badge_number: config.output_fields.key_by_badge_number,
             };
         }
+        if args.include_facility_names {
+            config.output_fields.include_facility_names = true;
+        }
+        if args.key_by_badge_number {
+            config.output_fields.key_by_badge_number = true;
+        }
 
         // Apply days override (always applied since it has a default value)
         config.days = args.days;
@@ -637,6 +1282,7 @@ impl SimulationConfig {
         self.validate_percentage("primary_building_affinity", self.primary_building_affinity)?;
         self.validate_percentage("same_location_travel", self.same_location_travel)?;
         self.validate_percentage("different_location_travel", self.different_location_travel)?;
+        self.validate_percentage("non_work_day_attendance", self.calendar.non_work_day_attendance)?;
 
         // Validate affinity sum
         let affinity_sum = self.primary_building_affinity
@@ -647,6 +1293,91 @@ impl SimulationConfig {
             return Err(ConfigValidationError::InvalidAffinitySum { sum: affinity_sum });
         }
 
+        // Validate output format
+        if self.get_output_format().is_err() {
+            return Err(ConfigValidationError::InvalidOutputFormat(self.output_format.clone()));
+        }
+
+        // Validate compression
+        if let (Some(compress), Err(_)) = (&self.compress, self.get_compression()) {
+            return Err(ConfigValidationError::InvalidCompression(compress.clone()));
+        }
+
+        // Validate file rotation
+        if self.rotate_max_bytes == Some(0) {
+            return Err(ConfigValidationError::InvalidRotationThreshold("rotate_max_bytes".to_string()));
+        }
+        if self.rotate_max_events == Some(0) {
+            return Err(ConfigValidationError::InvalidRotationThreshold("rotate_max_events".to_string()));
+        }
+        if (self.rotate_max_bytes.is_some() || self.rotate_max_events.is_some()) && self.output_dir.is_none() {
+            return Err(ConfigValidationError::RotationWithoutOutputDir);
+        }
+
+        // Validate event destinations
+        let destinations = self.event_destinations();
+        if destinations.len() > 1 {
+            return Err(ConfigValidationError::ConflictingEventDestinations(destinations.join(", ")));
+        }
+
+        // Validate syslog output
+        if let Some(target) = &self.syslog {
+            if self.syslog_target_port().is_none() {
+                return Err(ConfigValidationError::InvalidSyslogTarget(target.clone()));
+            }
+        } else if self.syslog_protocol.is_some() || self.syslog_speed.is_some() {
+            return Err(ConfigValidationError::SyslogOptionWithoutTarget);
+        }
+        if let (Some(protocol), Err(_)) = (&self.syslog_protocol, self.get_syslog_protocol()) {
+            return Err(ConfigValidationError::InvalidSyslogProtocol(protocol.clone()));
+        }
+        if let Some(speed) = self.syslog_speed {
+            if !(speed.is_finite() && speed > 0.0) {
+                return Err(ConfigValidationError::InvalidSyslogSpeed(speed));
+            }
+        }
+
+        // Validate HTTP output
+        if let Some(url) = &self.http_url {
+            if !(url.starts_with("http://") || url.starts_with("https://")) {
+                return Err(ConfigValidationError::InvalidHttpUrl(url.clone()));
+            }
+            if !self.get_output_format().map(|format| format.is_json()).unwrap_or(false) {
+                return Err(ConfigValidationError::HttpRequiresJsonFormat(self.output_format.clone()));
+            }
+        } else if self.http_batch_size.is_some()
+            || self.http_format.is_some()
+            || self.http_auth_header.is_some()
+            || self.http_max_retries.is_some()
+            || self.http_dead_letter.is_some()
+        {
+            return Err(ConfigValidationError::HttpOptionWithoutUrl);
+        }
+        if self.http_batch_size == Some(0) {
+            return Err(ConfigValidationError::InvalidHttpBatchSize);
+        }
+        if let (Some(format), Err(_)) = (&self.http_format, self.get_http_body_format()) {
+            return Err(ConfigValidationError::InvalidHttpFormat(format.clone()));
+        }
+        if let (Some(header), Err(_)) = (&self.http_auth_header, self.get_http_auth_header()) {
+            return Err(ConfigValidationError::InvalidHttpHeader(header.clone()));
+        }
+
+        // Validate Parquet output
+        if self.parquet_user_profiles && self.parquet_dir.is_none() {
+            return Err(ConfigValidationError::ParquetOptionWithoutDir);
+        }
+
+        // Validate badge number keying
+        if self.output_fields.key_by_badge_number {
+            if self.sqlite.is_some() {
+                return Err(ConfigValidationError::BadgeKeyUnsupportedOutput("sqlite".to_string()));
+            }
+            if self.parquet_dir.is_none() && !matches!(self.get_output_format(), Ok(OutputFormat::Json | OutputFormat::Csv)) {
+                return Err(ConfigValidationError::BadgeKeyUnsupportedOutput(self.output_format.clone()));
+            }
+        }
+
 
 
         Ok(())
@@ -673,13 +1404,106 @@ impl SimulationConfig {
         (self.min_rooms_per_building, self.max_rooms_per_building)
     }
 
+    /// Get the first simulated day
+    ///
+    /// Uses the configured start date when given. Seeded runs without a start date begin on
+    /// 2024-01-01 so their output does not depend on the day they are run; unseeded runs
+    /// start today.
+    pub fn simulation_start_date(&self) -> NaiveDate {
+        match (self.start_date, self.seed) {
+            (Some(date), _) => date,
+            (None, Some(_)) => NaiveDate::from_ymd_opt(2024, 1, 1).expect("valid fixed start date"),
+            (None, None) => Utc::now().date_naive(),
+        }
+    }
+
     /// Get the output format as an enum-like value
     pub fn get_output_format(&self) -> Result<OutputFormat, String> {
-        match self.output_format.to_lowercase().as_str() {
-            "json" => Ok(OutputFormat::Json),
-            "csv" => Ok(OutputFormat::Csv),
-            _ => Err(format!("Unknown output format: {}", self.output_format)),
+        self.output_format.parse()
+    }
+
+    /// Get the requested output compression (`Compression::None` when not set)
+    pub fn get_compression(&self) -> Result<Compression, String> {
+        match &self.compress {
+            Some(value) => value.parse(),
+            None => Ok(Compression::None),
+        }
+    }
+
+    /// Get the requested syslog transport (`SyslogProtocol::Udp` when not set)
+    pub fn get_syslog_protocol(&self) -> Result<SyslogProtocol, String> {
+        match &self.syslog_protocol {
+            Some(value) => value.parse(),
+            None => Ok(SyslogProtocol::default()),
+        }
+    }
+
+    /// Get the requested HTTP body format (`HttpBodyFormat::Ndjson` when not set)
+    pub fn get_http_body_format(&self) -> Result<HttpBodyFormat, String> {
+        match &self.http_format {
+            Some(value) => value.parse(),
+            None => Ok(HttpBodyFormat::default()),
+        }
+    }
+
+    /// Get the HTTP header to send with every request, split into name and value
+    pub fn get_http_auth_header(&self) -> Result<Option<(String, String)>, String> {
+        let Some(header) = &self.http_auth_header else {
+            return Ok(None);
+        };
+        match header.split_once(':') {
+            Some((name, value)) if !name.trim().is_empty() => {
+                Ok(Some((name.trim().to_string(), value.trim().to_string())))
+            }
+            _ => Err(format!("Invalid HTTP header: {}", header)),
+        }
+    }
+
+    /// Get the names of the configured event destinations other than stdout
+    pub fn event_destinations(&self) -> Vec<&'static str> {
+        let mut destinations = Vec::new();
+        if self.output_dir.is_some() {
+            destinations.push("output_dir");
+        }
+        if self.syslog.is_some() {
+            destinations.push("syslog");
+        }
+        if self.http_url.is_some() {
+            destinations.push("http_url");
+        }
+        if self.sqlite.is_some() {
+            destinations.push("sqlite");
+        }
+        if self.parquet_dir.is_some() {
+            destinations.push("parquet_dir");
         }
+        destinations
+    }
+
+    /// Get the port of the syslog target, if it is a valid host:port pair
+    fn syslog_target_port(&self) -> Option<u16> {
+        let (host, port) = self.syslog.as_deref()?.rsplit_once(':')?;
+        if host.is_empty() {
+            return None;
+        }
+        port.parse().ok()
+    }
+
+    /// Get the user profiles output path, with the compression it should be written with
+    ///
+    /// Defaults to `user_profiles.json`. A `.gz` or `.zst` extension selects compression;
+    /// otherwise the requested compression is applied and its extension appended.
+    pub fn resolved_user_profiles_output(&self) -> (String, Compression) {
+        let path = self.user_profiles_output.as_deref().unwrap_or("user_profiles.json");
+        Compression::resolve_path(path, self.get_compression().unwrap_or_default())
+    }
+
+    /// Get the facility topology output path, with the compression it should be written with
+    ///
+    /// Compression is resolved the same way as for the user profiles file.
+    pub fn resolved_facility_output(&self) -> Option<(String, Compression)> {
+        let path = self.facility_output.as_deref()?;
+        Some(Compression::resolve_path(path, self.get_compression().unwrap_or_default()))
     }
 
     /// Calculate the number of night shift users based on building count
@@ -749,6 +1573,62 @@ mod tests {
         assert_eq!(cli_args.days, 1);
     }
 
+    #[test]
+    fn test_start_date_cli_parsing() {
+        let args = vec!["test", "--start-date", "2026-10-16", "--seed", "7"];
+        let config = SimulationConfig::from_cli_args(CliArgs::try_parse_from(args).unwrap()).unwrap();
+        assert_eq!(config.start_date, NaiveDate::from_ymd_opt(2026, 10, 16));
+        assert_eq!(config.simulation_start_date(), NaiveDate::from_ymd_opt(2026, 10, 16).unwrap());
+
+        // Malformed dates are rejected by the parser
+        assert!(CliArgs::try_parse_from(vec!["test", "--start-date", "16/10/2026"]).is_err());
+    }
+
+    #[test]
+    fn test_calendar_cli_parsing() {
+        let config = SimulationConfig::from_cli_args(CliArgs::try_parse_from(vec!["test"]).unwrap()).unwrap();
+        assert_eq!(config.calendar, WorkCalendarConfig::default());
+        assert_eq!(config.calendar.weekend_days, vec![Weekday::Sat, Weekday::Sun]);
+
+        let args = vec![
+            "test", "--weekend-days", "Fri,Sat", "--holidays", "2024-12-25,2024-12-26", "--half-days", "2024-12-24",
+            "--non-work-day-attendance", "0.1",
+        ];
+        let config = SimulationConfig::from_cli_args(CliArgs::try_parse_from(args).unwrap()).unwrap();
+        assert_eq!(config.calendar.weekend_days, vec![Weekday::Fri, Weekday::Sat]);
+        assert_eq!(config.calendar.holidays.len(), 2);
+        assert_eq!(config.calendar.half_days, vec![NaiveDate::from_ymd_opt(2024, 12, 24).unwrap()]);
+        assert_eq!(config.calendar.non_work_day_attendance, 0.1);
+
+        let config =
+            SimulationConfig::from_cli_args(CliArgs::try_parse_from(vec!["test", "--no-weekends"]).unwrap()).unwrap();
+        assert!(config.calendar.weekend_days.is_empty());
+
+        assert!(CliArgs::try_parse_from(vec!["test", "--no-weekends", "--weekend-days", "Sun"]).is_err());
+        assert!(CliArgs::try_parse_from(vec!["test", "--weekend-days", "Funday"]).is_err());
+        let args = vec!["test", "--non-work-day-attendance", "1.5"];
+        let config = SimulationConfig::from_cli_args(CliArgs::try_parse_from(args).unwrap()).unwrap();
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_output_dir_cli_parsing() {
+        let args = vec![
+            "test", "--output-dir", "events", "--rotate-max-bytes", "1048576", "--rotate-max-events", "5000",
+        ];
+        let config = SimulationConfig::from_cli_args(CliArgs::try_parse_from(args).unwrap()).unwrap();
+        assert_eq!(config.output_dir.as_deref(), Some("events"));
+        assert_eq!(config.rotate_max_bytes, Some(1_048_576));
+        assert_eq!(config.rotate_max_events, Some(5000));
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_seeded_config_without_start_date_uses_fixed_date() {
+        let config = SimulationConfig { seed: Some(42), ..Default::default() };
+        assert_eq!(config.simulation_start_date(), NaiveDate::from_ymd_opt(2024, 1, 1).unwrap());
+    }
+
     #[test]
     fn test_days_validation() {
         // Test that days validation works for valid values
@@ -772,11 +1652,43 @@ mod tests {
             debug: false,
             dry_run: false,
             print_config: false,
+            evaluate: None,
+            evaluate_events: None,
+            infer_room_types: None,
+            detect: Vec::new(),
             include_failure_reason: false,
             include_event_type: false,
             include_metadata: false,
             include_all_fields: false,
+            include_facility_names: false,
+            include_local_offset: false,
+            key_by_badge_number: false,
             days: 7,
+            start_date: None,
+            weekend_days: None,
+            no_weekends: false,
+            holidays: None,
+            half_days: None,
+            non_work_day_attendance: None,
+            output_dir: None,
+            rotate_max_bytes: None,
+            rotate_max_events: None,
+            compress: None,
+            syslog: None,
+            syslog_protocol: None,
+            syslog_speed: None,
+            http_url: None,
+            http_batch_size: None,
+            http_format: None,
+            http_auth_header: None,
+            http_max_retries: None,
+            http_dead_letter: None,
+            sqlite: None,
+            parquet_dir: None,
+            parquet_user_profiles: false,
+            facility_output: None,
+            facility_definition: None,
+            user_roster: None,
         };
         
         let config = SimulationConfig::from_cli_args(args).unwrap();
@@ -847,11 +1759,43 @@ mod tests {
             debug: false,
             dry_run: false,
             print_config: false,
+            evaluate: None,
+            evaluate_events: None,
+            infer_room_types: None,
+            detect: Vec::new(),
             include_failure_reason: false,
             include_event_type: false,
             include_metadata: false,
             include_all_fields: false,
+            include_facility_names: false,
+            include_local_offset: false,
+            key_by_badge_number: false,
             days: 3,
+            start_date: None,
+            weekend_days: None,
+            no_weekends: false,
+            holidays: None,
+            half_days: None,
+            non_work_day_attendance: None,
+            output_dir: None,
+            rotate_max_bytes: None,
+            rotate_max_events: None,
+            compress: None,
+            syslog: None,
+            syslog_protocol: None,
+            syslog_speed: None,
+            http_url: None,
+            http_batch_size: None,
+            http_format: None,
+            http_auth_header: None,
+            http_max_retries: None,
+            http_dead_letter: None,
+            sqlite: None,
+            parquet_dir: None,
+            parquet_user_profiles: false,
+            facility_output: None,
+            facility_definition: None,
+            user_roster: None,
         };
 
         let config = SimulationConfig::from_cli_args(args).unwrap();
@@ -874,8 +1818,10 @@ mod tests {
 
     #[test]
     fn test_simulation_config_validation_user_count() {
-        let mut config = SimulationConfig::default();
-        config.user_count = 0;
+        let config = SimulationConfig {
+            user_count: 0,
+            ..Default::default()
+        };
 
         match config.validate() {
             Err(ConfigValidationError::InvalidUserCount(0)) => {}
@@ -885,8 +1831,10 @@ mod tests {
 
     #[test]
     fn test_simulation_config_validation_location_count() {
-        let mut config = SimulationConfig::default();
-        config.location_count = 0;
+        let config = SimulationConfig {
+            location_count: 0,
+            ..Default::default()
+        };
 
         match config.validate() {
             Err(ConfigValidationError::InvalidLocationCount(0)) => {}
@@ -896,9 +1844,11 @@ mod tests {
 
     #[test]
     fn test_simulation_config_validation_building_range() {
-        let mut config = SimulationConfig::default();
-        config.min_buildings_per_location = 10;
-        config.max_buildings_per_location = 5;
+        let config = SimulationConfig {
+            min_buildings_per_location: 10,
+            max_buildings_per_location: 5,
+            ..Default::default()
+        };
 
         match config.validate() {
             Err(ConfigValidationError::InvalidBuildingRange(10, 5)) => {}
@@ -908,9 +1858,11 @@ mod tests {
 
     #[test]
     fn test_simulation_config_validation_room_range() {
-        let mut config = SimulationConfig::default();
-        config.min_rooms_per_building = 20;
-        config.max_rooms_per_building = 10;
+        let config = SimulationConfig {
+            min_rooms_per_building: 20,
+            max_rooms_per_building: 10,
+            ..Default::default()
+        };
 
         match config.validate() {
             Err(ConfigValidationError::InvalidRoomRange(20, 10)) => {}
@@ -920,8 +1872,10 @@ mod tests {
 
     #[test]
     fn test_simulation_config_validation_percentage() {
-        let mut config = SimulationConfig::default();
-        config.curious_user_percentage = 1.5;
+        let config = SimulationConfig {
+            curious_user_percentage: 1.5,
+            ..Default::default()
+        };
 
         match config.validate() {
             Err(ConfigValidationError::InvalidPercentage { field, value }) => {
@@ -934,10 +1888,12 @@ mod tests {
 
     #[test]
     fn test_simulation_config_validation_affinity_sum() {
-        let mut config = SimulationConfig::default();
-        config.primary_building_affinity = 0.5;
-        config.same_location_travel = 0.3;
-        config.different_location_travel = 0.3; // Sum = 1.1
+        let config = SimulationConfig {
+            primary_building_affinity: 0.5,
+            same_location_travel: 0.3,
+            different_location_travel: 0.3, // Sum = 1.1
+            ..Default::default()
+        };
 
         match config.validate() {
             Err(ConfigValidationError::InvalidAffinitySum { sum }) => {
@@ -947,6 +1903,239 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_simulation_config_validation_output_format() {
+        let config = SimulationConfig { output_format: "xml".to_string(), ..Default::default() };
+
+        match config.validate() {
+            Err(ConfigValidationError::InvalidOutputFormat(format)) => assert_eq!(format, "xml"),
+            _ => panic!("Expected InvalidOutputFormat error"),
+        }
+
+        let config = SimulationConfig { output_format: "CSV".to_string(), ..Default::default() };
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_compress_cli_parsing_and_validation() {
+        let args = vec!["test", "--compress", "zstd", "--user-profiles-output", "profiles.jsonl"];
+        let config = SimulationConfig::from_cli_args(CliArgs::try_parse_from(args).unwrap()).unwrap();
+        assert_eq!(config.get_compression().unwrap(), Compression::Zstd);
+        assert_eq!(
+            config.resolved_user_profiles_output(),
+            ("profiles.jsonl.zst".to_string(), Compression::Zstd)
+        );
+        assert!(config.validate().is_ok());
+
+        let config = SimulationConfig { compress: Some("bzip2".to_string()), ..Default::default() };
+        match config.validate() {
+            Err(ConfigValidationError::InvalidCompression(value)) => assert_eq!(value, "bzip2"),
+            _ => panic!("Expected InvalidCompression error"),
+        }
+    }
+
+    #[test]
+    fn test_user_profiles_output_compression_from_extension() {
+        let config = SimulationConfig {
+            user_profiles_output: Some("profiles.jsonl.gz".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            config.resolved_user_profiles_output(),
+            ("profiles.jsonl.gz".to_string(), Compression::Gzip)
+        );
+        assert_eq!(
+            SimulationConfig::default().resolved_user_profiles_output(),
+            ("user_profiles.json".to_string(), Compression::None)
+        );
+    }
+
+    #[test]
+    fn test_simulation_config_validation_rotation() {
+        let config = SimulationConfig {
+            output_dir: Some("events".to_string()),
+            rotate_max_events: Some(0),
+            ..Default::default()
+        };
+        match config.validate() {
+            Err(ConfigValidationError::InvalidRotationThreshold(field)) => assert_eq!(field, "rotate_max_events"),
+            _ => panic!("Expected InvalidRotationThreshold error"),
+        }
+
+        let config = SimulationConfig { rotate_max_bytes: Some(1024), ..Default::default() };
+        assert!(matches!(config.validate(), Err(ConfigValidationError::RotationWithoutOutputDir)));
+    }
+
+    #[test]
+    fn test_syslog_cli_parsing_and_validation() {
+        let args = vec![
+            "test", "--syslog", "127.0.0.1:514", "--syslog-protocol", "tcp", "--syslog-speed", "60",
+        ];
+        let config = SimulationConfig::from_cli_args(CliArgs::try_parse_from(args).unwrap()).unwrap();
+        assert_eq!(config.syslog.as_deref(), Some("127.0.0.1:514"));
+        assert_eq!(config.get_syslog_protocol().unwrap(), SyslogProtocol::Tcp);
+        assert_eq!(config.syslog_speed, Some(60.0));
+        assert_eq!(config.event_destinations(), vec!["syslog"]);
+        assert!(config.validate().is_ok());
+
+        let config = SimulationConfig { syslog: Some("localhost".to_string()), ..Default::default() };
+        assert!(matches!(config.validate(), Err(ConfigValidationError::InvalidSyslogTarget(_))));
+
+        let config = SimulationConfig {
+            syslog: Some("localhost:514".to_string()),
+            syslog_protocol: Some("tls".to_string()),
+            ..Default::default()
+        };
+        assert!(matches!(config.validate(), Err(ConfigValidationError::InvalidSyslogProtocol(_))));
+
+        let config = SimulationConfig {
+            syslog: Some("localhost:514".to_string()),
+            syslog_speed: Some(0.0),
+            ..Default::default()
+        };
+        assert!(matches!(config.validate(), Err(ConfigValidationError::InvalidSyslogSpeed(_))));
+
+        let config = SimulationConfig { syslog_protocol: Some("tcp".to_string()), ..Default::default() };
+        assert!(matches!(config.validate(), Err(ConfigValidationError::SyslogOptionWithoutTarget)));
+
+        let config = SimulationConfig {
+            output_dir: Some("events".to_string()),
+            syslog: Some("localhost:514".to_string()),
+            ..Default::default()
+        };
+        match config.validate() {
+            Err(ConfigValidationError::ConflictingEventDestinations(destinations)) => {
+                assert_eq!(destinations, "output_dir, syslog")
+            }
+            _ => panic!("Expected ConflictingEventDestinations error"),
+        }
+    }
+
+    #[test]
+    fn test_http_cli_parsing_and_validation() {
+        let args = vec![
+            "test",
+            "--http-url", "http://localhost:8080/ingest",
+            "--http-batch-size", "100",
+            "--http-format", "json",
+            "--http-auth-header", "Authorization: Bearer secret",
+            "--http-max-retries", "5",
+            "--http-dead-letter", "failed.jsonl",
+        ];
+        let config = SimulationConfig::from_cli_args(CliArgs::try_parse_from(args).unwrap()).unwrap();
+        assert_eq!(config.http_url.as_deref(), Some("http://localhost:8080/ingest"));
+        assert_eq!(config.http_batch_size, Some(100));
+        assert_eq!(config.get_http_body_format().unwrap(), HttpBodyFormat::JsonArray);
+        assert_eq!(
+            config.get_http_auth_header().unwrap(),
+            Some(("Authorization".to_string(), "Bearer secret".to_string()))
+        );
+        assert_eq!(config.http_max_retries, Some(5));
+        assert_eq!(config.http_dead_letter.as_deref(), Some("failed.jsonl"));
+        assert!(config.validate().is_ok());
+
+        let http = |url: &str| SimulationConfig { http_url: Some(url.to_string()), ..Default::default() };
+        assert!(matches!(http("ftp://host/ingest").validate(), Err(ConfigValidationError::InvalidHttpUrl(_))));
+
+        let config = SimulationConfig { output_format: "csv".to_string(), ..http("http://localhost/ingest") };
+        assert!(matches!(config.validate(), Err(ConfigValidationError::HttpRequiresJsonFormat(_))));
+
+        let config = SimulationConfig { http_batch_size: Some(0), ..http("http://localhost/ingest") };
+        assert!(matches!(config.validate(), Err(ConfigValidationError::InvalidHttpBatchSize)));
+
+        let config = SimulationConfig {
+            http_auth_header: Some("no separator".to_string()),
+            ..http("http://localhost/ingest")
+        };
+        assert!(matches!(config.validate(), Err(ConfigValidationError::InvalidHttpHeader(_))));
+
+        let config = SimulationConfig { http_dead_letter: Some("failed.jsonl".to_string()), ..Default::default() };
+        assert!(matches!(config.validate(), Err(ConfigValidationError::HttpOptionWithoutUrl)));
+    }
+
+    #[test]
+    fn test_sqlite_cli_parsing_and_validation() {
+        let args = vec!["test", "--sqlite", "simulation.db"];
+        let config = SimulationConfig::from_cli_args(CliArgs::try_parse_from(args).unwrap()).unwrap();
+        assert_eq!(config.sqlite.as_deref(), Some("simulation.db"));
+        assert!(config.validate().is_ok());
+
+        let config = SimulationConfig {
+            sqlite: Some("simulation.db".to_string()),
+            http_url: Some("http://localhost/ingest".to_string()),
+            ..Default::default()
+        };
+        match config.validate() {
+            Err(ConfigValidationError::ConflictingEventDestinations(destinations)) => {
+                assert_eq!(destinations, "http_url, sqlite")
+            }
+            _ => panic!("Expected ConflictingEventDestinations error"),
+        }
+    }
+
+    #[test]
+    fn test_facility_output_cli_parsing() {
+        let args = vec!["test", "--facility-output", "facilities.csv", "--compress", "gzip"];
+        let config = SimulationConfig::from_cli_args(CliArgs::try_parse_from(args).unwrap()).unwrap();
+        assert_eq!(config.facility_output.as_deref(), Some("facilities.csv"));
+        assert_eq!(
+            config.resolved_facility_output(),
+            Some(("facilities.csv.gz".to_string(), Compression::Gzip))
+        );
+        assert_eq!(SimulationConfig::default().resolved_facility_output(), None);
+
+        let args = vec!["test", "--facility-definition", "site.yaml"];
+        let config = SimulationConfig::from_cli_args(CliArgs::try_parse_from(args).unwrap()).unwrap();
+        assert_eq!(config.facility_definition.as_deref(), Some("site.yaml"));
+
+        let args = vec!["test", "--user-roster", "roster.csv"];
+        let config = SimulationConfig::from_cli_args(CliArgs::try_parse_from(args).unwrap()).unwrap();
+        assert_eq!(config.user_roster.as_deref(), Some("roster.csv"));
+    }
+
+    #[test]
+    fn test_parquet_cli_parsing_and_validation() {
+        let args = vec!["test", "--parquet-dir", "parquet", "--parquet-user-profiles"];
+        let config = SimulationConfig::from_cli_args(CliArgs::try_parse_from(args).unwrap()).unwrap();
+        assert_eq!(config.parquet_dir.as_deref(), Some("parquet"));
+        assert!(config.parquet_user_profiles);
+        assert!(config.validate().is_ok());
+
+        let config = SimulationConfig { parquet_user_profiles: true, ..Default::default() };
+        assert!(matches!(config.validate(), Err(ConfigValidationError::ParquetOptionWithoutDir)));
+
+        let config = SimulationConfig {
+            parquet_dir: Some("parquet".to_string()),
+            sqlite: Some("simulation.db".to_string()),
+            ..Default::default()
+        };
+        match config.validate() {
+            Err(ConfigValidationError::ConflictingEventDestinations(destinations)) => {
+                assert_eq!(destinations, "sqlite, parquet_dir")
+            }
+            _ => panic!("Expected ConflictingEventDestinations error"),
+        }
+    }
+
+    #[test]
+    fn test_key_by_badge_number_cli_parsing_and_validation() {
+        let args = vec!["test", "--key-by-badge-number", "--output-format", "csv"];
+        let config = SimulationConfig::from_cli_args(CliArgs::try_parse_from(args).unwrap()).unwrap();
+        assert!(config.output_fields.key_by_badge_number);
+        assert!(config.validate().is_ok());
+
+        let mut config = SimulationConfig { output_format: "cef".to_string(), ..Default::default() };
+        config.output_fields.key_by_badge_number = true;
+        assert!(matches!(config.validate(), Err(ConfigValidationError::BadgeKeyUnsupportedOutput(_))));
+        config.parquet_dir = Some("parquet".to_string());
+        assert!(config.validate().is_ok());
+
+        config.parquet_dir = None;
+        config.output_format = "json".to_string();
+        config.sqlite = Some("simulation.db".to_string());
+        assert!(matches!(config.validate(), Err(ConfigValidationError::BadgeKeyUnsupportedOutput(_))));
+    }
+
     #[test]
     fn test_simulation_config_helper_methods() {
         let config = SimulationConfig::default();
@@ -979,11 +2168,43 @@ mod tests {
             debug: false,
             dry_run: false,
             print_config: false,
+            evaluate: None,
+            evaluate_events: None,
+            infer_room_types: None,
+            detect: Vec::new(),
             include_failure_reason: true,
             include_event_type: false,
             include_metadata: true,
             include_all_fields: false,
+            include_facility_names: false,
+            include_local_offset: false,
+            key_by_badge_number: false,
             days: 1,
+            start_date: None,
+            weekend_days: None,
+            no_weekends: false,
+            holidays: None,
+            half_days: None,
+            non_work_day_attendance: None,
+            output_dir: None,
+            rotate_max_bytes: None,
+            rotate_max_events: None,
+            compress: None,
+            syslog: None,
+            syslog_protocol: None,
+            syslog_speed: None,
+            http_url: None,
+            http_batch_size: None,
+            http_format: None,
+            http_auth_header: None,
+            http_max_retries: None,
+            http_dead_letter: None,
+            sqlite: None,
+            parquet_dir: None,
+            parquet_user_profiles: false,
+            facility_output: None,
+            facility_definition: None,
+            user_roster: None,
         };
 
         let config = SimulationConfig::from_cli_args(args).unwrap();
@@ -1017,11 +2238,43 @@ mod tests {
             debug: false,
             dry_run: false,
             print_config: false,
+            evaluate: None,
+            evaluate_events: None,
+            infer_room_types: None,
+            detect: Vec::new(),
             include_failure_reason: false,
             include_event_type: false,
             include_metadata: false,
             include_all_fields: true,
+            include_facility_names: false,
+            include_local_offset: false,
+            key_by_badge_number: false,
             days: 1,
+            start_date: None,
+            weekend_days: None,
+            no_weekends: false,
+            holidays: None,
+            half_days: None,
+            non_work_day_attendance: None,
+            output_dir: None,
+            rotate_max_bytes: None,
+            rotate_max_events: None,
+            compress: None,
+            syslog: None,
+            syslog_protocol: None,
+            syslog_speed: None,
+            http_url: None,
+            http_batch_size: None,
+            http_format: None,
+            http_auth_header: None,
+            http_max_retries: None,
+            http_dead_letter: None,
+            sqlite: None,
+            parquet_dir: None,
+            parquet_user_profiles: false,
+            facility_output: None,
+            facility_definition: None,
+            user_roster: None,
         };
 
         let config = SimulationConfig::from_cli_args(args).unwrap();
@@ -1056,11 +2309,43 @@ mod tests {
             debug: false,
             dry_run: false,
             print_config: false,
+            evaluate: None,
+            evaluate_events: None,
+            infer_room_types: None,
+            detect: Vec::new(),
             include_failure_reason: false,
             include_event_type: false,
             include_metadata: false,
             include_all_fields: false,
+            include_facility_names: false,
+            include_local_offset: false,
+            key_by_badge_number: false,
             days: 1,
+            start_date: None,
+            weekend_days: None,
+            no_weekends: false,
+            holidays: None,
+            half_days: None,
+            non_work_day_attendance: None,
+            output_dir: None,
+            rotate_max_bytes: None,
+            rotate_max_events: None,
+            compress: None,
+            syslog: None,
+            syslog_protocol: None,
+            syslog_speed: None,
+            http_url: None,
+            http_batch_size: None,
+            http_format: None,
+            http_auth_header: None,
+            http_max_retries: None,
+            http_dead_letter: None,
+            sqlite: None,
+            parquet_dir: None,
+            parquet_user_profiles: false,
+            facility_output: None,
+            facility_definition: None,
+            user_roster: None,
         };
 
         let config = SimulationConfig::from_cli_args(args).unwrap();
@@ -1151,14 +2436,22 @@ mod tests {
 
     #[test]
     fn test_output_format_parsing() {
-        let mut config = SimulationConfig::default();
-
-        config.output_format = "json".to_string();
+        let mut config =
+            SimulationConfig { output_format: "json".to_string(), ..Default::default() };
         assert!(matches!(config.get_output_format().unwrap(), OutputFormat::Json));
 
         config.output_format = "csv".to_string();
         assert!(matches!(config.get_output_format().unwrap(), OutputFormat::Csv));
 
+        config.output_format = "ocsf".to_string();
+        assert!(matches!(config.get_output_format().unwrap(), OutputFormat::Ocsf));
+
+        config.output_format = "ecs".to_string();
+        assert!(matches!(config.get_output_format().unwrap(), OutputFormat::Ecs));
+
+        config.output_format = "leef".to_string();
+        assert!(matches!(config.get_output_format().unwrap(), OutputFormat::Leef));
+
         config.output_format = "invalid".to_string();
         assert!(config.get_output_format().is_err());
     }
@@ -1180,6 +2473,9 @@ mod tests {
             include_event_type: false,
             include_metadata: true,
             include_all: false,
+            include_facility_names: false,
+            include_local_offset: false,
+            key_by_badge_number: false,
         };
 
         // Test serialization
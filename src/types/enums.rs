@@ -300,6 +300,14 @@ pub enum OutputFormat {
     Json,
     /// CSV format for tabular data
     Csv,
+    /// Open Cybersecurity Schema Framework (OCSF) Authentication events as JSON lines
+    Ocsf,
+    /// Elastic Common Schema (ECS) documents as JSON lines
+    Ecs,
+    /// ArcSight Common Event Format (CEF) lines
+    Cef,
+    /// QRadar Log Event Extended Format (LEEF) lines
+    Leef,
 }
 
 impl fmt::Display for OutputFormat {
@@ -307,6 +315,10 @@ impl fmt::Display for OutputFormat {
         match self {
             OutputFormat::Json => write!(f, "JSON"),
             OutputFormat::Csv => write!(f, "CSV"),
+            OutputFormat::Ocsf => write!(f, "OCSF"),
+            OutputFormat::Ecs => write!(f, "ECS"),
+            OutputFormat::Cef => write!(f, "CEF"),
+            OutputFormat::Leef => write!(f, "LEEF"),
         }
     }
 }
@@ -318,11 +330,163 @@ impl FromStr for OutputFormat {
         match s.to_lowercase().as_str() {
             "json" => Ok(OutputFormat::Json),
             "csv" => Ok(OutputFormat::Csv),
+            "ocsf" => Ok(OutputFormat::Ocsf),
+            "ecs" => Ok(OutputFormat::Ecs),
+            "cef" => Ok(OutputFormat::Cef),
+            "leef" => Ok(OutputFormat::Leef),
             _ => Err(format!("Unknown output format: {}", s)),
         }
     }
 }
 
+impl OutputFormat {
+    /// Check whether each formatted event is a JSON document
+    pub fn is_json(&self) -> bool {
+        matches!(self, OutputFormat::Json | OutputFormat::Ocsf | OutputFormat::Ecs)
+    }
+}
+
+/// Compression applied to output files
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum Compression {
+    /// Uncompressed output
+    #[default]
+    None,
+    /// gzip compression (`.gz`)
+    Gzip,
+    /// Zstandard compression (`.zst`)
+    Zstd,
+}
+
+impl Compression {
+    /// Get the file extension (without the dot) for this compression, if any
+    pub fn extension(&self) -> Option<&'static str> {
+        match self {
+            Compression::None => None,
+            Compression::Gzip => Some("gz"),
+            Compression::Zstd => Some("zst"),
+        }
+    }
+
+    /// Detect the compression implied by a file name's extension
+    pub fn from_path<P: AsRef<std::path::Path>>(path: P) -> Self {
+        match path.as_ref().extension().and_then(|ext| ext.to_str()) {
+            Some("gz") => Compression::Gzip,
+            Some("zst") | Some("zstd") => Compression::Zstd,
+            _ => Compression::None,
+        }
+    }
+
+    /// Resolve the compression and final path for an output file
+    ///
+    /// A compression extension on the path takes precedence. Otherwise the requested
+    /// compression is used and its extension is appended to the path.
+    pub fn resolve_path(path: &str, requested: Compression) -> (String, Compression) {
+        match Compression::from_path(path) {
+            Compression::None => match requested.extension() {
+                Some(extension) => (format!("{}.{}", path, extension), requested),
+                None => (path.to_string(), Compression::None),
+            },
+            detected => (path.to_string(), detected),
+        }
+    }
+}
+
+impl fmt::Display for Compression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Compression::None => write!(f, "none"),
+            Compression::Gzip => write!(f, "gzip"),
+            Compression::Zstd => write!(f, "zstd"),
+        }
+    }
+}
+
+impl FromStr for Compression {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "none" => Ok(Compression::None),
+            "gzip" | "gz" => Ok(Compression::Gzip),
+            "zstd" | "zst" => Ok(Compression::Zstd),
+            _ => Err(format!("Unknown compression: {}", s)),
+        }
+    }
+}
+
+/// Transport used to send syslog messages
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum SyslogProtocol {
+    /// One message per UDP datagram
+    #[default]
+    Udp,
+    /// Octet-counted messages over a TCP stream (RFC 6587)
+    Tcp,
+}
+
+impl fmt::Display for SyslogProtocol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SyslogProtocol::Udp => write!(f, "udp"),
+            SyslogProtocol::Tcp => write!(f, "tcp"),
+        }
+    }
+}
+
+impl FromStr for SyslogProtocol {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "udp" => Ok(SyslogProtocol::Udp),
+            "tcp" => Ok(SyslogProtocol::Tcp),
+            _ => Err(format!("Unknown syslog protocol: {}", s)),
+        }
+    }
+}
+
+/// Request body layout for batches posted to an HTTP endpoint
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum HttpBodyFormat {
+    /// Newline-delimited JSON, one event per line (`application/x-ndjson`)
+    #[default]
+    Ndjson,
+    /// A single JSON array of events (`application/json`)
+    JsonArray,
+}
+
+impl HttpBodyFormat {
+    /// Get the Content-Type header value for this body format
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            HttpBodyFormat::Ndjson => "application/x-ndjson",
+            HttpBodyFormat::JsonArray => "application/json",
+        }
+    }
+}
+
+impl fmt::Display for HttpBodyFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HttpBodyFormat::Ndjson => write!(f, "ndjson"),
+            HttpBodyFormat::JsonArray => write!(f, "json"),
+        }
+    }
+}
+
+impl FromStr for HttpBodyFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "ndjson" | "jsonl" => Ok(HttpBodyFormat::Ndjson),
+            "json" | "array" => Ok(HttpBodyFormat::JsonArray),
+            _ => Err(format!("Unknown HTTP body format: {}", s)),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -425,17 +589,76 @@ mod tests {
     fn test_output_format_display() {
         assert_eq!(format!("{}", OutputFormat::Json), "JSON");
         assert_eq!(format!("{}", OutputFormat::Csv), "CSV");
+        assert_eq!(format!("{}", OutputFormat::Ocsf), "OCSF");
+        assert_eq!(format!("{}", OutputFormat::Ecs), "ECS");
+        assert_eq!(format!("{}", OutputFormat::Cef), "CEF");
+        assert_eq!(format!("{}", OutputFormat::Leef), "LEEF");
     }
 
     #[test]
     fn test_output_format_from_str() {
         assert_eq!("json".parse::<OutputFormat>().unwrap(), OutputFormat::Json);
         assert_eq!("csv".parse::<OutputFormat>().unwrap(), OutputFormat::Csv);
+        assert_eq!("OCSF".parse::<OutputFormat>().unwrap(), OutputFormat::Ocsf);
+        assert_eq!("ecs".parse::<OutputFormat>().unwrap(), OutputFormat::Ecs);
+        assert_eq!("cef".parse::<OutputFormat>().unwrap(), OutputFormat::Cef);
+        assert_eq!("leef".parse::<OutputFormat>().unwrap(), OutputFormat::Leef);
 
         // Test error case
         assert!("invalid".parse::<OutputFormat>().is_err());
     }
 
+    #[test]
+    fn test_compression_from_str() {
+        assert_eq!("gzip".parse::<Compression>().unwrap(), Compression::Gzip);
+        assert_eq!("ZSTD".parse::<Compression>().unwrap(), Compression::Zstd);
+        assert_eq!("none".parse::<Compression>().unwrap(), Compression::None);
+        assert!("bzip2".parse::<Compression>().is_err());
+    }
+
+    #[test]
+    fn test_syslog_protocol_from_str() {
+        assert_eq!("udp".parse::<SyslogProtocol>().unwrap(), SyslogProtocol::Udp);
+        assert_eq!("TCP".parse::<SyslogProtocol>().unwrap(), SyslogProtocol::Tcp);
+        assert_eq!(SyslogProtocol::default(), SyslogProtocol::Udp);
+        assert_eq!(format!("{}", SyslogProtocol::Tcp), "tcp");
+        assert!("tls".parse::<SyslogProtocol>().is_err());
+    }
+
+    #[test]
+    fn test_http_body_format_from_str() {
+        assert_eq!("ndjson".parse::<HttpBodyFormat>().unwrap(), HttpBodyFormat::Ndjson);
+        assert_eq!("JSON".parse::<HttpBodyFormat>().unwrap(), HttpBodyFormat::JsonArray);
+        assert_eq!(HttpBodyFormat::default().content_type(), "application/x-ndjson");
+        assert!("xml".parse::<HttpBodyFormat>().is_err());
+    }
+
+    #[test]
+    fn test_output_format_is_json() {
+        assert!(OutputFormat::Json.is_json());
+        assert!(OutputFormat::Ecs.is_json());
+        assert!(!OutputFormat::Csv.is_json());
+        assert!(!OutputFormat::Cef.is_json());
+    }
+
+    #[test]
+    fn test_compression_resolve_path() {
+        // Extension takes precedence over the requested compression
+        assert_eq!(
+            Compression::resolve_path("profiles.jsonl.zst", Compression::Gzip),
+            ("profiles.jsonl.zst".to_string(), Compression::Zstd)
+        );
+        // Requested compression appends its extension
+        assert_eq!(
+            Compression::resolve_path("profiles.jsonl", Compression::Gzip),
+            ("profiles.jsonl.gz".to_string(), Compression::Gzip)
+        );
+        assert_eq!(
+            Compression::resolve_path("profiles.jsonl", Compression::None),
+            ("profiles.jsonl".to_string(), Compression::None)
+        );
+    }
+
     #[test]
     fn test_enum_serialization() {
         // Test that enums can be serialized and deserialized
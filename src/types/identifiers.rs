@@ -3,8 +3,10 @@
 //! This module contains UUID-based identifier types for users, locations,
 //! buildings, and rooms used throughout the simulation system.
 
+use rand::Rng;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::fmt;
+use std::str::FromStr;
 use uuid::Uuid;
 
 /// Unique identifier for a user
@@ -16,6 +18,11 @@ impl UserId {
     pub fn new() -> Self {
         Self(Uuid::new_v4())
     }
+
+    /// Create a user ID from the given random number generator (reproducible when seeded)
+    pub fn from_rng<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        Self(uuid::Builder::from_random_bytes(rng.gen()).into_uuid())
+    }
 }
 
 impl Default for UserId {
@@ -39,20 +46,22 @@ impl Serialize for UserId {
     }
 }
 
+impl FromStr for UserId {
+    type Err = uuid::Error;
+
+    /// Parse a `USER_`-prefixed id, or a raw UUID for backward compatibility
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Uuid::parse_str(s.strip_prefix("USER_").unwrap_or(s)).map(UserId)
+    }
+}
+
 impl<'de> Deserialize<'de> for UserId {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
         let s = String::deserialize(deserializer)?;
-        if let Some(uuid_str) = s.strip_prefix("USER_") {
-            let uuid = Uuid::parse_str(uuid_str).map_err(serde::de::Error::custom)?;
-            Ok(UserId(uuid))
-        } else {
-            // Fallback: try to parse as raw UUID for backward compatibility
-            let uuid = Uuid::parse_str(&s).map_err(serde::de::Error::custom)?;
-            Ok(UserId(uuid))
-        }
+        s.parse().map_err(serde::de::Error::custom)
     }
 }
 
@@ -65,6 +74,11 @@ impl LocationId {
     pub fn new() -> Self {
         Self(Uuid::new_v4())
     }
+
+    /// Create a location ID from the given random number generator (reproducible when seeded)
+    pub fn from_rng<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        Self(uuid::Builder::from_random_bytes(rng.gen()).into_uuid())
+    }
 }
 
 impl Default for LocationId {
@@ -88,20 +102,22 @@ impl Serialize for LocationId {
     }
 }
 
+impl FromStr for LocationId {
+    type Err = uuid::Error;
+
+    /// Parse a `LOC_`-prefixed id, or a raw UUID for backward compatibility
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Uuid::parse_str(s.strip_prefix("LOC_").unwrap_or(s)).map(LocationId)
+    }
+}
+
 impl<'de> Deserialize<'de> for LocationId {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
         let s = String::deserialize(deserializer)?;
-        if let Some(uuid_str) = s.strip_prefix("LOC_") {
-            let uuid = Uuid::parse_str(uuid_str).map_err(serde::de::Error::custom)?;
-            Ok(LocationId(uuid))
-        } else {
-            // Fallback: try to parse as raw UUID for backward compatibility
-            let uuid = Uuid::parse_str(&s).map_err(serde::de::Error::custom)?;
-            Ok(LocationId(uuid))
-        }
+        s.parse().map_err(serde::de::Error::custom)
     }
 }
 
@@ -114,6 +130,11 @@ impl BuildingId {
     pub fn new() -> Self {
         Self(Uuid::new_v4())
     }
+
+    /// Create a building ID from the given random number generator (reproducible when seeded)
+    pub fn from_rng<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        Self(uuid::Builder::from_random_bytes(rng.gen()).into_uuid())
+    }
 }
 
 impl Default for BuildingId {
@@ -137,20 +158,22 @@ impl Serialize for BuildingId {
     }
 }
 
+impl FromStr for BuildingId {
+    type Err = uuid::Error;
+
+    /// Parse a `BLD_`-prefixed id, or a raw UUID for backward compatibility
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Uuid::parse_str(s.strip_prefix("BLD_").unwrap_or(s)).map(BuildingId)
+    }
+}
+
 impl<'de> Deserialize<'de> for BuildingId {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
         let s = String::deserialize(deserializer)?;
-        if let Some(uuid_str) = s.strip_prefix("BLD_") {
-            let uuid = Uuid::parse_str(uuid_str).map_err(serde::de::Error::custom)?;
-            Ok(BuildingId(uuid))
-        } else {
-            // Fallback: try to parse as raw UUID for backward compatibility
-            let uuid = Uuid::parse_str(&s).map_err(serde::de::Error::custom)?;
-            Ok(BuildingId(uuid))
-        }
+        s.parse().map_err(serde::de::Error::custom)
     }
 }
 
@@ -163,6 +186,11 @@ impl RoomId {
     pub fn new() -> Self {
         Self(Uuid::new_v4())
     }
+
+    /// Create a room ID from the given random number generator (reproducible when seeded)
+    pub fn from_rng<R: Rng + ?Sized>(rng: &mut R) -> Self {
+        Self(uuid::Builder::from_random_bytes(rng.gen()).into_uuid())
+    }
 }
 
 impl Default for RoomId {
@@ -186,20 +214,22 @@ impl Serialize for RoomId {
     }
 }
 
+impl FromStr for RoomId {
+    type Err = uuid::Error;
+
+    /// Parse a `ROOM_`-prefixed id, or a raw UUID for backward compatibility
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Uuid::parse_str(s.strip_prefix("ROOM_").unwrap_or(s)).map(RoomId)
+    }
+}
+
 impl<'de> Deserialize<'de> for RoomId {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
         let s = String::deserialize(deserializer)?;
-        if let Some(uuid_str) = s.strip_prefix("ROOM_") {
-            let uuid = Uuid::parse_str(uuid_str).map_err(serde::de::Error::custom)?;
-            Ok(RoomId(uuid))
-        } else {
-            // Fallback: try to parse as raw UUID for backward compatibility
-            let uuid = Uuid::parse_str(&s).map_err(serde::de::Error::custom)?;
-            Ok(RoomId(uuid))
-        }
+        s.parse().map_err(serde::de::Error::custom)
     }
 }
 
@@ -402,4 +432,23 @@ mod tests {
         assert!(set.contains(&id2));
         assert!(set.contains(&id1_copy));
     }
+
+    #[test]
+    fn test_id_from_seeded_rng_is_reproducible() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        let mut rng_a = StdRng::seed_from_u64(42);
+        let mut rng_b = StdRng::seed_from_u64(42);
+
+        assert_eq!(UserId::from_rng(&mut rng_a), UserId::from_rng(&mut rng_b));
+        assert_eq!(LocationId::from_rng(&mut rng_a), LocationId::from_rng(&mut rng_b));
+        assert_eq!(BuildingId::from_rng(&mut rng_a), BuildingId::from_rng(&mut rng_b));
+        assert_eq!(RoomId::from_rng(&mut rng_a), RoomId::from_rng(&mut rng_b));
+
+        // Successive draws from the same stream must still be unique
+        assert_ne!(RoomId::from_rng(&mut rng_a), RoomId::from_rng(&mut rng_a));
+
+        // Seeded IDs are still valid version 4 UUIDs
+        assert_eq!(UserId::from_rng(&mut rng_a).0.get_version_num(), 4);
+    }
 }
@@ -9,9 +9,9 @@ use std::fmt;
 
 use crate::facility::LocationRegistry;
 use crate::permissions::{PermissionLevel, PermissionSet};
-use crate::types::{BuildingId, LocationId, RoomId, RoomType, SimulationConfig};
+use crate::types::{BuildingId, LocationId, RoomId, RoomType, SimulationConfig, UserId};
 
-use crate::user::{BehaviorProfile, User};
+use crate::user::{assign_identities, BehaviorProfile, User, UserRoster};
 
 /// Generator for creating users with primary assignments and permission logic
 pub struct UserGenerator {
@@ -99,6 +99,7 @@ impl UserGenerator {
             // Create the user
             let mut user =
                 User::new(location_id, building_id, workspace_room_id, permissions);
+            user.id = UserId::from_rng(&mut *self.rng);
 
             // Mark as curious based on percentage (adjust for night-shift users)
             if i < curious_count {
@@ -123,9 +124,92 @@ impl UserGenerator {
         use rand::seq::SliceRandom;
         users.shuffle(&mut *self.rng);
 
+        assign_identities(&mut users);
+
         Ok(users)
     }
 
+    /// Build users from an imported roster instead of generating them
+    ///
+    /// Assignments and grants come from the roster. Curious and cloned badge flags left empty
+    /// in the roster are filled at random so that the configured percentages of the whole
+    /// roster are reached. Night-shift users are taken from the roster only and patrol their
+    /// home building, but only the rooms their roster row grants; no extra access is added.
+    pub fn generate_users_from_roster(
+        &mut self,
+        roster: &UserRoster,
+        config: &SimulationConfig,
+        registry: &LocationRegistry,
+    ) -> Result<Vec<User>, String> {
+        let mut users = Vec::with_capacity(roster.len());
+
+        for entry in &roster.entries {
+            let assignment = entry
+                .resolve(registry)
+                .map_err(|e| format!("Roster user {}: {}", entry.user_id, e))?;
+
+            let mut user = User::new(
+                assignment.location_id,
+                assignment.building_id,
+                assignment.workspace_id,
+                assignment.permissions,
+            );
+            user.id = entry.user_id;
+            user.role = entry.role.clone();
+            user.is_curious = entry.is_curious.unwrap_or(false);
+            user.has_cloned_badge = entry.has_cloned_badge.unwrap_or(false);
+
+            if entry.is_night_shift.unwrap_or(false) {
+                user.is_night_shift = true;
+                user.assigned_night_building = Some(assignment.building_id);
+            }
+
+            users.push(user);
+        }
+
+        let curious_count = (users.len() as f64 * config.curious_user_percentage) as usize;
+        let unspecified: Vec<usize> =
+            (0..users.len()).filter(|&i| roster.entries[i].is_curious.is_none()).collect();
+        for i in self.pick_remaining(&users, unspecified, curious_count, |user| user.is_curious) {
+            users[i].is_curious = true;
+        }
+
+        let cloned_badge_count = (users.len() as f64 * config.cloned_badge_percentage) as usize;
+        let unspecified: Vec<usize> =
+            (0..users.len()).filter(|&i| roster.entries[i].has_cloned_badge.is_none()).collect();
+        for i in self.pick_remaining(&users, unspecified, cloned_badge_count, |user| user.has_cloned_badge) {
+            users[i].has_cloned_badge = true;
+        }
+
+        for user in &mut users {
+            user.behavior_profile = if user.is_curious {
+                BehaviorProfile::curious()
+            } else {
+                self.generate_behavior_profile()
+            };
+        }
+
+        assign_identities(&mut users);
+
+        Ok(users)
+    }
+
+    /// Choose which unspecified users to flag so that `target` users carry a flag in total
+    fn pick_remaining(
+        &mut self,
+        users: &[User],
+        mut unspecified: Vec<usize>,
+        target: usize,
+        is_flagged: impl Fn(&User) -> bool,
+    ) -> Vec<usize> {
+        use rand::seq::SliceRandom;
+
+        let remaining = target.saturating_sub(users.iter().filter(|user| is_flagged(user)).count());
+        unspecified.shuffle(&mut *self.rng);
+        unspecified.truncate(remaining);
+        unspecified
+    }
+
     /// Count total workspace rooms across all locations
     fn count_total_workspaces(&self, registry: &LocationRegistry) -> usize {
         registry
@@ -204,11 +288,9 @@ impl UserGenerator {
                 RoomType::Lobby | RoomType::Bathroom | RoomType::Kitchen | RoomType::Cafeteria => {
                     permissions.add_permission(PermissionLevel::Room(room.id));
                 }
-                RoomType::MeetingRoom => {
-                    // 80% chance to access meeting rooms in primary building
-                    if self.rng.gen::<f64>() < 0.8 {
-                        permissions.add_permission(PermissionLevel::Room(room.id));
-                    }
+                // 80% chance to access meeting rooms in primary building
+                RoomType::MeetingRoom if self.rng.gen::<f64>() < 0.8 => {
+                    permissions.add_permission(PermissionLevel::Room(room.id));
                 }
                 _ => {} // Other room types require special permissions
             }
@@ -224,11 +306,9 @@ impl UserGenerator {
                             RoomType::Lobby | RoomType::Bathroom | RoomType::Cafeteria => {
                                 permissions.add_permission(PermissionLevel::Room(room.id));
                             }
-                            RoomType::MeetingRoom => {
-                                // 50% chance for meeting rooms in other buildings
-                                if self.rng.gen::<f64>() < 0.5 {
-                                    permissions.add_permission(PermissionLevel::Room(room.id));
-                                }
+                            // 50% chance for meeting rooms in other buildings
+                            RoomType::MeetingRoom if self.rng.gen::<f64>() < 0.5 => {
+                                permissions.add_permission(PermissionLevel::Room(room.id));
                             }
                             _ => {}
                         }
@@ -359,13 +439,14 @@ impl UserGenerator {
                 )?;
 
                 // Create night-shift user
-                let user = User::new_night_shift(
+                let mut user = User::new_night_shift(
                     location_id,
                     building_id,
                     workspace_room_id,
                     permissions,
                     building_id, // Assigned night building is the same as primary building
                 );
+                user.id = UserId::from_rng(&mut *self.rng);
 
                 night_shift_users.push(user);
             }
@@ -441,7 +522,11 @@ impl UserGenerator {
         let permissions =
             self.generate_permissions(location_id, building_id, workspace_id, registry, config)?;
 
-        Ok(User::new(location_id, building_id, workspace_id, permissions))
+        let mut user = User::new(location_id, building_id, workspace_id, permissions);
+        user.id = UserId::from_rng(&mut *self.rng);
+        assign_identities(std::slice::from_mut(&mut user));
+
+        Ok(user)
     }
 
     /// Get statistics about user generation
@@ -861,4 +946,42 @@ mod tests {
         assert!(result.is_err());
         assert!(result.unwrap_err().contains("Cannot generate users without any locations"));
     }
+
+    #[test]
+    fn test_users_from_roster() {
+        use crate::facility::FacilityDefinition;
+
+        let registry = FacilityDefinition::from_yaml_str(
+            "locations: [{name: HQ, latitude: 0.0, longitude: 0.0, buildings: [{name: Main, rooms: [
+                {name: Lobby, room_type: Lobby, security_level: Public},
+                {name: Desks, room_type: Workspace, security_level: Standard}]}]}]",
+        )
+        .unwrap()
+        .into_registry()
+        .unwrap();
+        let mut csv = String::from("user_id,location,building,workspace,role,is_curious,is_night_shift\n");
+        csv.push_str("guard,HQ,Main,Desks,Security,false,true\n");
+        for i in 0..9 {
+            csv.push_str(&format!("user{},HQ,Main,Desks,Engineer,,\n", i));
+        }
+        let roster = UserRoster::from_reader(csv.as_bytes()).unwrap();
+        let config = SimulationConfig { curious_user_percentage: 0.2, cloned_badge_percentage: 0.1, ..Default::default() };
+
+        let users = UserGenerator::with_seed(7).generate_users_from_roster(&roster, &config, &registry).unwrap();
+        assert_eq!(users.len(), 10);
+        assert_eq!(users.iter().filter(|user| user.is_curious).count(), 2);
+        assert_eq!(users.iter().filter(|user| user.has_cloned_badge).count(), 1);
+        assert!(users.iter().all(|user| user.identity.is_some() && user.validate().is_ok()));
+
+        let guard = &users[0];
+        assert_eq!(guard.id, roster.entries[0].user_id);
+        assert_eq!(guard.role.as_deref(), Some("Security"));
+        assert!(guard.is_night_shift && !guard.is_curious);
+        assert_eq!(guard.assigned_night_building, Some(guard.primary_building));
+        assert!(!guard.permissions.can_access_building(guard.primary_building, guard.primary_location));
+
+        let missing_room = UserRoster::from_reader(csv.replace("user3,HQ,Main,Desks", "user3,HQ,Main,Vault").as_bytes()).unwrap();
+        let error = UserGenerator::with_seed(7).generate_users_from_roster(&missing_room, &config, &registry).unwrap_err();
+        assert!(error.contains("Vault"), "{}", error);
+    }
 }
@@ -0,0 +1,317 @@
+//! Synthetic user identities
+//!
+//! This module gives each user a pseudonymous but realistic identity: a name, a corporate
+//! email address, an employee number and the physical badge card number a reader sees.
+//! Real PACS logs identify badges rather than people, so event output can be keyed on the
+//! badge number and correlated back to an identity through the [`IdentityDirectory`].
+//!
+//! Identities are derived from the user and location ids, so the same population always
+//! gets the same identities without consuming any simulation random numbers. Collisions
+//! are resolved in user id order, which keeps badge numbers, employee numbers and emails
+//! unique across the population.
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::types::{LocationId, UserId};
+use crate::user::User;
+
+/// Domain used for generated corporate email addresses
+pub const EMAIL_DOMAIN: &str = "example.com";
+
+/// Given names used for generated identities
+const GIVEN_NAMES: [&str; 40] = [
+    "Aisha", "Alejandro", "Amara", "Andrei", "Beatriz", "Chen", "Daniel", "Divya", "Elena", "Emeka",
+    "Fatima", "Gabriel", "Hana", "Ibrahim", "Ingrid", "James", "Jia", "Kenji", "Lars", "Leila",
+    "Lucas", "Maria", "Mateo", "Mei", "Nadia", "Noah", "Olga", "Omar", "Priya", "Rafael",
+    "Rosa", "Samuel", "Sara", "Sven", "Tariq", "Thandiwe", "Valentina", "Wei", "Yusuf", "Zoe",
+];
+
+/// Family names used for generated identities
+const FAMILY_NAMES: [&str; 40] = [
+    "Adeyemi", "Alvarez", "Andersson", "Bauer", "Chen", "Costa", "Dubois", "Fernandes", "Fischer", "Garcia",
+    "Haddad", "Hansen", "Ivanova", "Jensen", "Kim", "Kowalski", "Kumar", "Larsen", "Lopez", "Mensah",
+    "Moreau", "Müller", "Nakamura", "Nguyen", "Novak", "Okafor", "Olsen", "Patel", "Petrov", "Rahman",
+    "Rossi", "Sato", "Schmidt", "Silva", "Singh", "Tanaka", "Wang", "Williams", "Yilmaz", "Zhang",
+];
+
+/// Physical badge card number, as encoded on a 26-bit Wiegand card
+///
+/// Displayed and serialized as `FFF-CCCCC`, e.g. `042-13087`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct BadgeNumber {
+    /// Facility code shared by the badges issued at a site
+    pub facility_code: u8,
+    /// Card number within the facility code
+    pub card_number: u16,
+}
+
+impl BadgeNumber {
+    /// Create a badge number from a facility code and card number
+    pub fn new(facility_code: u8, card_number: u16) -> Self {
+        Self { facility_code, card_number }
+    }
+
+    /// Encode the badge as a 26-bit Wiegand frame with its leading even and trailing odd parity bits
+    pub fn wiegand26(&self) -> u32 {
+        let data = ((self.facility_code as u32) << 16) | self.card_number as u32;
+        let even_parity = (data >> 12).count_ones() % 2;
+        let odd_parity = 1 - (data & 0xFFF).count_ones() % 2;
+        (even_parity << 25) | (data << 1) | odd_parity
+    }
+}
+
+impl fmt::Display for BadgeNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:03}-{:05}", self.facility_code, self.card_number)
+    }
+}
+
+impl FromStr for BadgeNumber {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (facility_code, card_number) =
+            s.split_once('-').ok_or_else(|| format!("Invalid badge number '{}': expected FFF-CCCCC", s))?;
+        let facility_code =
+            facility_code.parse().map_err(|_| format!("Invalid facility code in badge number '{}'", s))?;
+        let card_number = card_number.parse().map_err(|_| format!("Invalid card number in badge number '{}'", s))?;
+        Ok(Self::new(facility_code, card_number))
+    }
+}
+
+impl Serialize for BadgeNumber {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for BadgeNumber {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// Synthetic identity of a badge holder
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserIdentity {
+    /// Given name
+    pub given_name: String,
+    /// Family name
+    pub family_name: String,
+    /// Corporate email address
+    pub email: String,
+    /// Employee number (e.g. `E0412345`)
+    pub employee_number: String,
+    /// Badge card number
+    pub badge_number: BadgeNumber,
+}
+
+impl UserIdentity {
+    /// Get the full display name
+    pub fn full_name(&self) -> String {
+        format!("{} {}", self.given_name, self.family_name)
+    }
+}
+
+/// Assign a unique synthetic identity to every user
+///
+/// Users are processed in user id order, so the result does not depend on the order of
+/// the slice. The facility code is derived from the user's primary location.
+pub fn assign_identities(users: &mut [User]) {
+    let mut order: Vec<usize> = (0..users.len()).collect();
+    order.sort_by_key(|&index| users[index].id.0);
+
+    let mut badges = HashSet::new();
+    let mut employee_numbers = HashSet::new();
+    let mut emails = HashSet::new();
+
+    for index in order {
+        let user = &mut users[index];
+        let mut rng = StdRng::seed_from_u64(fold_u128(user.id.0.as_u128()));
+        let given_name = GIVEN_NAMES[rng.gen_range(0..GIVEN_NAMES.len())];
+        let family_name = FAMILY_NAMES[rng.gen_range(0..FAMILY_NAMES.len())];
+
+        let facility_code = facility_code(user.primary_location);
+        let mut badge_number = BadgeNumber::new(facility_code, rng.gen());
+        let mut attempts = 0u32;
+        while !badges.insert(badge_number) {
+            // Spill over into the next facility code once a site runs out of card numbers
+            attempts += 1;
+            if attempts.is_multiple_of(65_536) {
+                badge_number.facility_code = badge_number.facility_code.wrapping_add(1);
+            }
+            badge_number.card_number = badge_number.card_number.wrapping_add(1);
+        }
+
+        let mut employee_number = rng.gen_range(100_000..10_000_000u32);
+        while !employee_numbers.insert(employee_number) {
+            employee_number = employee_number % 9_999_999 + 1;
+        }
+
+        let local_part = format!("{}.{}", email_part(given_name), email_part(family_name));
+        let mut email = format!("{}@{}", local_part, EMAIL_DOMAIN);
+        let mut suffix = 2;
+        while !emails.insert(email.clone()) {
+            email = format!("{}{}@{}", local_part, suffix, EMAIL_DOMAIN);
+            suffix += 1;
+        }
+
+        user.identity = Some(UserIdentity {
+            given_name: given_name.to_string(),
+            family_name: family_name.to_string(),
+            email,
+            employee_number: format!("E{:07}", employee_number),
+            badge_number,
+        });
+    }
+}
+
+/// Derive the badge facility code of a site from its location id
+pub fn facility_code(location_id: LocationId) -> u8 {
+    let folded = fold_u128(location_id.0.as_u128());
+    (folded ^ (folded >> 8) ^ (folded >> 16) ^ (folded >> 24)) as u8
+}
+
+/// Fold a 128-bit id into 64 bits
+fn fold_u128(value: u128) -> u64 {
+    (value >> 64) as u64 ^ value as u64
+}
+
+/// Lowercase ASCII form of a name for use in an email address
+fn email_part(name: &str) -> String {
+    name.chars()
+        .map(|c| match c {
+            'ü' | 'Ü' => 'u',
+            c => c.to_ascii_lowercase(),
+        })
+        .filter(|c| c.is_ascii_alphanumeric())
+        .collect()
+}
+
+/// Lookup between users, their identities and their badge numbers
+#[derive(Debug, Clone, Default)]
+pub struct IdentityDirectory {
+    identities: HashMap<UserId, UserIdentity>,
+    badge_holders: HashMap<BadgeNumber, UserId>,
+}
+
+impl IdentityDirectory {
+    /// Build a directory from users with assigned identities
+    ///
+    /// Users without an identity are skipped.
+    pub fn from_users(users: &[User]) -> Self {
+        let mut directory = Self::default();
+        for user in users {
+            if let Some(identity) = &user.identity {
+                directory.badge_holders.insert(identity.badge_number, user.id);
+                directory.identities.insert(user.id, identity.clone());
+            }
+        }
+        directory
+    }
+
+    /// Get the identity of a user
+    pub fn identity(&self, user_id: UserId) -> Option<&UserIdentity> {
+        self.identities.get(&user_id)
+    }
+
+    /// Get the badge number of a user
+    pub fn badge_number(&self, user_id: UserId) -> Option<BadgeNumber> {
+        self.identities.get(&user_id).map(|identity| identity.badge_number)
+    }
+
+    /// Get the user a badge number was issued to
+    pub fn badge_holder(&self, badge_number: BadgeNumber) -> Option<UserId> {
+        self.badge_holders.get(&badge_number).copied()
+    }
+
+    /// Get the number of identities in the directory
+    pub fn len(&self) -> usize {
+        self.identities.len()
+    }
+
+    /// Check whether the directory is empty
+    pub fn is_empty(&self) -> bool {
+        self.identities.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::permissions::PermissionSet;
+    use crate::types::{BuildingId, RoomId};
+
+    fn create_users(count: usize, location_id: LocationId) -> Vec<User> {
+        let mut rng = StdRng::seed_from_u64(21);
+        (0..count)
+            .map(|_| {
+                let mut user = User::new(location_id, BuildingId::new(), RoomId::new(), PermissionSet::new());
+                user.id = UserId::from_rng(&mut rng);
+                user
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_badge_number_format_and_wiegand_parity() {
+        let badge = BadgeNumber::new(42, 13087);
+        assert_eq!(badge.to_string(), "042-13087");
+        assert_eq!("042-13087".parse::<BadgeNumber>().unwrap(), badge);
+        assert!("4213087".parse::<BadgeNumber>().is_err());
+        assert_eq!(serde_json::to_string(&badge).unwrap(), "\"042-13087\"");
+
+        let frame = badge.wiegand26();
+        assert_eq!((frame >> 1) & 0xFF_FFFF, (42 << 16) | 13087);
+        assert_eq!((frame >> 13).count_ones() % 2, 0, "leading 13 bits have even parity");
+        assert_eq!((frame & 0x1FFF).count_ones() % 2, 1, "trailing 13 bits have odd parity");
+    }
+
+    #[test]
+    fn test_identities_are_deterministic_and_unique() {
+        let location_id = LocationId::new();
+        let mut users = create_users(3000, location_id);
+        assign_identities(&mut users);
+
+        let mut reversed = users.clone();
+        reversed.reverse();
+        assign_identities(&mut reversed);
+        assert_eq!(reversed.last().unwrap().identity, users[0].identity);
+
+        let identities: Vec<&UserIdentity> = users.iter().map(|u| u.identity.as_ref().unwrap()).collect();
+        let badges: HashSet<_> = identities.iter().map(|i| i.badge_number).collect();
+        let emails: HashSet<_> = identities.iter().map(|i| i.email.as_str()).collect();
+        let employee_numbers: HashSet<_> = identities.iter().map(|i| i.employee_number.as_str()).collect();
+        assert_eq!(badges.len(), users.len());
+        assert_eq!(emails.len(), users.len());
+        assert_eq!(employee_numbers.len(), users.len());
+        assert!(badges.iter().all(|badge| badge.facility_code == facility_code(location_id)));
+        assert!(identities.iter().all(|i| i.email.ends_with("@example.com") && i.email.is_ascii()));
+    }
+
+    #[test]
+    fn test_directory_lookups() {
+        let mut users = create_users(10, LocationId::new());
+        assign_identities(&mut users);
+        let location_id = users[0].primary_location;
+        users.push(User::new(location_id, BuildingId::new(), RoomId::new(), PermissionSet::new()));
+
+        let directory = IdentityDirectory::from_users(&users);
+        assert_eq!(directory.len(), 10);
+        let badge = directory.badge_number(users[3].id).unwrap();
+        assert_eq!(directory.badge_holder(badge), Some(users[3].id));
+        assert!(directory.identity(users[10].id).is_none());
+    }
+}
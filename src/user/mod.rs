@@ -13,6 +13,8 @@
 //! - **UserState**: Manages current state and scheduled activities
 //! - **UserProfile**: Provides validation and analysis capabilities
 //! - **UserGenerator**: Creates realistic user populations with statistics
+//! - **UserIdentity**: Synthetic name, email, employee number and badge number
+//! - **UserRoster**: Users and permissions imported from a roster CSV file
 //!
 //! # Usage Example
 //!
@@ -58,12 +60,16 @@ pub mod behavior;
 #[allow(clippy::module_inception)]
 pub mod user;
 pub mod generator;
+pub mod identity;
 pub mod profile;
+pub mod roster;
 pub mod state;
 
 // Re-export all public types for convenience
 pub use behavior::{ActivityPreferences, BehaviorProfile};
 pub use user::User;
 pub use generator::{UserGenerator, UserStats};
+pub use identity::{assign_identities, BadgeNumber, IdentityDirectory, UserIdentity};
 pub use profile::{TravelPatterns, UserProfile};
+pub use roster::{RosterAssignment, RosterEntry, UserRoster};
 pub use state::{UserState, ScheduledActivity};
@@ -4,7 +4,7 @@
 
 use serde::{Deserialize, Serialize};
 
-use crate::user::{ActivityPreferences, BehaviorProfile, User};
+use crate::user::{ActivityPreferences, BehaviorProfile, User, UserIdentity};
 use crate::types::{BuildingId, UserId, LocationId, RoomId, SimulationConfig};
 
 /// User profile containing the "answer key" information for validation and analysis
@@ -37,6 +37,12 @@ pub struct UserProfile {
     pub activity_preferences: ActivityPreferences,
     /// Travel patterns and location affinity
     pub travel_patterns: TravelPatterns,
+    /// Synthetic identity, linking the user to the badge number seen in events
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub identity: Option<UserIdentity>,
+    /// Job role from an imported roster
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role: Option<String>,
 }
 
 /// Travel patterns that define how a user moves between locations
@@ -75,6 +81,8 @@ impl UserProfile {
                 &user.behavior_profile,
             ),
             travel_patterns: TravelPatterns::from_config_and_user(config, user),
+            identity: user.identity.clone(),
+            role: user.role.clone(),
         }
     }
 
@@ -199,6 +207,8 @@ mod tests {
                 frequent_locations: vec![location_id],
                 frequent_buildings: vec![building_id],
             },
+            identity: None,
+            role: None,
         };
 
         // Test room-level access
@@ -249,6 +259,8 @@ mod tests {
                 frequent_locations: vec![location_id],
                 frequent_buildings: vec![building_id],
             },
+            identity: None,
+            role: None,
         };
 
         // Test normal user
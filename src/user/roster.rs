@@ -0,0 +1,357 @@
+//! User roster import
+//!
+//! This module loads a real organization's access matrix from a CSV file instead of
+//! generating random users. The first line is a header naming the columns, in any order:
+//!
+//! ```csv
+//! user_id,location,building,workspace,role,permissions,is_curious,has_cloned_badge,is_night_shift
+//! jdoe,Seattle HQ,Tower A,Floor 3 Desks,Engineer,room:Main Lobby;room:Seattle HQ/Tower B/Lab,,,
+//! asmith,Seattle HQ,Tower A,Floor 3 Desks,Security,building:Tower A,false,false,true
+//! ```
+//!
+//! - `user_id`, `location`, `building` and `workspace` are required. User ids may be
+//!   `USER_`-prefixed ids, raw UUIDs, or any other string, which is mapped to a stable id.
+//! - Locations, buildings and rooms are given by name or by id (`LOC_…`, `BLD_…`, `ROOM_…`).
+//!   Building names are looked up in the user's location and room names in the user's
+//!   building, unless written as a `Location/Building/Room` path.
+//! - `permissions` is a `;`-separated list of `room:`, `building:` and `location:` grants,
+//!   matching the `PermissionLevel` variants. The workspace is always granted.
+//! - `role`, `permissions` and the three flag columns are optional. Empty flag cells are
+//!   filled from the configured curious and cloned badge percentages.
+
+use std::io::BufRead;
+use std::path::Path;
+use uuid::Uuid;
+
+use crate::events::parse_csv_row;
+use crate::facility::{Building, Location, LocationRegistry};
+use crate::permissions::{PermissionLevel, PermissionSet};
+use crate::simulation::open_input_file;
+use crate::types::{BuildingId, LocationId, RoomId, UserId};
+
+/// Namespace for ids derived from non-UUID roster user ids
+const USER_ID_NAMESPACE: Uuid = Uuid::from_u128(0x2b7e_5a1c_9d34_4f08_b6e2_7c1a_0d5f_3e98);
+
+/// Columns that must be present in the header
+const REQUIRED_COLUMNS: [&str; 4] = ["user_id", "location", "building", "workspace"];
+
+/// Users and their grants loaded from a roster file
+#[derive(Debug, Clone, Default)]
+pub struct UserRoster {
+    /// Roster rows in file order
+    pub entries: Vec<RosterEntry>,
+}
+
+/// A single roster row, with facility references still unresolved
+#[derive(Debug, Clone, PartialEq)]
+pub struct RosterEntry {
+    /// User id
+    pub user_id: UserId,
+    /// Home location name or id
+    pub location: String,
+    /// Home building name or id
+    pub building: String,
+    /// Workspace room name or id
+    pub workspace: String,
+    /// Job role, if given
+    pub role: Option<String>,
+    /// Granted rooms, buildings and locations, as `scope:reference` strings
+    pub permissions: Vec<String>,
+    /// Explicit curious flag, or `None` to use the configured percentage
+    pub is_curious: Option<bool>,
+    /// Explicit cloned badge flag, or `None` to use the configured percentage
+    pub has_cloned_badge: Option<bool>,
+    /// Explicit night-shift flag, or `None` for a day-shift user
+    pub is_night_shift: Option<bool>,
+}
+
+/// Roster entry resolved against a location registry
+#[derive(Debug, Clone)]
+pub struct RosterAssignment {
+    /// Home location
+    pub location_id: LocationId,
+    /// Home building
+    pub building_id: BuildingId,
+    /// Workspace room
+    pub workspace_id: RoomId,
+    /// Granted permissions, including the workspace
+    pub permissions: PermissionSet,
+}
+
+impl UserRoster {
+    /// Load a roster CSV file, decompressing `.gz` and `.zst` files transparently
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, String> {
+        let path = path.as_ref();
+        let reader = open_input_file(path)
+            .map_err(|e| format!("Failed to read user roster {}: {}", path.display(), e))?;
+        Self::from_reader(reader).map_err(|e| format!("Invalid user roster {}: {}", path.display(), e))
+    }
+
+    /// Parse a roster from CSV text with a header line
+    pub fn from_reader<R: BufRead>(reader: R) -> Result<Self, String> {
+        let mut lines = reader.lines().enumerate();
+        let header = loop {
+            match lines.next() {
+                Some((_, line)) => {
+                    let line = line.map_err(|e| e.to_string())?;
+                    if !line.trim().is_empty() {
+                        break parse_csv_row(&line).map_err(|e| format!("line 1: {}", e))?;
+                    }
+                }
+                None => return Err("Roster is empty".to_string()),
+            }
+        };
+        let columns: Vec<String> = header.iter().map(|name| name.trim().to_ascii_lowercase()).collect();
+        for required in REQUIRED_COLUMNS {
+            if !columns.iter().any(|column| column == required) {
+                return Err(format!("Missing required column '{}'", required));
+            }
+        }
+
+        let mut entries = Vec::new();
+        let mut seen_ids = std::collections::HashSet::new();
+        for (index, line) in lines {
+            let line = line.map_err(|e| e.to_string())?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let entry = parse_entry(&columns, &line).map_err(|e| format!("line {}: {}", index + 1, e))?;
+            if !seen_ids.insert(entry.user_id) {
+                return Err(format!("line {}: duplicate user id {}", index + 1, entry.user_id));
+            }
+            entries.push(entry);
+        }
+
+        Ok(Self { entries })
+    }
+
+    /// Number of users in the roster
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether the roster has no users
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl RosterEntry {
+    /// Resolve the home assignment and grants of this entry against a registry
+    pub fn resolve(&self, registry: &LocationRegistry) -> Result<RosterAssignment, String> {
+        let location = find_location(registry, &self.location)?;
+        let building = find_building(registry, location, &self.building)?;
+        let workspace_id = find_room(registry, building, &self.workspace)?;
+
+        let mut permissions = PermissionSet::new();
+        permissions.add_permission(PermissionLevel::Room(workspace_id));
+        for grant in &self.permissions {
+            permissions.add_permission(resolve_grant(registry, location, building, grant)?);
+        }
+
+        Ok(RosterAssignment {
+            location_id: location.id,
+            building_id: building.id,
+            workspace_id,
+            permissions,
+        })
+    }
+}
+
+/// Parse one data row using the header column names
+fn parse_entry(columns: &[String], line: &str) -> Result<RosterEntry, String> {
+    let values = parse_csv_row(line)?;
+    if values.len() > columns.len() {
+        return Err(format!("expected {} fields, found {}", columns.len(), values.len()));
+    }
+    let cell = |name: &str| {
+        columns
+            .iter()
+            .position(|column| column == name)
+            .and_then(|index| values.get(index))
+            .map(|value| value.trim())
+            .filter(|value| !value.is_empty())
+    };
+    let required = |name: &str| cell(name).map(str::to_string).ok_or_else(|| format!("missing {}", name));
+
+    let user_id = required("user_id")?;
+    Ok(RosterEntry {
+        user_id: user_id
+            .parse()
+            .unwrap_or_else(|_| UserId(Uuid::new_v5(&USER_ID_NAMESPACE, user_id.as_bytes()))),
+        location: required("location")?,
+        building: required("building")?,
+        workspace: required("workspace")?,
+        role: cell("role").map(str::to_string),
+        permissions: cell("permissions")
+            .map(|grants| {
+                grants.split(';').map(str::trim).filter(|grant| !grant.is_empty()).map(str::to_string).collect()
+            })
+            .unwrap_or_default(),
+        is_curious: cell("is_curious").map(parse_flag).transpose()?,
+        has_cloned_badge: cell("has_cloned_badge").map(parse_flag).transpose()?,
+        is_night_shift: cell("is_night_shift").map(parse_flag).transpose()?,
+    })
+}
+
+/// Parse a yes/no flag cell
+fn parse_flag(value: &str) -> Result<bool, String> {
+    match value.to_ascii_lowercase().as_str() {
+        "true" | "yes" | "y" | "1" => Ok(true),
+        "false" | "no" | "n" | "0" => Ok(false),
+        _ => Err(format!("invalid flag '{}', expected true or false", value)),
+    }
+}
+
+/// Resolve a `room:`, `building:` or `location:` grant
+fn resolve_grant(
+    registry: &LocationRegistry,
+    location: &Location,
+    building: &Building,
+    grant: &str,
+) -> Result<PermissionLevel, String> {
+    let (scope, reference) = grant
+        .split_once(':')
+        .map(|(scope, reference)| (scope.trim().to_ascii_lowercase(), reference.trim()))
+        .ok_or_else(|| format!("Permission '{}' must start with room:, building: or location:", grant))?;
+
+    match scope.as_str() {
+        "room" => Ok(PermissionLevel::Room(find_room(registry, building, reference)?)),
+        "building" => Ok(PermissionLevel::Building(find_building(registry, location, reference)?.id)),
+        "location" => Ok(PermissionLevel::Location(find_location(registry, reference)?.id)),
+        _ => Err(format!("Unknown permission scope '{}' in '{}'", scope, grant)),
+    }
+}
+
+/// Find a location by id or name
+fn find_location<'a>(registry: &'a LocationRegistry, reference: &str) -> Result<&'a Location, String> {
+    let found = match reference.parse::<LocationId>() {
+        Ok(id) => registry.get_location(id),
+        Err(_) => registry.get_all_locations().iter().find(|location| location.name == reference),
+    };
+    found.ok_or_else(|| format!("Unknown location '{}'", reference))
+}
+
+/// Find a building by id, by `Location/Building` path, or by name within the home location
+fn find_building<'a>(
+    registry: &'a LocationRegistry,
+    home: &'a Location,
+    reference: &str,
+) -> Result<&'a Building, String> {
+    if let Ok(id) = reference.parse::<BuildingId>() {
+        return registry.get_building(id).ok_or_else(|| format!("Unknown building '{}'", reference));
+    }
+    let (location, name) = match reference.rsplit_once('/') {
+        Some((location, name)) => (find_location(registry, location)?, name),
+        None => (home, reference),
+    };
+    location
+        .buildings
+        .iter()
+        .find(|building| building.name == name)
+        .ok_or_else(|| format!("Unknown building '{}' in location '{}'", name, location.name))
+}
+
+/// Find a room by id, by `Location/Building/Room` path, or by name within the home building
+fn find_room(registry: &LocationRegistry, home: &Building, reference: &str) -> Result<RoomId, String> {
+    if let Ok(id) = reference.parse::<RoomId>() {
+        return if registry.room_exists(id) { Ok(id) } else { Err(format!("Unknown room '{}'", reference)) };
+    }
+    let (building, name) = match reference.splitn(3, '/').collect::<Vec<_>>()[..] {
+        [location, building, name] => {
+            let location = find_location(registry, location)?;
+            (find_building(registry, location, building)?, name)
+        }
+        _ => (home, reference),
+    };
+    building
+        .rooms
+        .iter()
+        .find(|room| room.name == name)
+        .map(|room| room.id)
+        .ok_or_else(|| format!("Unknown room '{}' in building '{}'", name, building.name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::facility::FacilityDefinition;
+
+    const FACILITIES: &str = "
+locations:
+  - name: Seattle HQ
+    latitude: 47.6062
+    longitude: -122.3321
+    buildings:
+      - name: Tower A
+        rooms:
+          - { name: Main Lobby, room_type: Lobby, security_level: Public }
+          - { name: Floor 3 Desks, room_type: Workspace, security_level: Standard }
+      - name: Tower B
+        rooms:
+          - { name: Main Lobby, room_type: Lobby, security_level: Public }
+          - { name: Lab, room_type: Laboratory, security_level: Restricted }
+";
+
+    const ROSTER: &str = "\
+user_id,location,building,workspace,role,permissions,is_curious,has_cloned_badge,is_night_shift
+jdoe,Seattle HQ,Tower A,Floor 3 Desks,Engineer,room:Main Lobby;room:Seattle HQ/Tower B/Lab,,,
+asmith,Seattle HQ,Tower A,Floor 3 Desks,\"Security, Nights\",building:Tower A;location:Seattle HQ,no,0,yes
+";
+
+    #[test]
+    fn test_roster_parsing() {
+        let roster = UserRoster::from_reader(ROSTER.as_bytes()).unwrap();
+        assert_eq!(roster.len(), 2);
+
+        let jdoe = &roster.entries[0];
+        assert_eq!(jdoe.role.as_deref(), Some("Engineer"));
+        assert_eq!(jdoe.permissions, vec!["room:Main Lobby", "room:Seattle HQ/Tower B/Lab"]);
+        assert_eq!(jdoe.is_curious, None);
+
+        let asmith = &roster.entries[1];
+        assert_eq!(asmith.role.as_deref(), Some("Security, Nights"));
+        assert_eq!((asmith.is_curious, asmith.has_cloned_badge, asmith.is_night_shift), (Some(false), Some(false), Some(true)));
+
+        // Non-UUID ids map to stable ids, and prefixed ids are kept as-is
+        let again = UserRoster::from_reader(ROSTER.as_bytes()).unwrap();
+        assert_eq!(again.entries[0].user_id, jdoe.user_id);
+        let id = UserId::new();
+        let roster = UserRoster::from_reader(format!("workspace,building,location,user_id\nDesk,B,L,{}\n", id).as_bytes()).unwrap();
+        assert_eq!(roster.entries[0].user_id, id);
+    }
+
+    #[test]
+    fn test_roster_resolution() {
+        let registry = FacilityDefinition::from_yaml_str(FACILITIES).unwrap().into_registry().unwrap();
+        let roster = UserRoster::from_reader(ROSTER.as_bytes()).unwrap();
+        let location = &registry.locations[0];
+        let (tower_a, tower_b) = (&location.buildings[0], &location.buildings[1]);
+
+        let assignment = roster.entries[0].resolve(&registry).unwrap();
+        assert_eq!(assignment.building_id, tower_a.id);
+        assert_eq!(assignment.workspace_id, tower_a.rooms[1].id);
+        assert!(assignment.permissions.can_access_room(tower_a.rooms[0].id, tower_a.id, location.id));
+        assert!(assignment.permissions.can_access_room(tower_b.rooms[1].id, tower_b.id, location.id));
+        assert!(!assignment.permissions.can_access_room(tower_b.rooms[0].id, tower_b.id, location.id));
+
+        let assignment = roster.entries[1].resolve(&registry).unwrap();
+        assert!(assignment.permissions.get_authorized_buildings().contains(&tower_a.id));
+        assert_eq!(assignment.permissions.get_authorized_locations(), vec![location.id]);
+    }
+
+    #[test]
+    fn test_invalid_rosters_are_rejected() {
+        let registry = FacilityDefinition::from_yaml_str(FACILITIES).unwrap().into_registry().unwrap();
+
+        assert!(UserRoster::from_reader("user_id,location,building\n".as_bytes()).unwrap_err().contains("workspace"));
+        assert!(UserRoster::from_reader(ROSTER.replace(",no,0,", ",maybe,0,").as_bytes()).is_err());
+        let duplicate = format!("{}{}", ROSTER, ROSTER.lines().nth(1).unwrap());
+        assert!(UserRoster::from_reader(duplicate.as_bytes()).unwrap_err().contains("duplicate"));
+
+        let unknown_room = UserRoster::from_reader(ROSTER.replace("room:Main Lobby", "room:Vault").as_bytes()).unwrap();
+        assert!(unknown_room.entries[0].resolve(&registry).unwrap_err().contains("Vault"));
+        let bad_scope = UserRoster::from_reader(ROSTER.replace("room:Main Lobby", "floor:3").as_bytes()).unwrap();
+        assert!(bad_scope.entries[0].resolve(&registry).is_err());
+    }
+}
@@ -5,7 +5,7 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-use crate::user::{BehaviorProfile, UserState, ScheduledActivity};
+use crate::user::{BehaviorProfile, UserIdentity, UserState, ScheduledActivity};
 use crate::permissions::{PermissionLevel, PermissionSet};
 use crate::types::{BuildingId, UserId, LocationId, RoomId};
 
@@ -34,6 +34,12 @@ pub struct User {
     pub behavior_profile: BehaviorProfile,
     /// Current state and location of the user
     pub current_state: UserState,
+    /// Synthetic name, email, employee number and badge number (assigned by the generator)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub identity: Option<UserIdentity>,
+    /// Job role from an imported roster
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role: Option<String>,
 }
 
 impl User {
@@ -59,6 +65,8 @@ impl User {
             assigned_night_building: None,
             behavior_profile: BehaviorProfile::default(),
             current_state: UserState::new(primary_building, primary_location, current_time),
+            identity: None,
+            role: None,
         }
     }
 
@@ -290,6 +298,8 @@ impl User {
             assigned_night_building: None,
             behavior_profile: if is_curious { BehaviorProfile::curious() } else { BehaviorProfile::default() },
             current_state: UserState::new(primary_building, primary_location, current_time),
+            identity: None,
+            role: None,
         }
     }
 }
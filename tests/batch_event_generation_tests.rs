@@ -5,8 +5,13 @@
 
 use amzn_career_pathway_activity_rust::user::{User, UserGenerator};
 use amzn_career_pathway_activity_rust::facility::{FacilityGenerator, LocationRegistry};
-use amzn_career_pathway_activity_rust::simulation::BatchEventGenerator;
-use amzn_career_pathway_activity_rust::types::SimulationConfig;
+use amzn_career_pathway_activity_rust::events::{AccessEvent, EventFormatter};
+use amzn_career_pathway_activity_rust::simulation::{
+    BatchEventGenerator, DirectoryEventSink, EventSink, MemoryEventSink, OutputManifest,
+    RotationPolicy, SimulationResult, WriterEventSink,
+};
+use chrono::NaiveDate;
+use amzn_career_pathway_activity_rust::types::{OutputFormat, SimulationConfig};
 
 /// Create a minimal test setup for batch generation tests
 fn create_test_setup() -> (SimulationConfig, LocationRegistry, Vec<User>) {
@@ -17,15 +22,19 @@ fn create_test_setup() -> (SimulationConfig, LocationRegistry, Vec<User>) {
         max_buildings_per_location: 3,
         min_rooms_per_building: 5,
         max_rooms_per_building: 10,
+        // Start on a Monday so multi-day runs cover work days regardless of today's date
+        start_date: NaiveDate::from_ymd_opt(2024, 1, 8),
+        // Small buildings may lack workspace rooms, so keep the layout fixed
+        seed: Some(42),
         ..Default::default()
     };
     
     // Generate facilities
-    let mut facility_generator = FacilityGenerator::new();
+    let mut facility_generator = FacilityGenerator::with_seed(42);
     let location_registry = facility_generator.generate_facilities(&config).unwrap();
     
     // Generate users
-    let mut user_generator = UserGenerator::new();
+    let mut user_generator = UserGenerator::with_seed(42);
     let users = user_generator.generate_users(&config, &location_registry).unwrap();
     
     (config, location_registry, users)
@@ -237,6 +246,7 @@ fn test_batch_generation_performance() {
     let config = SimulationConfig {
         user_count: 50,
         location_count: 2,
+        start_date: NaiveDate::from_ymd_opt(2024, 1, 8),
         ..Default::default()
     };
     
@@ -292,6 +302,7 @@ fn test_batch_generation_different_user_types() {
         location_count: 2,
         curious_user_percentage: 0.1,
         cloned_badge_percentage: 0.05,
+        start_date: NaiveDate::from_ymd_opt(2024, 1, 8),
         ..Default::default()
     };
     
@@ -318,3 +329,130 @@ fn test_batch_generation_different_user_types() {
     // Should track special user types (can be 0 or more)
     assert!(total_special_users <= stats.total_users, "Special users should not exceed total");
 }
+
+/// Test batch generation with CSV output format
+#[test]
+fn test_batch_generation_csv_output_format() {
+    let (mut config, location_registry, users) = create_test_setup();
+    config.output_format = "csv".to_string();
+    config.output_fields.include_all = true;
+    
+    let mut generator = BatchEventGenerator::new(config, location_registry, users);
+    
+    let result = generator.generate_events_for_days(1);
+    assert!(result.is_ok(), "CSV generation should succeed");
+    assert!(generator.get_statistics().total_events > 0);
+}
+
+/// Test that an in-memory sink receives every event in chronological order
+#[test]
+fn test_batch_generation_into_memory_sink() {
+    let (config, location_registry, users) = create_test_setup();
+    let start_date = config.simulation_start_date();
+    let mut generator = BatchEventGenerator::new(config, location_registry, users);
+    let mut sink = MemoryEventSink::new();
+
+    let result = generator.generate_events_with_sink(2, &mut sink);
+    assert!(result.is_ok(), "Generation into a memory sink should succeed");
+
+    assert!(sink.is_finished(), "Sink should be finished after generation");
+    assert_eq!(sink.events().len(), generator.get_statistics().total_events);
+    assert_eq!(sink.days()[0], start_date);
+    assert!(sink.days().windows(2).all(|days| days[0] < days[1]), "Days should be in order");
+    assert!(
+        sink.events().windows(2).all(|pair| pair[0].timestamp <= pair[1].timestamp),
+        "Events should be in chronological order"
+    );
+}
+
+/// Test that a custom sink receives begin/end hooks around each day's events
+#[test]
+fn test_batch_generation_custom_sink_hooks() {
+    #[derive(Debug, Default)]
+    struct HookRecorder {
+        open_day: Option<NaiveDate>,
+        completed_days: usize,
+        events_outside_day: usize,
+        finish_calls: usize,
+    }
+
+    impl EventSink for HookRecorder {
+        fn begin_day(&mut self, date: NaiveDate) -> SimulationResult<()> {
+            assert!(self.open_day.is_none(), "Days should not overlap");
+            self.open_day = Some(date);
+            Ok(())
+        }
+
+        fn write_event(&mut self, _event: &AccessEvent) -> SimulationResult<()> {
+            if self.open_day.is_none() {
+                self.events_outside_day += 1;
+            }
+            Ok(())
+        }
+
+        fn end_day(&mut self, date: NaiveDate) -> SimulationResult<()> {
+            assert_eq!(self.open_day.take(), Some(date));
+            self.completed_days += 1;
+            Ok(())
+        }
+
+        fn finish(&mut self) -> SimulationResult<()> {
+            self.finish_calls += 1;
+            Ok(())
+        }
+    }
+
+    let (config, location_registry, users) = create_test_setup();
+    let mut generator = BatchEventGenerator::new(config, location_registry, users);
+    let mut sink = HookRecorder::default();
+
+    generator.generate_events_with_sink(3, &mut sink).unwrap();
+
+    assert!(sink.completed_days >= 3, "Every simulated day should be delivered");
+    assert_eq!(sink.events_outside_day, 0);
+    assert_eq!(sink.finish_calls, 1);
+}
+
+/// Test that a writer sink renders the same events the memory sink collects
+#[test]
+fn test_batch_generation_into_writer_sink() {
+    let (mut config, location_registry, users) = create_test_setup();
+    config.output_fields.include_all = true;
+    let formatter = EventFormatter::new(OutputFormat::Csv, config.output_fields.clone());
+    let mut generator = BatchEventGenerator::new(config, location_registry, users);
+    let mut sink = WriterEventSink::new(Vec::new(), formatter.clone());
+
+    generator.generate_events_with_sink(1, &mut sink).unwrap();
+
+    let events_written = sink.events_written();
+    let output = String::from_utf8(sink.into_inner().unwrap()).unwrap();
+    let mut lines = output.lines();
+    assert_eq!(lines.next(), formatter.header().as_deref());
+    assert_eq!(lines.count(), events_written);
+    assert_eq!(events_written, generator.get_statistics().total_events);
+}
+
+/// Test that directory output writes one file per day plus a manifest covering every event
+#[test]
+fn test_batch_generation_into_directory_sink() {
+    let (config, location_registry, users) = create_test_setup();
+    let start_date = config.simulation_start_date();
+    let formatter = EventFormatter::from_config(&config).unwrap();
+    let output_dir = tempfile::tempdir().unwrap();
+    let mut generator = BatchEventGenerator::new(config, location_registry, users);
+    let mut sink =
+        DirectoryEventSink::create(output_dir.path(), formatter, RotationPolicy::none()).unwrap();
+
+    generator.generate_events_with_sink(2, &mut sink).unwrap();
+
+    let manifest = OutputManifest::load(sink.manifest_path()).unwrap();
+    assert_eq!(manifest.total_events, generator.get_statistics().total_events);
+    assert_eq!(manifest.files[0].path, format!("{}.jsonl", start_date));
+    assert!(manifest.files.len() >= 2, "Each simulated day should get its own file");
+
+    for file in &manifest.files {
+        let contents = std::fs::read_to_string(output_dir.path().join(&file.path)).unwrap();
+        assert_eq!(contents.lines().count(), file.event_count);
+        assert!(contents.lines().all(|line| line.contains(&format!("\"timestamp\":\"{}", file.date))));
+    }
+}
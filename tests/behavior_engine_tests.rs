@@ -73,10 +73,11 @@ fn test_daily_schedule_generation_curious_user() {
     assert!(schedule.len() >= 2); // At least arrival and departure
 }
 
-/// Test schedule generation for all days (no weekend logic)
+/// Test schedule generation for all days of a seven-day work week
 #[test]
 fn test_all_days_schedule_generation() {
-    let config = SimulationConfig::default();
+    let mut config = SimulationConfig::default();
+    config.calendar.weekend_days.clear();
     let time_manager = TimeManager::default();
     let mut engine = BehaviorEngine::new(config, time_manager);
     let registry = LocationRegistry::new();
@@ -88,7 +89,7 @@ fn test_all_days_schedule_generation() {
     
     let user = User::new(location_id, building_id, room_id, permissions);
     
-    // Test all days of the week - all should be treated as work days
+    // Without weekend days, all days of the week are work days
     let dates = vec![
         NaiveDate::from_ymd_opt(2024, 1, 8).unwrap(),  // Monday
         NaiveDate::from_ymd_opt(2024, 1, 9).unwrap(),  // Tuesday
@@ -109,22 +110,53 @@ fn test_all_days_schedule_generation() {
     }
 }
 
+/// Test that regular users stay home on non-work days while night-shift security patrols
+#[test]
+fn test_non_work_day_schedule_generation() {
+    let calendar = WorkCalendarConfig {
+        holidays: vec![NaiveDate::from_ymd_opt(2024, 1, 15).unwrap()],
+        half_days: vec![NaiveDate::from_ymd_opt(2024, 1, 16).unwrap()],
+        non_work_day_attendance: 0.0,
+        ..Default::default()
+    };
+    let config = SimulationConfig { calendar, ..Default::default() };
+    let mut engine = BehaviorEngine::new(config, TimeManager::default());
+    let registry = LocationRegistry::new();
+
+    let user = User::new(LocationId::new(), BuildingId::new(), RoomId::new(), PermissionSet::new());
+    let mut guard = User::new(LocationId::new(), BuildingId::new(), RoomId::new(), PermissionSet::new());
+    guard.is_night_shift = true;
+
+    for date in [
+        NaiveDate::from_ymd_opt(2024, 1, 13).unwrap(), // Saturday
+        NaiveDate::from_ymd_opt(2024, 1, 14).unwrap(), // Sunday
+        NaiveDate::from_ymd_opt(2024, 1, 15).unwrap(), // Holiday
+    ] {
+        assert!(engine.generate_daily_schedule(&user, date, &registry).unwrap().is_empty());
+        assert!(!engine.generate_daily_schedule(&guard, date, &registry).unwrap().is_empty());
+    }
+
+    // On a half-day everyone has left by early afternoon
+    let half_day = NaiveDate::from_ymd_opt(2024, 1, 16).unwrap();
+    let schedule = engine.generate_daily_schedule(&user, half_day, &registry).unwrap();
+    let departure = schedule.iter().find(|a| a.activity_type == ActivityType::Departure).unwrap();
+    assert!(departure.start_time.hour() < 14);
+    assert!(schedule.iter().all(|a| a.start_time <= departure.start_time));
+}
+
 /// Test behavior profile impact on scheduling
 #[test]
 fn test_behavior_profile_impact_on_scheduling() {
     // Test schedule-focused behavior
-    let mut schedule_focused_profile = BehaviorProfile::default();
-    schedule_focused_profile.schedule_adherence = 0.9;
+    let schedule_focused_profile = BehaviorProfile { schedule_adherence: 0.9, ..Default::default() };
     assert!(schedule_focused_profile.is_schedule_focused());
     
     // Test flexible behavior
-    let mut flexible_profile = BehaviorProfile::default();
-    flexible_profile.schedule_adherence = 0.5;
+    let flexible_profile = BehaviorProfile { schedule_adherence: 0.5, ..Default::default() };
     assert!(!flexible_profile.is_schedule_focused());
     
     // Test social behavior
-    let mut social_profile = BehaviorProfile::default();
-    social_profile.social_level = 0.8;
+    let social_profile = BehaviorProfile { social_level: 0.8, ..Default::default() };
     assert!(social_profile.is_social());
 }
 
@@ -207,13 +239,11 @@ fn test_behavior_profile_characteristics() {
     assert!(curious_profile.curiosity_level > 0.5);
     
     // Test social behavior
-    let mut social_profile = BehaviorProfile::default();
-    social_profile.social_level = 0.8;
+    let social_profile = BehaviorProfile { social_level: 0.8, ..Default::default() };
     assert!(social_profile.is_social());
     
     // Test schedule-focused behavior
-    let mut schedule_profile = BehaviorProfile::default();
-    schedule_profile.schedule_adherence = 0.9;
+    let schedule_profile = BehaviorProfile { schedule_adherence: 0.9, ..Default::default() };
     assert!(schedule_profile.is_schedule_focused());
 }
 
@@ -602,11 +632,11 @@ fn test_night_shift_schedule_timing_patterns() {
     if let (Some(departure), Some(arrival)) = (departure_activity, arrival_activity) {
         // Departure should be in the morning (around 8 AM)
         let departure_hour = departure.start_time.hour();
-        assert!(departure_hour >= 6 && departure_hour <= 10, "Departure should be in morning, got hour {}", departure_hour);
+        assert!((6..=10).contains(&departure_hour), "Departure should be in morning, got hour {}", departure_hour);
         
         // Arrival should be in the evening (around 5 PM)
         let arrival_hour = arrival.start_time.hour();
-        assert!(arrival_hour >= 15 && arrival_hour <= 19, "Arrival should be in evening, got hour {}", arrival_hour);
+        assert!((15..=19).contains(&arrival_hour), "Arrival should be in evening, got hour {}", arrival_hour);
         
         // Departure should be before arrival (inverted schedule)
         assert!(departure.start_time < arrival.start_time);
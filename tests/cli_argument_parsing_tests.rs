@@ -54,11 +54,43 @@ fn test_days_configuration_validation() {
         debug: false,
         dry_run: false,
         print_config: false,
+        evaluate: None,
+        evaluate_events: None,
+        infer_room_types: None,
+        detect: Vec::new(),
         include_failure_reason: false,
         include_event_type: false,
         include_metadata: false,
         include_all_fields: false,
+        include_facility_names: false,
+        include_local_offset: false,
+        key_by_badge_number: false,
         days: 7,
+        start_date: None,
+        weekend_days: None,
+        no_weekends: false,
+        holidays: None,
+        half_days: None,
+        non_work_day_attendance: None,
+        output_dir: None,
+        rotate_max_bytes: None,
+        rotate_max_events: None,
+        compress: None,
+        syslog: None,
+        syslog_protocol: None,
+        syslog_speed: None,
+        http_url: None,
+        http_batch_size: None,
+        http_format: None,
+        http_auth_header: None,
+        http_max_retries: None,
+        http_dead_letter: None,
+        sqlite: None,
+        parquet_dir: None,
+        parquet_user_profiles: false,
+        facility_output: None,
+        facility_definition: None,
+        user_roster: None,
     };
     
     let config = SimulationConfig::from_cli_args(args).unwrap();
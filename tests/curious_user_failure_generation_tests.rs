@@ -16,13 +16,15 @@ use chrono::{Duration, Utc};
 #[test]
 fn test_curious_user_generates_expected_failure_rate() {
     // Create a test configuration with 5% curious users
-    let mut config = SimulationConfig::default();
-    config.curious_user_percentage = 0.05;
-    config.location_count = 1;
-    config.min_buildings_per_location = 1;
-    config.max_buildings_per_location = 1;
-    config.min_rooms_per_building = 10;
-    config.max_rooms_per_building = 10;
+    let config = SimulationConfig {
+        curious_user_percentage: 0.05,
+        location_count: 1,
+        min_buildings_per_location: 1,
+        max_buildings_per_location: 1,
+        min_rooms_per_building: 10,
+        max_rooms_per_building: 10,
+        ..Default::default()
+    };
 
     // Create location registry with actual facilities
     let mut facility_generator = FacilityGenerator::new();
@@ -115,12 +117,14 @@ fn test_curious_user_generates_expected_failure_rate() {
 #[test]
 fn test_non_curious_user_generates_no_curious_events() {
     // Create a test configuration
-    let mut config = SimulationConfig::default();
-    config.location_count = 1;
-    config.min_buildings_per_location = 1;
-    config.max_buildings_per_location = 1;
-    config.min_rooms_per_building = 10;
-    config.max_rooms_per_building = 10;
+    let config = SimulationConfig {
+        location_count: 1,
+        min_buildings_per_location: 1,
+        max_buildings_per_location: 1,
+        min_rooms_per_building: 10,
+        max_rooms_per_building: 10,
+        ..Default::default()
+    };
 
     // Create location registry with actual facilities
     let mut facility_generator = FacilityGenerator::new();
@@ -189,12 +193,14 @@ fn test_non_curious_user_generates_no_curious_events() {
 #[test]
 fn test_curious_users_generate_daily_attempts() {
     // Create a test configuration
-    let mut config = SimulationConfig::default();
-    config.location_count = 1;
-    config.min_buildings_per_location = 1;
-    config.max_buildings_per_location = 1;
-    config.min_rooms_per_building = 10;
-    config.max_rooms_per_building = 10;
+    let config = SimulationConfig {
+        location_count: 1,
+        min_buildings_per_location: 1,
+        max_buildings_per_location: 1,
+        min_rooms_per_building: 10,
+        max_rooms_per_building: 10,
+        ..Default::default()
+    };
 
     // Create location registry with actual facilities
     let mut facility_generator = FacilityGenerator::new();
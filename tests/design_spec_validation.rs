@@ -94,15 +94,16 @@ fn test_design_spec_compliance() {
     println!("\n✅ 5. Time Management");
 
     let time_manager = TimeManager::new();
-    let current_time = time_manager.current_simulated_time();
+    let _ = time_manager.current_simulated_time();
 
-    // Test business hours detection (using simple hour values)
+    // Test business hours detection on a weekday (weekends have no business hours)
+    let current_time = chrono::TimeZone::with_ymd_and_hms(&chrono::Utc, 2024, 1, 10, 0, 0, 0).unwrap();
     let business_hour_time = current_time.with_hour(14).unwrap();
     let non_business_hour_time = current_time.with_hour(22).unwrap();
 
-    assert!(time_manager.is_business_hours(business_hour_time), "Should detect business hours");
+    assert!(time_manager.is_business_hours(business_hour_time, chrono_tz::Tz::UTC), "Should detect business hours");
     assert!(
-        !time_manager.is_business_hours(non_business_hour_time),
+        !time_manager.is_business_hours(non_business_hour_time, chrono_tz::Tz::UTC),
         "Should detect non-business hours"
     );
     println!("   ✓ Time acceleration working");
@@ -117,7 +118,7 @@ fn test_design_spec_compliance() {
 
     assert_eq!(access_event.user_id, user.id, "Event has correct user ID");
     assert_eq!(access_event.room_id, room_id, "Event has correct room ID");
-    assert_eq!(access_event.success, true, "Event success status correct");
+    assert!(access_event.success, "Event success status correct");
     println!("   ✓ Access event generation working");
 
     // 7. Simulation Orchestrator
@@ -119,7 +119,7 @@ fn test_event_ordering_across_day_boundaries() {
     );
     
     // Create unsorted list
-    let mut events = vec![event3, event1, event4, event2];
+    let mut events = [event3, event1, event4, event2];
     
     // Sort by timestamp (this is what the batch generator should do)
     events.sort_by_key(|event| event.timestamp);
@@ -238,7 +238,7 @@ fn test_event_ordering_with_same_timestamps() {
         EventType::Success,
     );
     
-    let mut events = vec![event1, event2, event3];
+    let mut events = [event1, event2, event3];
     
     // Sort by timestamp (stable sort should preserve relative order for same timestamps)
     events.sort_by_key(|event| event.timestamp);
@@ -293,7 +293,7 @@ fn test_event_ordering_microsecond_precision() {
         EventType::Success,
     );
     
-    let mut events = vec![event1, event2, event3];
+    let mut events = [event1, event2, event3];
     
     // Sort by timestamp
     events.sort_by_key(|event| event.timestamp);
@@ -348,7 +348,7 @@ fn test_event_ordering_with_night_shift() {
         EventType::Success,
     );
     
-    let mut events = vec![midnight_event, early_morning_event, evening_event];
+    let mut events = [midnight_event, early_morning_event, evening_event];
     
     // Sort by timestamp
     events.sort_by_key(|event| event.timestamp);
@@ -66,7 +66,7 @@ fn test_night_shift_user_complete_lifecycle() {
 
     // Verify each building has 1-3 night-shift users
     let _total_buildings = location_registry.total_building_count();
-    assert!(building_night_shift_counts.len() > 0, "No buildings have night-shift users");
+    assert!(!building_night_shift_counts.is_empty(), "No buildings have night-shift users");
     
     for (building_id, count) in &building_night_shift_counts {
         assert!(*count >= 1 && *count <= 3, 
@@ -37,11 +37,13 @@ fn test_orchestrator_statistics_initialization() {
 /// Test orchestrator initialization with real facility and user data
 #[test]
 fn test_orchestrator_initialization_with_real_data() {
-    let mut config = SimulationConfig::default();
-    config.user_count = 10;
-    config.location_count = 2;
-    config.curious_user_percentage = 0.2; // 20% curious
-    config.cloned_badge_percentage = 0.1; // 10% cloned badges
+    let config = SimulationConfig {
+        user_count: 10,
+        location_count: 2,
+        curious_user_percentage: 0.2, // 20% curious
+        cloned_badge_percentage: 0.1, // 10% cloned badges
+        ..Default::default()
+    };
 
     // Generate facilities
     let mut facility_generator = FacilityGenerator::new();
@@ -320,11 +322,13 @@ fn test_statistics_consistency() {
 /// Test complete application statistics output formatting
 #[test]
 fn test_complete_application_statistics_output() {
-    let mut config = SimulationConfig::default();
-    config.user_count = 100;
-    config.location_count = 3;
-    config.curious_user_percentage = 0.1; // 10% curious
-    config.cloned_badge_percentage = 0.02; // 2% cloned badges
+    let config = SimulationConfig {
+        user_count: 100,
+        location_count: 3,
+        curious_user_percentage: 0.1, // 10% curious
+        cloned_badge_percentage: 0.02, // 2% cloned badges
+        ..Default::default()
+    };
 
     // Generate facilities
     let mut facility_generator = FacilityGenerator::new();
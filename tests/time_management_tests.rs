@@ -3,7 +3,8 @@
 //! Tests Requirements: 6.1, 6.2, 6.3, 6.4, 6.5
 
 use amzn_career_pathway_activity_rust::*;
-use chrono::{Duration, TimeZone, Timelike, Utc};
+use chrono::{Duration, NaiveDate, TimeZone, Timelike, Utc};
+use chrono_tz::Tz;
 use rand::thread_rng;
 use std::thread;
 use std::time::Duration as StdDuration;
@@ -40,7 +41,7 @@ fn test_time_manager_invalid_acceleration() {
 fn test_business_hours_detection() {
     let tm = TimeManager::default();
     
-    // Test business hours (9 AM - 5 PM, every day)
+    // Test business hours (9 AM - 5 PM on work days)
     let monday_10am = Utc.with_ymd_and_hms(2024, 1, 8, 10, 0, 0).unwrap(); // Monday
     let tuesday_2pm = Utc.with_ymd_and_hms(2024, 1, 9, 14, 0, 0).unwrap(); // Tuesday
     let wednesday_noon = Utc.with_ymd_and_hms(2024, 1, 10, 12, 0, 0).unwrap(); // Wednesday
@@ -49,13 +50,13 @@ fn test_business_hours_detection() {
     let saturday_10am = Utc.with_ymd_and_hms(2024, 1, 13, 10, 0, 0).unwrap(); // Saturday
     let sunday_2pm = Utc.with_ymd_and_hms(2024, 1, 14, 14, 0, 0).unwrap(); // Sunday
     
-    assert!(tm.is_business_hours(monday_10am));
-    assert!(tm.is_business_hours(tuesday_2pm));
-    assert!(tm.is_business_hours(wednesday_noon));
-    assert!(tm.is_business_hours(thursday_4pm));
-    assert!(tm.is_business_hours(friday_2pm));
-    assert!(tm.is_business_hours(saturday_10am)); // Saturday is now a work day
-    assert!(tm.is_business_hours(sunday_2pm)); // Sunday is now a work day
+    assert!(tm.is_business_hours(monday_10am, Tz::UTC));
+    assert!(tm.is_business_hours(tuesday_2pm, Tz::UTC));
+    assert!(tm.is_business_hours(wednesday_noon, Tz::UTC));
+    assert!(tm.is_business_hours(thursday_4pm, Tz::UTC));
+    assert!(tm.is_business_hours(friday_2pm, Tz::UTC));
+    assert!(!tm.is_business_hours(saturday_10am, Tz::UTC)); // Weekends have no business hours
+    assert!(!tm.is_business_hours(sunday_2pm, Tz::UTC));
 }
 
 /// Test non-business hours detection
@@ -70,11 +71,11 @@ fn test_non_business_hours_detection() {
     let saturday_8am = Utc.with_ymd_and_hms(2024, 1, 13, 8, 0, 0).unwrap(); // Saturday before business hours
     let sunday_6pm = Utc.with_ymd_and_hms(2024, 1, 14, 18, 0, 0).unwrap(); // Sunday after business hours
     
-    assert!(!tm.is_business_hours(monday_8am));
-    assert!(!tm.is_business_hours(monday_6pm));
-    assert!(!tm.is_business_hours(monday_midnight));
-    assert!(!tm.is_business_hours(saturday_8am)); // Outside business hours
-    assert!(!tm.is_business_hours(sunday_6pm)); // Outside business hours
+    assert!(!tm.is_business_hours(monday_8am, Tz::UTC));
+    assert!(!tm.is_business_hours(monday_6pm, Tz::UTC));
+    assert!(!tm.is_business_hours(monday_midnight, Tz::UTC));
+    assert!(!tm.is_business_hours(saturday_8am, Tz::UTC)); // Outside business hours
+    assert!(!tm.is_business_hours(sunday_6pm, Tz::UTC)); // Outside business hours
 }
 
 
@@ -209,33 +210,51 @@ fn test_travel_time_no_previous_room() {
 
 
 
+/// Test that holidays and half-days from the work calendar apply
+#[test]
+fn test_holiday_and_half_day_business_hours() {
+    let calendar = WorkCalendarConfig {
+        holidays: vec![NaiveDate::from_ymd_opt(2024, 12, 25).unwrap()],
+        half_days: vec![NaiveDate::from_ymd_opt(2024, 12, 24).unwrap()],
+        ..Default::default()
+    };
+    let tm = TimeManager::new().with_calendar(WorkCalendar::new(calendar));
+
+    assert!(!tm.is_business_hours(Utc.with_ymd_and_hms(2024, 12, 25, 10, 0, 0).unwrap(), Tz::UTC));
+    assert!(tm.is_business_hours(Utc.with_ymd_and_hms(2024, 12, 24, 10, 0, 0).unwrap(), Tz::UTC));
+    assert!(!tm.is_business_hours(Utc.with_ymd_and_hms(2024, 12, 24, 14, 0, 0).unwrap(), Tz::UTC));
+    assert!(tm.is_business_hours(Utc.with_ymd_and_hms(2024, 12, 23, 14, 0, 0).unwrap(), Tz::UTC));
+}
+
 /// Test business hours edge cases
 #[test]
 fn test_business_hours_edge_cases() {
     let tm = TimeManager::default();
     
-    // Test exact boundary times (works for any day of the week)
+    // Test exact boundary times on a work day
     let monday_9am = Utc.with_ymd_and_hms(2024, 1, 8, 9, 0, 0).unwrap(); // Start of business hours
     let monday_5pm = Utc.with_ymd_and_hms(2024, 1, 8, 17, 0, 0).unwrap(); // End of business hours
     let monday_859am = Utc.with_ymd_and_hms(2024, 1, 8, 8, 59, 0).unwrap(); // Just before
     let monday_501pm = Utc.with_ymd_and_hms(2024, 1, 8, 17, 1, 0).unwrap(); // Just after
-    let saturday_9am = Utc.with_ymd_and_hms(2024, 1, 13, 9, 0, 0).unwrap(); // Saturday start of business hours
-    let sunday_4pm = Utc.with_ymd_and_hms(2024, 1, 14, 16, 0, 0).unwrap(); // Sunday during business hours
-    
-    assert!(tm.is_business_hours(monday_9am));
-    assert!(!tm.is_business_hours(monday_5pm)); // 5 PM is not included (9-17 exclusive)
-    assert!(!tm.is_business_hours(monday_859am));
-    assert!(!tm.is_business_hours(monday_501pm));
-    assert!(tm.is_business_hours(saturday_9am)); // Saturday is now a work day
-    assert!(tm.is_business_hours(sunday_4pm)); // Sunday is now a work day
+    let saturday_9am = Utc.with_ymd_and_hms(2024, 1, 13, 9, 0, 0).unwrap(); // Saturday, start of business hours on a work day
+    let sunday_4pm = Utc.with_ymd_and_hms(2024, 1, 14, 16, 0, 0).unwrap(); // Sunday, during business hours on a work day
+    
+    assert!(tm.is_business_hours(monday_9am, Tz::UTC));
+    assert!(!tm.is_business_hours(monday_5pm, Tz::UTC)); // 5 PM is not included (9-17 exclusive)
+    assert!(!tm.is_business_hours(monday_859am, Tz::UTC));
+    assert!(!tm.is_business_hours(monday_501pm, Tz::UTC));
+    assert!(!tm.is_business_hours(saturday_9am, Tz::UTC)); // Weekends have no business hours
+    assert!(!tm.is_business_hours(sunday_4pm, Tz::UTC));
 }
 
 /// Test business hours for all days of the week
 #[test]
 fn test_all_days_business_hours() {
-    let tm = TimeManager::default();
+    // A calendar without weekend days makes every day of the week a work day
+    let calendar = WorkCalendarConfig { weekend_days: Vec::new(), ..Default::default() };
+    let tm = TimeManager::new().with_calendar(WorkCalendar::new(calendar));
     
-    // Test all days of the week (January 8-14, 2024) - all are work days now
+    // Test all days of the week (January 8-14, 2024)
     let days = vec![
         Utc.with_ymd_and_hms(2024, 1, 8, 10, 0, 0).unwrap(), // Monday
         Utc.with_ymd_and_hms(2024, 1, 9, 10, 0, 0).unwrap(), // Tuesday
@@ -248,13 +267,13 @@ fn test_all_days_business_hours() {
     
     for date in days {
         // Business hours should be true for all days during 9-17
-        assert!(tm.is_business_hours(date));
+        assert!(tm.is_business_hours(date, Tz::UTC));
         
         // Test non-business hours for the same days
         let early_morning = date.with_hour(8).unwrap();
         let evening = date.with_hour(18).unwrap();
-        assert!(!tm.is_business_hours(early_morning));
-        assert!(!tm.is_business_hours(evening));
+        assert!(!tm.is_business_hours(early_morning, Tz::UTC));
+        assert!(!tm.is_business_hours(evening, Tz::UTC));
     }
 }
 
@@ -323,18 +323,15 @@ fn test_behavior_profile_characteristics() {
     assert!(curious_profile.curiosity_level > 0.5);
     
     // Test social behavior
-    let mut social_profile = BehaviorProfile::default();
-    social_profile.social_level = 0.8;
+    let social_profile = BehaviorProfile { social_level: 0.8, ..Default::default() };
     assert!(social_profile.is_social());
     
     // Test schedule-focused behavior
-    let mut schedule_profile = BehaviorProfile::default();
-    schedule_profile.schedule_adherence = 0.9;
+    let schedule_profile = BehaviorProfile { schedule_adherence: 0.9, ..Default::default() };
     assert!(schedule_profile.is_schedule_focused());
     
     // Test travel behavior
-    let mut travel_profile = BehaviorProfile::default();
-    travel_profile.travel_frequency = 0.3;
+    let travel_profile = BehaviorProfile { travel_frequency: 0.3, ..Default::default() };
     assert!(travel_profile.travel_frequency > 0.2);
 }
 